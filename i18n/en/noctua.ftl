@@ -30,6 +30,8 @@ menu-view-flip-horizontal = Flip Horizontally
 menu-view-flip-vertical = Flip Vertically
 menu-view-rotate-cw = Rotate Clockwise
 menu-view-rotate-ccw = Rotate Counter-Clockwise
+menu-properties = Properties
+menu-open-recent = Open Recent
 
 
 ## Tooltips (for buttons and icons)
@@ -44,13 +46,16 @@ tooltip-rotate-cw = Rotate clockwise
 tooltip-flip-horizontal = Flip horizontally
 tooltip-flip-vertical = Flip vertically
 tooltip-info-panel = Toggle info panel
+tooltip-settings-panel = Toggle settings panel
 
 
 ## Footer / Status bar
 status-zoom-fit = Fit
+status-zoom-fit-height = Fit height
 status-zoom-percent = { $percent }%
 status-doc-dimensions = { $width } × { $height }
 status-nav-position = { $current } / { $total }
+status-page-position = Page { $label } ({ $current } / { $total })
 status-separator =  | 
 
 
@@ -67,14 +72,38 @@ label-page = Page
 label-pages = Pages
 
 
+## Crop aspect ratios
+crop-aspect-free = Free
+crop-aspect-original = Original
+crop-aspect-screen = Screen
+
+
+## Measure tool
+measure-hint = Click two points to measure
+levels-hint-white = Click a point that should be white
+levels-hint-black = Now click a point that should be black
+measure-distance = { $pixels } px
+measure-distance-physical = { $pixels } px ({ $millimeters } mm)
+
+
 ## Loading states
+loading-document = Loading…
 loading-metadata = Loading metadata…
 loading-thumbnails = Loading { $current } / { $total }…
 
 
 ## Error messages
 error-failed-to-open = Failed to open "{ $path }"
+error-cannot-open = Cannot open this file
 error-unsupported-format = Unsupported file format
+error-unsupported-file = "{ $name }" is in a format Noctua cannot read
+error-corrupt = "{ $name }" is damaged or incomplete. Try copying or downloading it again.
+error-permission-denied = You do not have permission to read "{ $name }". Check the file permissions.
+error-invalid-svg = "{ $name }" is not a valid SVG file
+error-oversized = "{ $name }" is very large ({ $width } × { $height }) and needs about { $size } of memory to open
+action-open-anyway = Open Anyway
+error-pdf-unavailable = PDF support is not available. Install Poppler (poppler-glib) and build Noctua with the "portable" feature to view PDF files.
+error-djvu-unavailable = DjVu support needs the djvulibre tools (ddjvu, djvused). Install djvulibre to view DjVu files.
 error-no-image-loaded = No image loaded
 
 
@@ -85,13 +114,24 @@ panel-actions = Actions
 meta-section-file = File Information
 meta-section-exif = Camera Information
 meta-section-image = Image Information
+meta-section-description = Description
 
 ## File metadata
 meta-filename = Name
 meta-format = Format
 meta-dimensions = Dimensions
+meta-aspect-ratio = Aspect Ratio
+meta-megapixels = Megapixels
 meta-filesize = Size
 meta-colortype = Color Type
+meta-display-size = Displayed at
+meta-frames = Frames
+badge-animated = Animated · { $frames } frames
+meta-transform = Transform
+transform-none = None
+transform-rotated = Rotated { $degrees }°
+transform-flipped-h = Flipped horizontally
+transform-flipped-v = Flipped vertically
 meta-path = Path
 meta-pages = Pages
 meta-current-page = Current Page
@@ -108,14 +148,108 @@ meta-exposure = Exposure
 meta-aperture = Aperture
 meta-iso = ISO { $iso }
 meta-focal = Focal Length
+meta-lens = Lens
+meta-exposure-program = Exposure Program
+meta-metering = Metering Mode
+meta-flash = Flash
+meta-white-balance = White Balance
+meta-orientation = Orientation
 meta-gps = GPS Location
 
+## IPTC / XMP metadata
+meta-title = Title
+meta-description = Description
+meta-keywords = Keywords
+meta-creator = Creator
+meta-copyright = Copyright
+meta-rating = Rating
+meta-rejected = Rejected
+meta-label = Label
+meta-from-sidecar = { $value } (sidecar)
+
 ## Action buttons
 action-set-wallpaper = Set as Wallpaper
+action-crop-wallpaper = Crop as Wallpaper
+action-copy-metadata = Copy Metadata
+action-export-metadata-json = Save Metadata as JSON
 action-open-with = Open With…
 action-show-in-folder = Show in Folder
+action-print = Print
+action-compare-next = Compare with Next
+action-close-compare = Close Comparison
+compare-sync-views = Sync zoom and pan
+action-export-png = Export as PNG…
+
+## Export dialog
+export-png-title = Export as PNG
+export-png-body = The image is scaled to fit within the given size and saved next to the original.
+export-width = Width
+export-height = Height
+export-action = Export
+export-cancel = Cancel
+
+
+## Settings panel
+panel-settings = Settings
+settings-section-view = View
+settings-lock-view = Keep zoom and position when navigating
+settings-inertial-panning = Inertial panning
+settings-fit-upscale = Enlarge small images to fit the window
+settings-pan-step = Arrow key pan distance
+settings-pan-step-pixels = { $pixels } px
+settings-zoom-sensitivity = Mouse wheel zoom step
+settings-zoom-sensitivity-percent = { $percent } %
+settings-thumbnail-width = Page thumbnail width
+settings-nav-panel-width = Page panel width
+settings-pixels = { $pixels } px
+settings-filter = Image smoothing
+settings-filter-linear = Smooth
+settings-filter-nearest = Pixelated
+settings-filter-auto = Pixelated when zoomed in
+settings-crop-grid = Crop guide
+settings-crop-grid-none = None
+settings-crop-grid-thirds = Rule of thirds
+settings-crop-grid-golden = Golden ratio
+settings-background = Background
+settings-background-theme = Theme
+settings-background-black = Black
+settings-background-white = White
+settings-background-gray = Gray
+settings-background-custom = Custom
+settings-background-color = Background color
+settings-svg-system-fonts = Use system fonts for SVG text
+settings-pdf-transparent = Transparent PDF page background
+settings-section-navigation = Navigation
+settings-sort-by-date = Sort by capture date
+settings-show-hidden = Show hidden files
+settings-cross-folder = Continue into neighboring folders
+settings-restore-session = Reopen last file on startup
+settings-default-folder = Default folder
+settings-default-folder-none = None
+action-set-default-folder = Set Current Folder as Default
+settings-section-files = File Operations
+settings-transfer-dir = Destination folder
+settings-transfer-dir-placeholder = Folder path
+action-copy-to-folder = Copy to Folder
+action-move-to-folder = Move to Folder
+settings-section-editor = External Editor
+settings-external-editor = Editor command
+settings-external-editor-placeholder = e.g. gimp
+settings-section-cache = Thumbnail Cache
+settings-cache-size = Cache size
+settings-cache-freed = Freed { $size }
+action-clear-cache = Clear Cache
 
 
 ## Navigation panel (thumbnails)
 nav-panel-title = Pages
 nav-panel-loading = Loading { $current } / { $total }…
+
+
+## Debug overlay
+debug-kind = Kind: { $kind }
+debug-dimensions = Native size: { $width } × { $height }
+debug-scale = Scale: { $scale }
+debug-pan = Pan: { $x }, { $y }
+debug-decode-time = Decode time: { $time }
+debug-thumbnails = Thumbnails: { $loaded } / { $total }
@@ -0,0 +1,188 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// src/app/document/archive.rs
+//
+// Zip and tar archives of images, browsed like a folder without extracting.
+//
+// An entry is addressed by a virtual path: the archive path joined with the
+// entry name (e.g. `comics/issue1.cbz/pages/001.png`).
+
+use std::path::{Component, Path, PathBuf};
+
+use super::DocumentKind;
+
+/// Archive file extensions, lowercase and without the dot.
+const ZIP_EXTENSIONS: &[&str] = &["zip", "cbz"];
+const TAR_EXTENSIONS: &[&str] = &["tar", "cbt"];
+
+/// Check whether `path` names a supported archive (by extension).
+pub fn is_archive(path: &Path) -> bool {
+    cfg!(feature = "archive") && archive_format(path).is_some()
+}
+
+/// Split a virtual path into the archive file and the entry name inside it.
+///
+/// Returns None for ordinary paths.
+pub fn split(path: &Path) -> Option<(&Path, String)> {
+    path.ancestors()
+        .skip(1)
+        .find(|ancestor| is_archive(ancestor) && ancestor.is_file())
+        .and_then(|archive| {
+            let entry = path.strip_prefix(archive).ok()?;
+            Some((archive, entry_name(entry)?))
+        })
+}
+
+/// Virtual paths of the image entries in `archive`, in archive order.
+pub fn list_images(archive: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let entries = match archive_format(archive) {
+        Some(Format::Zip) => backend::zip_entries(archive)?,
+        Some(Format::Tar) => backend::tar_entries(archive)?,
+        None => anyhow::bail!("Not an archive: {}", archive.display()),
+    };
+
+    Ok(entries
+        .into_iter()
+        .filter_map(|name| entry_name(Path::new(&name)))
+        .map(|name| archive.join(name))
+        .filter(|path| DocumentKind::from_path(path) == Some(DocumentKind::Raster))
+        .collect())
+}
+
+/// Read the bytes of one entry.
+pub fn read_entry(archive: &Path, entry: &str) -> anyhow::Result<Vec<u8>> {
+    match archive_format(archive) {
+        Some(Format::Zip) => backend::zip_read(archive, entry),
+        Some(Format::Tar) => backend::tar_read(archive, entry),
+        None => anyhow::bail!("Not an archive: {}", archive.display()),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Format {
+    Zip,
+    Tar,
+}
+
+fn archive_format(path: &Path) -> Option<Format> {
+    let ext = path.extension()?.to_str()?.to_lowercase();
+    if ZIP_EXTENSIONS.contains(&ext.as_str()) {
+        Some(Format::Zip)
+    } else if TAR_EXTENSIONS.contains(&ext.as_str()) {
+        Some(Format::Tar)
+    } else {
+        None
+    }
+}
+
+/// Normalized entry name (`/`-separated, no `./`).
+///
+/// Names that could point outside the archive (`..`, absolute) are rejected.
+fn entry_name(path: &Path) -> Option<String> {
+    let mut parts = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_str()?),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+#[cfg(feature = "archive")]
+mod backend {
+    use std::fs::File;
+    use std::io::Read;
+    use std::path::Path;
+
+    use anyhow::Context;
+
+    /// Largest entry read into memory, whatever size its header declares.
+    const MAX_ENTRY_BYTES: u64 = 1 << 30;
+
+    /// Names of the regular files in a zip archive.
+    pub fn zip_entries(archive: &Path) -> anyhow::Result<Vec<String>> {
+        let mut zip = zip::ZipArchive::new(File::open(archive)?)?;
+        let mut names = Vec::with_capacity(zip.len());
+        for index in 0..zip.len() {
+            let file = zip.by_index(index)?;
+            if file.is_file() {
+                names.push(file.name().to_string());
+            }
+        }
+        Ok(names)
+    }
+
+    pub fn zip_read(archive: &Path, entry: &str) -> anyhow::Result<Vec<u8>> {
+        let mut zip = zip::ZipArchive::new(File::open(archive)?)?;
+        // Stored names may carry a `./` prefix the entry name dropped.
+        let index = (0..zip.len())
+            .find(|&index| {
+                zip.name_for_index(index)
+                    .and_then(|name| super::entry_name(Path::new(name)))
+                    .as_deref()
+                    == Some(entry)
+            })
+            .with_context(|| format!("{entry} not found in {}", archive.display()))?;
+
+        read_limited(zip.by_index(index)?, entry)
+    }
+
+    /// Names of the regular files in a tar archive.
+    pub fn tar_entries(archive: &Path) -> anyhow::Result<Vec<String>> {
+        let mut tar = tar::Archive::new(File::open(archive)?);
+        let mut names = Vec::new();
+        for file in tar.entries()? {
+            let file = file?;
+            if file.header().entry_type().is_file() {
+                names.push(file.path()?.to_string_lossy().into_owned());
+            }
+        }
+        Ok(names)
+    }
+
+    pub fn tar_read(archive: &Path, entry: &str) -> anyhow::Result<Vec<u8>> {
+        let mut tar = tar::Archive::new(File::open(archive)?);
+        for file in tar.entries()? {
+            let file = file?;
+            if super::entry_name(&file.path()?).as_deref() == Some(entry) {
+                return read_limited(file, entry);
+            }
+        }
+        anyhow::bail!("{entry} not found in {}", archive.display())
+    }
+
+    /// Read an entry, refusing more than `MAX_ENTRY_BYTES` of data.
+    ///
+    /// Sizes in archive headers are untrusted, so nothing is pre-allocated.
+    fn read_limited(reader: impl Read, entry: &str) -> anyhow::Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        reader.take(MAX_ENTRY_BYTES + 1).read_to_end(&mut bytes)?;
+        if u64::try_from(bytes.len()).unwrap_or(u64::MAX) > MAX_ENTRY_BYTES {
+            anyhow::bail!("{entry} is larger than {} MiB", MAX_ENTRY_BYTES >> 20);
+        }
+        Ok(bytes)
+    }
+}
+
+/// Archive support was left out of this build; `is_archive` is always false.
+#[cfg(not(feature = "archive"))]
+mod backend {
+    use std::path::Path;
+
+    pub fn zip_entries(_archive: &Path) -> anyhow::Result<Vec<String>> {
+        anyhow::bail!("Archive support is not available in this build")
+    }
+
+    pub fn zip_read(_archive: &Path, _entry: &str) -> anyhow::Result<Vec<u8>> {
+        anyhow::bail!("Archive support is not available in this build")
+    }
+
+    pub fn tar_entries(_archive: &Path) -> anyhow::Result<Vec<String>> {
+        anyhow::bail!("Archive support is not available in this build")
+    }
+
+    pub fn tar_read(_archive: &Path, _entry: &str) -> anyhow::Result<Vec<u8>> {
+        anyhow::bail!("Archive support is not available in this build")
+    }
+}
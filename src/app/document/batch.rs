@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// src/app/document/batch.rs
+//
+// Headless thumbnail generation to pre-warm the disk cache.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{DocumentKind, file};
+
+/// Generate cached thumbnails for every supported document below `dir`.
+///
+/// Multi-page documents get a thumbnail per page, other documents one for
+/// the gallery. Progress is reported on stdout.
+pub fn generate_thumbnails(dir: &Path) -> anyhow::Result<()> {
+    if !dir.is_dir() {
+        return Err(anyhow::anyhow!("Not a directory: {}", dir.display()));
+    }
+
+    let mut paths = Vec::new();
+    collect_documents(dir, &mut paths);
+    paths.sort();
+
+    let total = paths.len();
+    let mut failed = 0;
+
+    for (index, path) in paths.iter().enumerate() {
+        println!("[{}/{total}] {}", index + 1, path.display());
+        if let Err(e) = generate_for(path) {
+            eprintln!("  failed: {e}");
+            failed += 1;
+        }
+    }
+
+    println!("Processed {total} documents ({failed} failed)");
+    Ok(())
+}
+
+/// Populate the cache for a single document.
+fn generate_for(path: &Path) -> anyhow::Result<()> {
+    match DocumentKind::from_path(path) {
+        Some(DocumentKind::Portable | DocumentKind::Djvu) => {
+            file::open_document(path)?.generate_thumbnails();
+            Ok(())
+        }
+        // Vector graphics are rendered on demand and have no cached thumbnail.
+        Some(DocumentKind::Vector) => Ok(()),
+        _ => super::file_thumbnail(path)
+            .map(|_| ())
+            .ok_or_else(|| anyhow::anyhow!("No thumbnail available")),
+    }
+}
+
+/// Recursively collect supported documents.
+fn collect_documents(dir: &Path, paths: &mut Vec<PathBuf>) {
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        log::warn!("Failed to read directory: {}", dir.display());
+        return;
+    };
+
+    for entry in read_dir.flatten() {
+        let path = entry.path();
+        // Symlinked directories are not followed to avoid cycles.
+        if entry.file_type().is_ok_and(|t| t.is_dir()) {
+            collect_documents(&path, paths);
+        } else if DocumentKind::from_path(&path).is_some() {
+            paths.push(path);
+        }
+    }
+}
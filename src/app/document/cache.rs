@@ -2,16 +2,21 @@
 // src/app/document/cache.rs
 //
 // Disk cache for document thumbnails stored in ~/.cache/noctua/
+//
+// Thumbnails always show the page as stored in the file. Rotations and flips
+// are view state of the open document and never reach the cache, so they
+// are not part of the cache key.
 
 use std::fs;
 use std::io::BufWriter;
 use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use image::DynamicImage;
 use sha2::{Digest, Sha256};
 
 use super::ImageHandle;
-use crate::constant::{CACHE_DIR, THUMBNAIL_EXT};
+use crate::constant::{CACHE_DIR, PRINT_DIR, THUMBNAIL_EXT, WALLPAPER_DIR};
 
 /// Get the cache directory path (~/.cache/noctua/).
 fn cache_dir() -> Option<PathBuf> {
@@ -27,14 +32,16 @@ fn ensure_cache_dir() -> Option<PathBuf> {
 
 /// Generate a cache key from file path, modification time, and page number.
 /// Format: sha256(path + mtime + page)
+///
+/// The transform is deliberately not included: cached thumbnails are untransformed.
 fn cache_key(file_path: &Path, page: usize) -> Option<String> {
-    let metadata = fs::metadata(file_path).ok()?;
-    let mtime = metadata
-        .modified()
-        .ok()?
-        .duration_since(std::time::UNIX_EPOCH)
-        .ok()?
-        .as_secs();
+    let modified = fs::metadata(file_path).ok()?.modified().ok()?;
+    cache_key_for(file_path, modified, page)
+}
+
+/// Generate a cache key for an explicit modification time.
+fn cache_key_for(file_path: &Path, modified: SystemTime, page: usize) -> Option<String> {
+    let mtime = modified.duration_since(UNIX_EPOCH).ok()?.as_secs();
 
     let mut hasher = Sha256::new();
     hasher.update(file_path.to_string_lossy().as_bytes());
@@ -52,9 +59,22 @@ fn thumbnail_path(file_path: &Path, page: usize) -> Option<PathBuf> {
     Some(dir.join(format!("{key}.{THUMBNAIL_EXT}")))
 }
 
+/// Remove cached thumbnails of a file version identified by its old modification time.
+pub fn remove_thumbnails(file_path: &Path, modified: SystemTime, pages: usize) {
+    let Some(dir) = cache_dir() else {
+        return;
+    };
+    for page in 0..pages {
+        if let Some(key) = cache_key_for(file_path, modified, page) {
+            let _ = fs::remove_file(dir.join(format!("{key}.{THUMBNAIL_EXT}")));
+        }
+    }
+}
+
 /// Load a thumbnail from disk cache.
-/// Returns None if not cached or cache is invalid.
-pub fn load_thumbnail(file_path: &Path, page: usize) -> Option<ImageHandle> {
+/// Returns None if not cached, the cache is invalid or the cached thumbnail
+/// is narrower than `min_width` (it is then regenerated and overwritten).
+pub fn load_thumbnail(file_path: &Path, page: usize, min_width: u32) -> Option<ImageHandle> {
     let cache_path = thumbnail_path(file_path, page)?;
 
     log::debug!("Cache lookup: file={}, page={}", file_path.display(), page);
@@ -69,6 +89,15 @@ pub fn load_thumbnail(file_path: &Path, page: usize) -> Option<ImageHandle> {
     }
 
     let img = image::open(&cache_path).ok()?;
+    if img.width() < min_width {
+        log::debug!(
+            "Cached thumbnail too small: file={} page={} width={}",
+            file_path.display(),
+            page,
+            img.width()
+        );
+        return None;
+    }
     log::debug!(
         "Thumbnail loaded from cache: file={} page={}",
         file_path.display(),
@@ -124,13 +153,79 @@ pub fn has_thumbnail(file_path: &Path, page: usize) -> bool {
     thumbnail_path(file_path, page).is_some_and(|p| p.exists())
 }
 
+/// Compute the total size in bytes of all files in the cache directory.
+/// Returns 0 if the cache directory does not exist.
+pub fn cache_size() -> u64 {
+    let Some(dir) = cache_dir() else {
+        return 0;
+    };
+
+    let Ok(read_dir) = fs::read_dir(&dir) else {
+        return 0;
+    };
+
+    read_dir
+        .flatten()
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(fs::Metadata::is_file)
+        .map(|m| m.len())
+        .sum()
+}
+
 /// Clear all cached thumbnails.
-#[allow(dead_code)]
-pub fn clear_cache() -> std::io::Result<()> {
-    if let Some(dir) = cache_dir()
-        && dir.exists()
-    {
-        fs::remove_dir_all(&dir)?;
+/// Returns the number of bytes freed (0 if the cache directory does not exist).
+///
+/// Subdirectories are kept: the cropped wallpaper is still shown by the desktop.
+pub fn clear_cache() -> std::io::Result<u64> {
+    let freed = cache_size();
+    let Some(dir) = cache_dir() else {
+        return Ok(freed);
+    };
+    let Ok(read_dir) = fs::read_dir(&dir) else {
+        return Ok(freed);
+    };
+
+    for entry in read_dir.flatten() {
+        if entry.file_type().is_ok_and(|t| t.is_file()) {
+            fs::remove_file(entry.path())?;
+        }
+    }
+    Ok(freed)
+}
+
+/// Save a cropped wallpaper, replacing the previous one.
+///
+/// Each wallpaper gets a new file name, since desktops may not reload an
+/// image whose path did not change.
+pub fn save_wallpaper(image: &DynamicImage) -> Option<PathBuf> {
+    save_latest(WALLPAPER_DIR, "wallpaper", image)
+}
+
+/// Save an image for printing, replacing the previous one.
+pub fn save_print_image(image: &DynamicImage) -> Option<PathBuf> {
+    save_latest(PRINT_DIR, "print", image)
+}
+
+/// Save `image` as a PNG in the cache subdirectory `dir_name`, removing
+/// the files saved there before.
+fn save_latest(dir_name: &str, prefix: &str, image: &DynamicImage) -> Option<PathBuf> {
+    let dir = ensure_cache_dir()?.join(dir_name);
+    fs::create_dir_all(&dir).ok()?;
+
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .ok()?
+        .as_millis();
+    let path = dir.join(format!("{prefix}-{stamp}.png"));
+    image.save(&path).ok()?;
+
+    // Drop older files.
+    if let Ok(read_dir) = fs::read_dir(&dir) {
+        for entry in read_dir.flatten() {
+            if entry.path() != path {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
     }
-    Ok(())
+    Some(path)
 }
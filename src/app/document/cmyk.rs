@@ -0,0 +1,211 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// src/app/document/cmyk.rs
+//
+// CMYK JPEG detection and conversion to RGB.
+
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use image::{DynamicImage, RgbImage};
+use zune_jpeg::JpegDecoder;
+use zune_jpeg::zune_core::colorspace::ColorSpace;
+use zune_jpeg::zune_core::options::DecoderOptions;
+
+/// Color type shown in the properties panel for converted CMYK images.
+pub const CMYK_COLOR_TYPE: &str = "CMYK (converted)";
+
+/// Header facts needed to decode a CMYK JPEG.
+#[derive(Debug, Default, Clone, Copy)]
+struct JpegHeader {
+    /// Number of color components in the frame (4 for CMYK/YCCK).
+    components: u8,
+    /// An Adobe APP14 segment is present; Adobe writes CMYK inverted.
+    adobe: bool,
+}
+
+/// Check whether a JPEG file stores four color components (CMYK or YCCK).
+///
+/// Only walks the marker segments up to the frame header.
+pub fn is_cmyk_jpeg(path: &Path) -> bool {
+    File::open(path)
+        .and_then(|file| read_header(BufReader::new(file)))
+        .is_ok_and(|header| header.components == 4)
+}
+
+/// Decode a CMYK JPEG and convert it to RGB.
+///
+/// The `image` crate ignores the Adobe inversion, so the raw channels are
+/// decoded with zune-jpeg and converted here.
+pub fn decode(path: &Path) -> anyhow::Result<DynamicImage> {
+    let bytes = std::fs::read(path)?;
+    let header = read_header(std::io::Cursor::new(&bytes))?;
+
+    let options = DecoderOptions::default().jpeg_set_out_colorspace(ColorSpace::CMYK);
+    let mut decoder = JpegDecoder::new_with_options(bytes.as_slice(), options);
+    let pixels = decoder
+        .decode()
+        .map_err(|e| anyhow::anyhow!("Failed to decode CMYK JPEG: {e:?}"))?;
+    let (width, height) = decoder
+        .dimensions()
+        .ok_or_else(|| anyhow::anyhow!("CMYK JPEG has no dimensions"))?;
+
+    let rgb: Vec<u8> = pixels
+        .chunks_exact(4)
+        .flat_map(|cmyk| cmyk_to_rgb(cmyk, header.adobe))
+        .collect();
+
+    let image = RgbImage::from_raw(u32::try_from(width)?, u32::try_from(height)?, rgb)
+        .ok_or_else(|| anyhow::anyhow!("CMYK JPEG has unexpected size"))?;
+    Ok(DynamicImage::ImageRgb8(image))
+}
+
+/// Convert one CMYK pixel (naive, without a color profile).
+#[allow(clippy::cast_possible_truncation)]
+fn cmyk_to_rgb(cmyk: &[u8], inverted: bool) -> [u8; 3] {
+    // Ink amounts with 255 = full coverage.
+    let ink = |value: u8| if inverted { 255 - value } else { value };
+    let white = 255 - u16::from(ink(cmyk[3]));
+    let channel = |value: u8| ((255 - u16::from(ink(value))) * white / 255) as u8;
+    [channel(cmyk[0]), channel(cmyk[1]), channel(cmyk[2])]
+}
+
+/// Walk the JPEG marker segments until the frame header (SOFn).
+fn read_header<R: Read + Seek>(mut reader: R) -> std::io::Result<JpegHeader> {
+    let invalid = || std::io::Error::new(std::io::ErrorKind::InvalidData, "not a JPEG frame");
+
+    let mut soi = [0u8; 2];
+    reader.read_exact(&mut soi)?;
+    if soi != [0xFF, 0xD8] {
+        return Err(invalid());
+    }
+
+    let mut header = JpegHeader::default();
+    loop {
+        let mut marker = [0u8; 2];
+        reader.read_exact(&mut marker)?;
+        if marker[0] != 0xFF {
+            return Err(invalid());
+        }
+        // Fill bytes before a marker.
+        if marker[1] == 0xFF {
+            reader.seek(SeekFrom::Current(-1))?;
+            continue;
+        }
+
+        let mut length = [0u8; 2];
+        reader.read_exact(&mut length)?;
+        let length = u16::from_be_bytes(length);
+        if length < 2 {
+            return Err(invalid());
+        }
+        let payload = i64::from(length - 2);
+
+        match marker[1] {
+            // APP14: "Adobe" followed by version, flags and transform.
+            0xEE if payload >= 5 => {
+                let mut id = [0u8; 5];
+                reader.read_exact(&mut id)?;
+                header.adobe |= &id == b"Adobe";
+                reader.seek(SeekFrom::Current(payload - 5))?;
+            }
+            // SOFn (except DHT, JPG and DAC, which share the range).
+            0xC0..=0xCF if !matches!(marker[1], 0xC4 | 0xC8 | 0xCC) => {
+                // Precision (1), height (2), width (2), components (1).
+                let mut frame = [0u8; 6];
+                reader.read_exact(&mut frame)?;
+                header.components = frame[5];
+                return Ok(header);
+            }
+            // Start of scan without a frame header.
+            0xDA => return Err(invalid()),
+            _ => {
+                reader.seek(SeekFrom::Current(payload))?;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    /// JPEG markers up to a baseline frame header with `components` channels.
+    fn jpeg(segments: &[&[u8]], components: u8) -> Vec<u8> {
+        let mut bytes = vec![0xFF, 0xD8];
+        for segment in segments {
+            bytes.extend_from_slice(segment);
+        }
+        let length = 8 + 3 * u16::from(components);
+        bytes.extend_from_slice(&[0xFF, 0xC0]);
+        bytes.extend_from_slice(&length.to_be_bytes());
+        bytes.extend_from_slice(&[8, 0, 16, 0, 16, components]);
+        for id in 1..=components {
+            bytes.extend_from_slice(&[id, 0x11, 0]);
+        }
+        bytes
+    }
+
+    /// Adobe APP14: version 100, no flags, transform 2 (YCCK).
+    const ADOBE: &[u8] = &[
+        0xFF, 0xEE, 0, 14, b'A', b'd', b'o', b'b', b'e', 0, 100, 0, 0, 0, 0, 2,
+    ];
+    const JFIF: &[u8] = &[
+        0xFF, 0xE0, 0, 16, b'J', b'F', b'I', b'F', 0, 1, 1, 0, 0, 1, 0, 1, 0, 0,
+    ];
+
+    #[test]
+    fn adobe_cmyk_is_inverted() {
+        let header = read_header(Cursor::new(jpeg(&[ADOBE], 4))).unwrap();
+        assert_eq!(header.components, 4);
+        assert!(header.adobe);
+    }
+
+    #[test]
+    fn plain_cmyk_is_not_inverted() {
+        let header = read_header(Cursor::new(jpeg(&[JFIF], 4))).unwrap();
+        assert_eq!(header.components, 4);
+        assert!(!header.adobe);
+    }
+
+    #[test]
+    fn rgb_jpeg_has_three_components() {
+        let header = read_header(Cursor::new(jpeg(&[JFIF, ADOBE], 3))).unwrap();
+        assert_eq!(header.components, 3);
+        assert!(header.adobe);
+    }
+
+    #[test]
+    fn fill_bytes_before_marker() {
+        let header = read_header(Cursor::new(jpeg(&[&[0xFF], ADOBE], 4))).unwrap();
+        assert_eq!(header.components, 4);
+        assert!(header.adobe);
+    }
+
+    #[test]
+    fn rejects_non_jpeg() {
+        assert!(read_header(Cursor::new(b"\x89PNG\r\n\x1a\n".to_vec())).is_err());
+        // Scan data before any frame header.
+        let scan = [0xFF, 0xD8, 0xFF, 0xDA, 0, 2];
+        assert!(read_header(Cursor::new(scan.to_vec())).is_err());
+    }
+
+    #[test]
+    fn convert_plain_cmyk() {
+        assert_eq!(cmyk_to_rgb(&[0, 0, 0, 0], false), [255, 255, 255]);
+        assert_eq!(cmyk_to_rgb(&[0, 0, 0, 255], false), [0, 0, 0]);
+        assert_eq!(cmyk_to_rgb(&[255, 0, 0, 0], false), [0, 255, 255]);
+        assert_eq!(cmyk_to_rgb(&[0, 255, 255, 0], false), [255, 0, 0]);
+        assert_eq!(cmyk_to_rgb(&[0, 0, 0, 128], false), [127, 127, 127]);
+    }
+
+    #[test]
+    fn convert_inverted_cmyk() {
+        assert_eq!(cmyk_to_rgb(&[255, 255, 255, 255], true), [255, 255, 255]);
+        assert_eq!(cmyk_to_rgb(&[255, 255, 255, 0], true), [0, 0, 0]);
+        assert_eq!(cmyk_to_rgb(&[0, 255, 255, 255], true), [0, 255, 255]);
+        assert_eq!(cmyk_to_rgb(&[255, 0, 0, 255], true), [255, 0, 0]);
+    }
+}
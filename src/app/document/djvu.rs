@@ -0,0 +1,314 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// src/app/document/djvu.rs
+//
+// DjVu documents (scanned books) rendered via the djvulibre command-line tools.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use image::{imageops, DynamicImage, ImageFormat};
+
+use super::file::{Backend, OpenError};
+use super::{
+    cache, DocResult, DocumentInfo, FlipDirection, ImageHandle, MultiPage, MultiPageThumbnails,
+    Renderable, RenderOutput, Rotation, TransformState, Transformable,
+};
+use crate::constant::DJVU_THUMBNAIL_SIZE;
+
+/// Represents a DjVu document.
+pub struct DjvuDocument {
+    /// Path to the source file (rendered on demand by `ddjvu`).
+    source_path: PathBuf,
+    /// Total number of pages.
+    num_pages: usize,
+    /// Current page index (0-based).
+    page_index: usize,
+    /// Current transformation state.
+    transform: TransformState,
+    /// Current page as decoded (before transforms).
+    page_image: DynamicImage,
+    /// Current rendered page with transforms applied.
+    pub rendered: DynamicImage,
+    /// Image handle for display.
+    pub handle: ImageHandle,
+    /// Cached thumbnail handles for each page (None = not yet generated).
+    thumbnail_cache: Option<Vec<ImageHandle>>,
+}
+
+impl DjvuDocument {
+    /// Open a DjVu document and render the first page.
+    pub fn open(path: &Path) -> anyhow::Result<Self> {
+        let num_pages = Self::query_page_count(path)?;
+        if num_pages == 0 {
+            return Err(anyhow::anyhow!("DjVu document has no pages"));
+        }
+
+        let page_image = Self::render_page(path, 0, None)?;
+        let rendered = page_image.clone();
+        let handle = super::create_image_handle_from_image(&rendered);
+
+        Ok(Self {
+            source_path: path.to_path_buf(),
+            num_pages,
+            page_index: 0,
+            transform: TransformState::default(),
+            page_image,
+            rendered,
+            handle,
+            thumbnail_cache: None,
+        })
+    }
+
+    /// Query the number of pages with `djvused`.
+    fn query_page_count(path: &Path) -> anyhow::Result<usize> {
+        let output = Command::new("djvused")
+            .arg("-e")
+            .arg("n")
+            .arg(path)
+            .output()
+            .map_err(|e| tool_error("djvused", e))?;
+
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "Failed to parse DjVu: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse()
+            .map_err(|e| anyhow::anyhow!("Invalid DjVu page count: {e}"))
+    }
+
+    /// Render a page with `ddjvu`, optionally fitting into a square box of `size` pixels.
+    fn render_page(
+        path: &Path,
+        page_index: usize,
+        size: Option<u32>,
+    ) -> anyhow::Result<DynamicImage> {
+        let mut command = Command::new("ddjvu");
+        command
+            .arg("-format=ppm")
+            .arg(format!("-page={}", page_index + 1));
+        if let Some(size) = size {
+            command.arg(format!("-size={size}x{size}"));
+        }
+        let output = command
+            .arg(path)
+            .output()
+            .map_err(|e| tool_error("ddjvu", e))?;
+
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "Failed to render DjVu page {page_index}: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        image::load_from_memory_with_format(&output.stdout, ImageFormat::Pnm)
+            .map_err(|e| anyhow::anyhow!("Failed to decode DjVu page {page_index}: {e}"))
+    }
+
+    /// Get the number of thumbnails currently loaded.
+    pub fn thumbnails_loaded(&self) -> usize {
+        self.thumbnail_cache.as_ref().map_or(0, Vec::len)
+    }
+
+    /// Initialize thumbnail cache (empty, ready for incremental loading).
+    fn init_thumbnail_cache(&mut self) {
+        if self.thumbnail_cache.is_none() {
+            self.thumbnail_cache = Some(Vec::with_capacity(self.num_pages));
+        }
+    }
+
+    /// Generate a single thumbnail page. Returns the next page to generate, or None if done.
+    pub fn generate_thumbnail_page(&mut self, page: usize) -> Option<usize> {
+        self.init_thumbnail_cache();
+
+        let should_generate = {
+            let cache = self.thumbnail_cache.as_ref()?;
+            page >= cache.len() && page < self.num_pages
+        };
+
+        if should_generate {
+            let handle = self.load_or_generate_thumbnail(page);
+            if let Some(cache) = self.thumbnail_cache.as_mut() {
+                cache.push(handle);
+            }
+        }
+
+        let next = page + 1;
+        if next < self.num_pages {
+            Some(next)
+        } else {
+            None
+        }
+    }
+
+    /// Load thumbnail from cache or generate and cache it.
+    ///
+    /// Rendered without the current transform, as the cache key doesn't include it.
+    fn load_or_generate_thumbnail(&self, page: usize) -> ImageHandle {
+        let width = super::thumbnail_width();
+        if let Some(handle) = cache::load_thumbnail(&self.source_path, page, width) {
+            return handle;
+        }
+
+        let size = DJVU_THUMBNAIL_SIZE.max(width);
+        match Self::render_page(&self.source_path, page, Some(size)) {
+            Ok(img) => {
+                let _ = cache::save_thumbnail(&self.source_path, page, &img);
+                super::create_image_handle_from_image(&img)
+            }
+            Err(e) => {
+                log::warn!("Failed to generate thumbnail for page {page}: {e}");
+                ImageHandle::from_rgba(1, 1, vec![0, 0, 0, 0])
+            }
+        }
+    }
+
+    /// Re-apply the current transform to the decoded page.
+    fn apply_transform(&mut self) {
+        let mut image = self.page_image.clone();
+        if self.transform.flip_h {
+            image = DynamicImage::ImageRgba8(imageops::flip_horizontal(&image));
+        }
+        if self.transform.flip_v {
+            image = DynamicImage::ImageRgba8(imageops::flip_vertical(&image));
+        }
+        image = match self.transform.rotation {
+            Rotation::Cw90 => DynamicImage::ImageRgba8(imageops::rotate90(&image)),
+            Rotation::Cw180 => DynamicImage::ImageRgba8(imageops::rotate180(&image)),
+            Rotation::Cw270 => DynamicImage::ImageRgba8(imageops::rotate270(&image)),
+            Rotation::None => image,
+        };
+        self.rendered = image;
+        self.handle = super::create_image_handle_from_image(&self.rendered);
+    }
+
+    /// Returns the dimensions of the currently rendered page.
+    pub fn dimensions(&self) -> (u32, u32) {
+        (self.rendered.width(), self.rendered.height())
+    }
+
+    /// Extract metadata for this DjVu document.
+    pub fn extract_meta(&self, path: &Path) -> super::meta::DocumentMeta {
+        let (width, height) = self.dimensions();
+        #[allow(clippy::cast_possible_truncation)]
+        super::meta::build_djvu_meta(path, width, height, self.num_pages as u32)
+    }
+}
+
+/// Error for a djvulibre tool that failed to start.
+///
+/// A missing tool means djvulibre is not installed.
+fn tool_error(tool: &str, err: std::io::Error) -> anyhow::Error {
+    if err.kind() == std::io::ErrorKind::NotFound {
+        OpenError::MissingBackend(Backend::Djvulibre).into()
+    } else {
+        anyhow::anyhow!("Failed to run {tool}: {err}")
+    }
+}
+
+// ============================================================================
+// Trait Implementations
+// ============================================================================
+
+impl Renderable for DjvuDocument {
+    fn render(&mut self, _scale: f64) -> DocResult<RenderOutput> {
+        let (width, height) = self.dimensions();
+        Ok(RenderOutput {
+            handle: self.handle.clone(),
+            width,
+            height,
+        })
+    }
+
+    fn info(&self) -> DocumentInfo {
+        DocumentInfo {
+            width: self.page_image.width(),
+            height: self.page_image.height(),
+            format: "DjVu".to_string(),
+        }
+    }
+}
+
+impl Transformable for DjvuDocument {
+    fn rotate(&mut self, rotation: Rotation) {
+        self.transform.rotation = rotation;
+        self.apply_transform();
+    }
+
+    fn flip(&mut self, direction: FlipDirection) {
+        match direction {
+            FlipDirection::Horizontal => self.transform.flip_h = !self.transform.flip_h,
+            FlipDirection::Vertical => self.transform.flip_v = !self.transform.flip_v,
+        }
+        self.apply_transform();
+    }
+
+    fn transform_state(&self) -> TransformState {
+        self.transform
+    }
+}
+
+impl MultiPage for DjvuDocument {
+    fn page_count(&self) -> usize {
+        self.num_pages
+    }
+
+    fn current_page(&self) -> usize {
+        self.page_index
+    }
+
+    fn go_to_page(&mut self, page: usize) -> DocResult<()> {
+        if page >= self.num_pages {
+            return Err(anyhow::anyhow!(
+                "Page {} out of range (0-{})",
+                page,
+                self.num_pages - 1
+            ));
+        }
+        self.page_image = Self::render_page(&self.source_path, page, None)?;
+        self.page_index = page;
+        self.apply_transform();
+        Ok(())
+    }
+}
+
+impl MultiPageThumbnails for DjvuDocument {
+    fn thumbnails_ready(&self) -> bool {
+        self.thumbnail_cache
+            .as_ref()
+            .is_some_and(|c| c.len() >= self.num_pages)
+    }
+
+    fn thumbnails_loaded(&self) -> usize {
+        DjvuDocument::thumbnails_loaded(self)
+    }
+
+    fn generate_thumbnail_page(&mut self, page: usize) -> Option<usize> {
+        DjvuDocument::generate_thumbnail_page(self, page)
+    }
+
+    fn generate_all_thumbnails(&mut self) {
+        if self.thumbnails_ready() {
+            return;
+        }
+        self.init_thumbnail_cache();
+        for page in 0..self.num_pages {
+            self.generate_thumbnail_page(page);
+        }
+    }
+
+    fn get_thumbnail(&self, page: usize) -> Option<ImageHandle> {
+        self.thumbnail_cache
+            .as_ref()
+            .and_then(|cache| cache.get(page).cloned())
+    }
+
+    fn reset_thumbnails(&mut self) {
+        self.thumbnail_cache = None;
+    }
+}
@@ -4,60 +4,249 @@
 // Opening files, folder scanning, and navigation helpers.
 
 use std::fs;
+use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Instant, SystemTime};
 
 use anyhow::anyhow;
 
-use super::portable::PortableDocument;
+use super::archive;
 use super::raster::RasterDocument;
-use super::vector::VectorDocument;
-use super::{DocumentContent, DocumentKind};
+use super::meta::DocumentMeta;
+use super::vector::{InvalidSvg, VectorDocument};
+use super::{DocumentContent, DocumentKind, Transformable};
 
+use crate::app::message::{DecodedEntry, DecodedImage};
 use crate::app::model::{AppModel, ViewMode};
+use crate::constant::STDIN_ARG;
+use crate::fl;
 
 /// Open a document from a file path and dispatch to the correct type.
 ///
 /// Raster formats are delegated to the `image` crate, which decides
 /// based on enabled codecs (e.g. default-formats).
-pub fn open_document(path: &Path) -> anyhow::Result<DocumentContent> {
-    let kind = DocumentKind::from_path(path)
-        .ok_or_else(|| anyhow!("Unsupported document type: {}", path.display()))?;
+///
+/// Raster images above the decode limit fail with `OpenError::TooLarge`.
+pub fn open_document(path: &Path) -> Result<DocumentContent, OpenError> {
+    check_decode_size(path)?;
+    Ok(decode_document(path)?)
+}
+
+/// Why a document could not be opened, for a message that says what to do.
+///
+/// Loaders report library errors (`image`, `io`, Poppler, ...) through
+/// `anyhow`; `From<anyhow::Error>` sorts them into these cases.
+#[derive(Debug, Clone)]
+pub enum OpenError {
+    /// No decoder in this build reads the file.
+    UnsupportedFormat,
+    /// The file is damaged, truncated or not what its extension claims.
+    Corrupt,
+    /// The file cannot be read with the current permissions.
+    PermissionDenied,
+    /// Decoding would exceed the configured decode limit.
+    TooLarge(OversizedImage),
+    /// The format needs a library or tool missing from this build or system.
+    MissingBackend(Backend),
+    /// Anything else, shown with the underlying message.
+    Other(String),
+}
+
+/// Library or tool a document format depends on.
+#[derive(Debug, Clone, Copy)]
+pub enum Backend {
+    /// PDF rendering, built with the "portable" feature.
+    Poppler,
+    /// DjVu rendering with `ddjvu` and `djvused`, run at runtime.
+    Djvulibre,
+}
 
-    let content = match kind {
-        DocumentKind::Raster => {
-            let raster = RasterDocument::open(path)?;
-            DocumentContent::Raster(raster)
+impl OpenError {
+    /// Localized message for the file at `path`.
+    fn message(&self, path: &Path) -> String {
+        let name = path.file_name().map_or_else(
+            || path.display().to_string(),
+            |n| n.to_string_lossy().into_owned(),
+        );
+        let kind = DocumentKind::from_path(path);
+        match self {
+            Self::UnsupportedFormat => fl!("error-unsupported-file", name: name),
+            Self::Corrupt if kind == Some(DocumentKind::Vector) => {
+                fl!("error-invalid-svg", name: name)
+            }
+            Self::Corrupt => fl!("error-corrupt", name: name),
+            Self::PermissionDenied => fl!("error-permission-denied", name: name),
+            Self::TooLarge(oversized) => fl!(
+                "error-oversized",
+                name: name,
+                width: oversized.width,
+                height: oversized.height,
+                size: super::meta::format_file_size(oversized.decoded_bytes())
+            ),
+            Self::MissingBackend(Backend::Poppler) => fl!("error-pdf-unavailable"),
+            Self::MissingBackend(Backend::Djvulibre) => fl!("error-djvu-unavailable"),
+            Self::Other(message) => message.clone(),
         }
-        DocumentKind::Vector => {
-            let vector = VectorDocument::open(path)?;
-            DocumentContent::Vector(vector)
+    }
+
+    fn from_image_error(err: &image::ImageError) -> Option<Self> {
+        match err {
+            image::ImageError::Unsupported(_) => Some(Self::UnsupportedFormat),
+            image::ImageError::Decoding(_) => Some(Self::Corrupt),
+            image::ImageError::IoError(err) => Self::from_io_error(err),
+            _ => None,
         }
-        DocumentKind::Portable => {
-            let portable = PortableDocument::open(path)?;
-            DocumentContent::Portable(portable)
+    }
+
+    fn from_io_error(err: &std::io::Error) -> Option<Self> {
+        match err.kind() {
+            std::io::ErrorKind::PermissionDenied => Some(Self::PermissionDenied),
+            std::io::ErrorKind::InvalidData | std::io::ErrorKind::UnexpectedEof => {
+                Some(Self::Corrupt)
+            }
+            _ => None,
         }
-    };
+    }
+}
 
-    Ok(content)
+impl From<anyhow::Error> for OpenError {
+    fn from(err: anyhow::Error) -> Self {
+        let known = err.chain().find_map(|cause| {
+            if let Some(open_error) = cause.downcast_ref::<Self>() {
+                Some(open_error.clone())
+            } else if cause.is::<InvalidSvg>() {
+                Some(Self::Corrupt)
+            } else if let Some(err) = cause.downcast_ref::<image::ImageError>() {
+                Self::from_image_error(err)
+            } else {
+                cause
+                    .downcast_ref::<std::io::Error>()
+                    .and_then(Self::from_io_error)
+            }
+        });
+        known.unwrap_or_else(|| {
+            log::debug!("Unclassified open error: {err:#}");
+            Self::Other(err.to_string())
+        })
+    }
 }
 
-/// Open the initial path passed on the command line.
+impl std::fmt::Display for OpenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedFormat => write!(f, "Unsupported file format"),
+            Self::Corrupt => write!(f, "The file is damaged or incomplete"),
+            Self::PermissionDenied => write!(f, "Permission denied"),
+            Self::TooLarge(oversized) => write!(f, "{oversized}"),
+            Self::MissingBackend(Backend::Poppler) => {
+                write!(f, "PDF support is not available in this build")
+            }
+            Self::MissingBackend(Backend::Djvulibre) => {
+                write!(f, "DjVu support needs the djvulibre tools")
+            }
+            Self::Other(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for OpenError {}
+
+/// A raster image whose decoded pixels would exceed the configured limit.
+#[derive(Debug, Clone, Copy)]
+pub struct OversizedImage {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl OversizedImage {
+    /// Approximate memory needed for the decoded image (RGBA, 8 bits per channel).
+    pub fn decoded_bytes(&self) -> u64 {
+        u64::from(self.width) * u64::from(self.height) * 4
+    }
+}
+
+impl std::fmt::Display for OversizedImage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (width, height) = (self.width, self.height);
+        write!(f, "Image too large to decode ({width} × {height})")
+    }
+}
+
+/// Fail with `OpenError::TooLarge` if a raster image exceeds the decode limit.
 ///
-/// If `path` is a directory, this will collect supported documents inside it,
-/// open the first one, and initialize navigation state. If it is a file, the
-/// file is opened directly and the surrounding folder is scanned.
-pub fn open_initial_path(model: &mut AppModel, path: &PathBuf) {
-    if path.is_dir() {
-        open_from_directory(model, path);
-    } else {
-        open_single_file(model, path);
+/// Only the file header is read, so this is cheap even for huge files.
+fn check_decode_size(path: &Path) -> Result<(), OpenError> {
+    if super::max_decode_pixels() == 0
+        || DocumentKind::from_path(path) != Some(DocumentKind::Raster)
+    {
+        return Ok(());
+    }
+    check_dimensions(RasterDocument::header_dimensions(path))
+}
+
+/// Fail with `OpenError::TooLarge` if header `dimensions` exceed the decode limit.
+fn check_dimensions(dimensions: Option<(u32, u32)>) -> Result<(), OpenError> {
+    let limit = super::max_decode_pixels();
+    match dimensions {
+        Some((width, height)) if limit != 0 && u64::from(width) * u64::from(height) > limit => {
+            Err(OpenError::TooLarge(OversizedImage { width, height }))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Decode a document without the size check.
+fn decode_document(path: &Path) -> anyhow::Result<DocumentContent> {
+    let format = super::registry::lookup(path).ok_or(OpenError::UnsupportedFormat)?;
+    (format.loader)(path)
+}
+
+/// Open the initial paths passed on the command line.
+///
+/// If a single path is a directory, this will collect supported documents
+/// inside it, open the first one, and initialize navigation state. If it is a
+/// file, the file is opened directly and the surrounding folder is scanned.
+/// Several files are navigated in the given order without folder scanning.
+pub fn open_initial_path(model: &mut AppModel, paths: &[PathBuf]) {
+    match paths {
+        [] => {}
+        [path] if path.is_dir() => open_from_directory(model, path),
+        [path] => open_single_file(model, path),
+        paths => open_file_list(model, paths),
     }
 }
 
+/// Open the first of several files and use them as the navigation list.
+fn open_file_list(model: &mut AppModel, paths: &[PathBuf]) {
+    let entries: Vec<PathBuf> = paths
+        .iter()
+        .filter(|path| {
+            let supported = path.is_file() && DocumentKind::from_path(path).is_some();
+            if !supported {
+                log::warn!("Skipping unsupported file: {}", path.display());
+            }
+            supported
+        })
+        .cloned()
+        .collect();
+
+    let Some(first) = entries.first().cloned() else {
+        model.set_error("None of the given files is a supported document");
+        return;
+    };
+
+    model.folder_entries = entries;
+    model.current_index = Some(0);
+
+    load_document_into_model(model, &first);
+}
+
 /// Open the first supported document from the given directory and
 /// populate folder navigation state.
 pub fn open_from_directory(model: &mut AppModel, dir: &Path) {
-    let entries = collect_supported_files(dir);
+    let mut entries = collect_supported_files(dir, model.show_hidden, None);
+    sort_entries(model, &mut entries);
 
     if entries.is_empty() {
         model.set_error(format!(
@@ -75,7 +264,14 @@ pub fn open_from_directory(model: &mut AppModel, dir: &Path) {
 }
 
 /// Open a single file, update current path and refresh folder entries.
+///
+/// An archive opens its first image, with its images as the folder entries.
 pub fn open_single_file(model: &mut AppModel, path: &Path) {
+    if archive::is_archive(path) && path.is_file() {
+        open_archive(model, path);
+        return;
+    }
+
     load_document_into_model(model, path);
 
     // Refresh folder listing based on parent directory.
@@ -86,16 +282,415 @@ pub fn open_single_file(model: &mut AppModel, path: &Path) {
     }
 }
 
+/// Open the first image of a zip or tar archive and navigate its images.
+fn open_archive(model: &mut AppModel, path: &Path) {
+    let entries = match archive::list_images(path) {
+        Ok(entries) => entries,
+        Err(err) => {
+            model.set_error(format!("Failed to read {}: {err}", path.display()));
+            return;
+        }
+    };
+
+    let Some(first) = entries.first().cloned() else {
+        model.set_error(format!("No images found in archive: {}", path.display()));
+        return;
+    };
+
+    model.folder_entries = entries;
+    model.current_index = Some(0);
+
+    load_document_into_model(model, &first);
+}
+
+/// Reopen an entry of the recent files list, forgetting it if it is gone.
+pub fn open_recent(model: &mut AppModel, index: usize) {
+    let Some(path) = model.recent_files.get(index).cloned() else {
+        return;
+    };
+    if path.exists() {
+        open_single_file(model, &path);
+    } else {
+        model.recent_files.remove(index);
+        model.set_error(format!("{} no longer exists", path.display()));
+    }
+}
+
+/// Reload the current file if it changed on disk since it was loaded.
+///
+/// Stale thumbnails of the previous version are removed from the disk cache.
+/// Returns true if the document was reloaded.
+pub fn reload_if_modified(model: &mut AppModel, path: &Path) -> bool {
+    if model.current_path.as_deref() != Some(path) {
+        return false;
+    }
+    let modified = modified_time(path);
+    if modified.is_some() && modified == model.current_mtime {
+        return false;
+    }
+
+    remove_current_thumbnails(model, path);
+
+    log::info!("Reloading {} after change on disk", path.display());
+    load_document_into_model(model, path);
+    true
+}
+
+/// Re-decode the current file from disk, discarding transforms.
+///
+/// Cached thumbnails and the gallery handle of the file are dropped, so they
+/// are regenerated from the new content.
+pub fn reload_current(model: &mut AppModel) {
+    let Some(path) = model.current_path.clone() else {
+        model.set_error("No document loaded");
+        return;
+    };
+
+    remove_current_thumbnails(model, &path);
+    model.gallery_thumbnails.remove(&path);
+    model.capture_dates.remove(&path);
+    model.image_views.remove(&path);
+
+    log::info!("Reloading {}", path.display());
+    load_document_into_model(model, &path);
+}
+
+/// Re-parse the open SVG documents, keeping their transforms.
+///
+/// Used after the font settings change, since text is laid out at parse time.
+pub fn reparse_vectors(model: &mut AppModel) {
+    reparse_vector(&mut model.document, model.current_path.as_deref());
+    reparse_vector(
+        &mut model.secondary_document,
+        model.secondary_path.as_deref(),
+    );
+}
+
+fn reparse_vector(slot: &mut Option<DocumentContent>, path: Option<&Path>) {
+    let (Some(DocumentContent::Vector(old)), Some(path)) = (slot.as_ref(), path) else {
+        return;
+    };
+    match VectorDocument::open(path) {
+        Ok(mut doc) => {
+            doc.set_transform(old.transform_state());
+            *slot = Some(DocumentContent::Vector(doc));
+        }
+        Err(err) => log::warn!("Failed to re-parse {}: {err}", path.display()),
+    }
+}
+
+/// Remove disk-cached thumbnails of the loaded version of `path`.
+fn remove_current_thumbnails(model: &AppModel, path: &Path) {
+    if let Some(old_mtime) = model.current_mtime {
+        let pages = model
+            .document
+            .as_ref()
+            .and_then(DocumentContent::page_count)
+            .map_or(1, |n| n.max(1));
+        super::cache::remove_thumbnails(path, old_mtime, pages);
+    }
+}
+
 /// Load a document into the model, resetting view state.
+///
+/// Oversized images are only decoded once confirmed with `open_oversized`.
 fn load_document_into_model(model: &mut AppModel, path: &Path) {
-    match open_document(path) {
+    model.loading = None;
+    model.oversized = None;
+    model.measurement = None;
+    model.levels_white = None;
+
+    if let Some((archive, _)) = archive::split(path) {
+        // Entries are read and decoded in the background like large images.
+        begin_progressive_load(model, path);
+        // Archive entries are remembered as their archive, which exists on disk.
+        model.add_recent(archive);
+        return;
+    }
+
+    if model.oversized_confirmed.as_deref() != Some(path)
+        && let Err(err) = check_decode_size(path)
+    {
+        set_load_error(model, path, err);
+        return;
+    }
+
+    if DocumentKind::from_path(path) == Some(DocumentKind::Raster)
+        && RasterDocument::supports_progressive(path)
+    {
+        begin_progressive_load(model, path);
+        model.add_recent(path);
+        return;
+    }
+
+    let started = Instant::now();
+    let result = decode_document(path);
+    let elapsed = started.elapsed();
+    log::debug!("Opened {} in {elapsed:?}", path.display());
+    model.decode_time = Some(elapsed);
+
+    match result {
         Ok(doc) => {
             // Extract metadata before storing the document.
             let metadata = doc.extract_meta(path);
+            show_document(model, path, doc, metadata);
+        }
+        Err(err) => set_load_error(model, path, err.into()),
+    }
+}
+
+/// Store a freshly decoded document and restore or reset the view.
+fn show_document(model: &mut AppModel, path: &Path, doc: DocumentContent, metadata: DocumentMeta) {
+    let (width, height) = doc.dimensions();
+    model.end_crop_preview();
+    let keep_view = model.lock_view && model.document.is_some();
+
+    model.document = Some(doc);
+    model.metadata = Some(metadata);
+    model.current_path = Some(path.to_path_buf());
+    model.current_mtime = modified_time(path);
+    model.clear_error();
+    // Archive entries are remembered as their archive, which exists on disk.
+    model.add_recent(archive::split(path).map_or(path, |(archive, _)| archive));
 
-            model.document = Some(doc);
+    if keep_view {
+        // Compare mode: keep zoom/pan, but stay within the new image.
+        model.clamp_pan(width, height);
+    } else {
+        // Back to this image's view if it was open before, else fit.
+        model.restore_view(path);
+        model.clamp_pan(width, height);
+    }
+}
+
+/// Show why `path` could not be opened.
+fn set_load_error(model: &mut AppModel, path: &Path, err: OpenError) {
+    // Keep the path so navigation continues from here and the
+    // canvas can tell which file failed. A broken SVG leaves the
+    // previous document on screen instead of blanking the view.
+    let invalid_svg = matches!(err, OpenError::Corrupt)
+        && DocumentKind::from_path(path) == Some(DocumentKind::Vector);
+    if !invalid_svg {
+        model.document = None;
+    }
+    model.metadata = None;
+    model.current_path = Some(path.to_path_buf());
+    model.current_mtime = modified_time(path);
+
+    model.set_error(err.message(path));
+    if matches!(err, OpenError::TooLarge(_)) {
+        model.oversized = Some(path.to_path_buf());
+    }
+}
+
+/// Decode the image that was held back for its size after the user confirmed it.
+pub fn open_oversized(model: &mut AppModel) {
+    if let Some(path) = model.oversized.take() {
+        model.oversized_confirmed = Some(path.clone());
+        load_document_into_model(model, &path);
+    }
+}
+
+/// Open `path` beside the current document for comparison.
+pub fn open_secondary(model: &mut AppModel, path: &Path) {
+    match open_document(path) {
+        Ok(doc) => {
+            model.secondary_document = Some(doc);
+            model.secondary_path = Some(path.to_path_buf());
+            model.secondary_render = None;
+            model.secondary_view = (ViewMode::Fit, 0.0, 0.0);
+        }
+        Err(err) => model.set_error(err.message(path)),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Progressive loading (large raster images)
+// ---------------------------------------------------------------------------
+
+/// Mark a large image or an archive entry as loading; the decode tasks are
+/// spawned by the app.
+fn begin_progressive_load(model: &mut AppModel, path: &Path) {
+    model.end_crop_preview();
+    let keep_view = model.lock_view && model.document.is_some();
+
+    model.document = None;
+    model.metadata = None;
+    model.current_path = Some(path.to_path_buf());
+    model.current_mtime = modified_time(path);
+    model.loading = Some(path.to_path_buf());
+    model.load_started = Some(Instant::now());
+    model.decode_time = None;
+    model.clear_error();
+
+    if !keep_view {
+        model.restore_view(path);
+    }
+}
+
+/// Show the low-resolution preview while the full image is still decoding.
+pub fn apply_preview(model: &mut AppModel, path: &Path, image: &DecodedImage) {
+    if model.loading.as_deref() != Some(path) || model.document.is_some() {
+        return;
+    }
+    if let Some(image) = image.lock().ok().and_then(|mut slot| slot.take()) {
+        let mut raster = RasterDocument::from_image(image);
+        raster.apply_exif_orientation(path);
+
+        // The preview is smaller than the image it stands for: clamp the
+        // (possibly locked) pan to the full size, oriented like the preview.
+        if let Some((width, height)) = RasterDocument::header_dimensions(path) {
+            let (preview_width, preview_height) = raster.dimensions();
+            if (preview_width > preview_height) == (width > height) {
+                model.clamp_pan(width, height);
+            } else {
+                model.clamp_pan(height, width);
+            }
+        }
+        model.document = Some(DocumentContent::Raster(raster));
+    }
+}
+
+/// Replace the preview with the decoded full-resolution image.
+pub fn finish_progressive_load(
+    model: &mut AppModel,
+    path: &Path,
+    result: &Result<DecodedImage, OpenError>,
+) {
+    // Ignore results for documents the user already navigated away from.
+    if model.loading.as_deref() != Some(path) {
+        return;
+    }
+    model.loading = None;
+    model.decode_time = model.load_started.take().map(|started| started.elapsed());
+
+    let image = match result {
+        Ok(image) => image.lock().ok().and_then(|mut slot| slot.take()),
+        Err(err) => {
+            set_load_error(model, path, err.clone());
+            return;
+        }
+    };
+
+    if let Some(image) = image {
+        let mut raster = RasterDocument::from_image(image);
+        raster.apply_exif_orientation(path);
+        raster.detect_source_color_type(path);
+        let doc = DocumentContent::Raster(raster);
+        let (width, height) = doc.dimensions();
+        model.clamp_pan(width, height);
+        model.metadata = Some(doc.extract_meta(path));
+        model.document = Some(doc);
+    }
+}
+
+/// Read and decode an image stored in an archive (blocking, run off the UI thread).
+///
+/// `path` is the entry's virtual path. Unless the user already confirmed it,
+/// an entry over the decode limit fails before its pixels are decoded.
+pub fn decode_archive_entry(path: &Path, confirmed: bool) -> anyhow::Result<DecodedEntry> {
+    let (archive, entry) =
+        archive::split(path).ok_or_else(|| anyhow!("{} is not in an archive", path.display()))?;
+    let bytes = archive::read_entry(archive, &entry)?;
+    if !confirmed {
+        check_dimensions(RasterDocument::header_dimensions_from_bytes(&bytes))?;
+    }
+
+    let image = image::load_from_memory(&bytes)?;
+    let name = path
+        .file_name()
+        .map_or(entry, |n| n.to_string_lossy().into_owned());
+    let mut metadata =
+        super::meta::build_memory_meta(&name, &bytes, &image, image.width(), image.height());
+    metadata.basic.file_path = path.display().to_string();
+    Ok(Arc::new(Mutex::new(Some((image, metadata)))))
+}
+
+/// Show an archive entry decoded in the background.
+pub fn finish_archive_load(
+    model: &mut AppModel,
+    path: &Path,
+    result: &Result<DecodedEntry, OpenError>,
+) {
+    // Ignore results for documents the user already navigated away from.
+    if model.loading.as_deref() != Some(path) {
+        return;
+    }
+    model.loading = None;
+    model.decode_time = model.load_started.take().map(|started| started.elapsed());
+
+    let entry = match result {
+        Ok(entry) => entry.lock().ok().and_then(|mut slot| slot.take()),
+        Err(err) => {
+            set_load_error(model, path, err.clone());
+            return;
+        }
+    };
+
+    if let Some((image, metadata)) = entry {
+        let doc = DocumentContent::Raster(RasterDocument::from_image(image));
+        let (width, height) = doc.dimensions();
+        model.clamp_pan(width, height);
+        model.metadata = Some(metadata);
+        model.document = Some(doc);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// In-memory documents (stdin, URL)
+// ---------------------------------------------------------------------------
+
+/// Check whether a command-line argument refers to standard input.
+pub fn is_stdin_arg(arg: &Path) -> bool {
+    arg.as_os_str() == STDIN_ARG
+}
+
+/// Check whether a command-line argument is a remote http(s) URL.
+pub fn is_remote_url(arg: &Path) -> bool {
+    arg.to_str()
+        .is_some_and(|s| s.starts_with("http://") || s.starts_with("https://"))
+}
+
+/// Read all document bytes from standard input.
+pub fn read_stdin() -> std::io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    std::io::stdin().lock().read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Download a remote document (blocking, run off the UI thread).
+pub fn fetch_url(url: &str) -> anyhow::Result<Vec<u8>> {
+    let response = ureq::get(url)
+        .call()
+        .map_err(|e| anyhow!("Failed to download {url}: {e}"))?;
+
+    let mut bytes = Vec::new();
+    response
+        .into_reader()
+        .read_to_end(&mut bytes)
+        .map_err(|e| anyhow!("Failed to read response from {url}: {e}"))?;
+    Ok(bytes)
+}
+
+/// Load a raster document from memory into the model.
+///
+/// In-memory documents have no backing folder, so navigation state is cleared.
+pub fn open_from_bytes(model: &mut AppModel, name: &str, bytes: &[u8]) {
+    model.folder_entries.clear();
+    model.current_index = None;
+
+    let started = Instant::now();
+    let result = RasterDocument::from_bytes(bytes);
+    model.decode_time = Some(started.elapsed());
+
+    match result {
+        Ok(raster) => {
+            let metadata = raster.extract_meta_from_bytes(name, bytes);
+
+            model.document = Some(DocumentContent::Raster(raster));
             model.metadata = Some(metadata);
-            model.current_path = Some(path.to_path_buf());
+            model.current_path = None;
+            model.current_mtime = None;
             model.clear_error();
 
             // Reset view state for new document.
@@ -106,15 +701,23 @@ fn load_document_into_model(model: &mut AppModel, path: &Path) {
             model.document = None;
             model.metadata = None;
             model.current_path = None;
-            model.set_error(err.to_string());
+            model.current_mtime = None;
+            model.set_error(format!("Failed to decode {name}: {err}"));
         }
     }
 }
 
 /// Refresh the `folder_entries` list and current index based on the
 /// given folder and currently active file.
+///
+/// The active file stays in the list even if it is hidden. Images in an
+/// archive are listed from the archive instead, in archive order.
 pub fn refresh_folder_entries(model: &mut AppModel, folder: &Path, current: &Path) {
-    let entries = collect_supported_files(folder);
+    let mut entries = match archive::split(current) {
+        Some((archive, _)) => archive::list_images(archive).unwrap_or_default(),
+        None => collect_supported_files(folder, model.show_hidden, Some(current)),
+    };
+    sort_entries(model, &mut entries);
 
     // Determine current index.
     let current_index = entries.iter().position(|p| p == current);
@@ -124,69 +727,386 @@ pub fn refresh_folder_entries(model: &mut AppModel, folder: &Path, current: &Pat
 }
 
 /// Collect all supported document files from a directory, sorted alphabetically.
-fn collect_supported_files(dir: &Path) -> Vec<PathBuf> {
-    let mut entries: Vec<PathBuf> = Vec::new();
+///
+/// Files are classified by extension first, so unsupported entries never
+/// touch the filesystem. The file type comes from the directory listing;
+/// only symlinks are resolved with an extra `stat`.
+///
+/// Dot files are skipped unless `show_hidden` is set or the file is `keep`.
+fn collect_supported_files(dir: &Path, show_hidden: bool, keep: Option<&Path>) -> Vec<PathBuf> {
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
 
-    if let Ok(read_dir) = fs::read_dir(dir) {
-        for entry in read_dir.flatten() {
+    let mut entries: Vec<PathBuf> = read_dir
+        .flatten()
+        .filter_map(|entry| {
             let path = entry.path();
-
-            // Only keep regular files that are recognized as supported documents.
-            if path.is_file() && DocumentKind::from_path(&path).is_some() {
-                entries.push(path);
+            if DocumentKind::from_path(&path).is_none() {
+                return None;
             }
-        }
-    }
+            if !show_hidden && is_hidden(&path) && keep != Some(path.as_path()) {
+                return None;
+            }
+
+            // Only keep regular files (or links to them).
+            let file_type = entry.file_type().ok()?;
+            let is_file = file_type.is_file() || (file_type.is_symlink() && path.is_file());
+            is_file.then_some(path)
+        })
+        .collect();
 
     entries.sort();
     entries
 }
 
+/// Check whether a file name starts with a dot (hidden on Unix).
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name.as_encoded_bytes().starts_with(b"."))
+}
+
+/// Open the folder entry at the given index.
+pub fn open_entry(model: &mut AppModel, index: usize) {
+    if let Some(path) = model.folder_entries.get(index).cloned() {
+        model.current_index = Some(index);
+        load_document_into_model(model, &path);
+    }
+}
+
+/// Apply the configured sort order to alphabetically sorted entries.
+///
+/// Capture-date sorting uses EXIF `DateTimeOriginal`, falling back to the
+/// file modification time. Timestamps are cached in the model.
+fn sort_entries(model: &mut AppModel, entries: &mut [PathBuf]) {
+    if !model.sort_by_capture_date {
+        return;
+    }
+
+    let missing: Vec<&PathBuf> = entries
+        .iter()
+        .filter(|path| !model.capture_dates.contains_key(*path))
+        .collect();
+
+    // Reading EXIF dominates on large folders, so spread it over all cores.
+    let threads = std::thread::available_parallelism().map_or(1, std::num::NonZero::get);
+    let chunk_size = missing.len().div_ceil(threads).max(1);
+    let timestamps: Vec<(PathBuf, i64)> = std::thread::scope(|scope| {
+        let workers: Vec<_> = missing
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|path| ((*path).clone(), capture_timestamp(path)))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap_or_default())
+            .collect()
+    });
+    model.capture_dates.extend(timestamps);
+
+    // Stable sort keeps alphabetical order for equal timestamps.
+    entries.sort_by_key(|path| model.capture_dates.get(path).copied().unwrap_or_default());
+}
+
+/// Capture time of a file in Unix seconds (EXIF, falling back to mtime).
+fn capture_timestamp(path: &Path) -> i64 {
+    super::meta::read_capture_timestamp(path)
+        .or_else(|| {
+            modified_time(path)
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .and_then(|d| i64::try_from(d.as_secs()).ok())
+        })
+        .unwrap_or_default()
+}
+
+/// Position of the current file in `folder_entries`.
+enum Position {
+    /// The current file is listed at this index.
+    Listed(usize),
+    /// The current file is gone; the stale index is where it used to be.
+    Removed(usize),
+    /// No current file.
+    Unknown,
+}
+
+/// Resolve the current position by path, since `folder_entries` may have
+/// been refreshed since the file was opened.
+fn current_position(model: &AppModel) -> Position {
+    let listed = model
+        .current_path
+        .as_ref()
+        .and_then(|current| model.folder_entries.iter().position(|p| p == current));
+
+    match (listed, model.current_index) {
+        (Some(index), _) => Position::Listed(index),
+        (None, Some(index)) => Position::Removed(index),
+        (None, None) => Position::Unknown,
+    }
+}
+
 /// Navigate to the next document in the folder.
+///
+/// At the last document, continues in the next sibling folder if enabled.
 pub fn navigate_next(model: &mut AppModel) {
-    if model.folder_entries.is_empty() {
+    let len = model.folder_entries.len();
+    if len == 0 {
         return;
     }
 
-    let new_index = match model.current_index {
-        Some(idx) => {
-            if idx + 1 < model.folder_entries.len() {
-                idx + 1
-            } else {
-                0 // Wrap around to first.
-            }
-        }
-        None => 0,
+    let position = current_position(model);
+    if matches!(position, Position::Listed(idx) if idx + 1 == len)
+        && let Some(entries) = sibling_folder_entries(model, true)
+    {
+        open_folder_entries(model, entries, 0);
+        return;
+    }
+
+    let new_index = match position {
+        Position::Listed(idx) => (idx + 1) % len, // Wrap around to first.
+        // The entry that took the place of the removed file comes next.
+        Position::Removed(idx) if idx < len => idx,
+        Position::Removed(_) | Position::Unknown => 0,
     };
 
-    if let Some(path) = model.folder_entries.get(new_index).cloned() {
-        model.current_index = Some(new_index);
-        load_document_into_model(model, &path);
-    }
+    open_other_entry(model, new_index);
+}
+
+/// The folder entry after the current file (wrapping), if it is another file.
+pub fn next_entry(model: &AppModel) -> Option<PathBuf> {
+    let Position::Listed(index) = current_position(model) else {
+        return None;
+    };
+    let next = model
+        .folder_entries
+        .get((index + 1) % model.folder_entries.len())?;
+    (Some(next) != model.current_path.as_ref()).then(|| next.clone())
 }
 
 /// Navigate to the previous document in the folder.
+///
+/// At the first document, continues with the last document of the previous
+/// sibling folder if enabled.
 pub fn navigate_prev(model: &mut AppModel) {
-    if model.folder_entries.is_empty() {
+    let len = model.folder_entries.len();
+    if len == 0 {
         return;
     }
 
-    let new_index = match model.current_index {
-        Some(idx) => {
-            if idx > 0 {
-                idx - 1
-            } else {
-                model.folder_entries.len() - 1 // Wrap around to last.
-            }
-        }
-        None => model.folder_entries.len().saturating_sub(1),
+    let position = current_position(model);
+    if matches!(position, Position::Listed(0))
+        && let Some(entries) = sibling_folder_entries(model, false)
+    {
+        let last = entries.len() - 1;
+        open_folder_entries(model, entries, last);
+        return;
+    }
+
+    let new_index = match position {
+        Position::Listed(idx) | Position::Removed(idx) if idx > 0 => idx.min(len) - 1,
+        // Wrap around to last.
+        Position::Listed(_) | Position::Removed(_) | Position::Unknown => len - 1,
+    };
+
+    open_other_entry(model, new_index);
+}
+
+/// Navigate to the first document in the folder.
+pub fn navigate_first(model: &mut AppModel) {
+    open_other_entry(model, 0);
+}
+
+/// Navigate to the last document in the folder.
+pub fn navigate_last(model: &mut AppModel) {
+    if let Some(last) = model.folder_entries.len().checked_sub(1) {
+        open_other_entry(model, last);
+    }
+}
+
+/// Sorted entries of the nearest sibling folder after (`forward`) or before
+/// the current folder that contains documents.
+///
+/// Siblings are ordered by name. Returns None unless cross-folder navigation
+/// is enabled, and for images inside an archive.
+fn sibling_folder_entries(model: &mut AppModel, forward: bool) -> Option<Vec<PathBuf>> {
+    if !model.cross_folder_navigation {
+        return None;
+    }
+    let current = model.current_path.as_deref()?;
+    if archive::split(current).is_some() {
+        return None;
+    }
+    let folder = current.parent()?;
+
+    let mut siblings: Vec<PathBuf> = fs::read_dir(folder.parent()?)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir() && (model.show_hidden || !is_hidden(path)))
+        .collect();
+    siblings.sort();
+
+    let index = siblings.iter().position(|dir| dir == folder)?;
+    let candidates: Vec<&PathBuf> = if forward {
+        siblings[index + 1..].iter().collect()
+    } else {
+        siblings[..index].iter().rev().collect()
+    };
+    let mut entries = candidates.into_iter().find_map(|dir| {
+        let entries = collect_supported_files(dir, model.show_hidden, None);
+        (!entries.is_empty()).then_some(entries)
+    })?;
+    sort_entries(model, &mut entries);
+    Some(entries)
+}
+
+/// Replace the folder entries and open the one at `index`.
+fn open_folder_entries(model: &mut AppModel, entries: Vec<PathBuf>, index: usize) {
+    let Some(path) = entries.get(index).cloned() else {
+        return;
     };
+    model.folder_entries = entries;
+    model.current_index = Some(index);
+    load_document_into_model(model, &path);
+}
 
-    if let Some(path) = model.folder_entries.get(new_index).cloned() {
-        model.current_index = Some(new_index);
+/// Open the entry at `index` unless it is the file already shown.
+fn open_other_entry(model: &mut AppModel, index: usize) {
+    let Some(path) = model.folder_entries.get(index).cloned() else {
+        return;
+    };
+
+    model.current_index = Some(index);
+    if model.current_path.as_ref() != Some(&path) {
         load_document_into_model(model, &path);
     }
 }
+
+// ---------------------------------------------------------------------------
+// Copy / move operations
+// ---------------------------------------------------------------------------
+
+/// Copy the current file into `dir`.
+pub fn copy_current_to(model: &mut AppModel, dir: &Path) {
+    let result = transfer_target(model, dir)
+        .and_then(|(source, target)| Ok(fs::copy(&source, &target).map(|_| target)?));
+
+    match result {
+        Ok(target) => log::info!("Copied to {}", target.display()),
+        Err(e) => model.set_error(format!("Copy failed: {e}")),
+    }
+}
+
+/// Move the current file into `dir` and show the next folder entry.
+pub fn move_current_to(model: &mut AppModel, dir: &Path) {
+    let result = transfer_target(model, dir).and_then(|(source, target)| {
+        super::utils::move_file(&source, &target)?;
+        Ok(target)
+    });
+
+    match result {
+        Ok(target) => {
+            log::info!("Moved to {}", target.display());
+            remove_current_entry(model);
+        }
+        Err(e) => model.set_error(format!("Move failed: {e}")),
+    }
+}
+
+/// Resolve source and target path for copying/moving the current file into `dir`.
+fn transfer_target(model: &AppModel, dir: &Path) -> anyhow::Result<(PathBuf, PathBuf)> {
+    let source = model
+        .current_path
+        .clone()
+        .ok_or_else(|| anyhow!("No document loaded"))?;
+    if !dir.is_dir() {
+        return Err(anyhow!("Not a directory: {}", dir.display()));
+    }
+    let name = source
+        .file_name()
+        .ok_or_else(|| anyhow!("Invalid path: {}", source.display()))?;
+
+    let target = dir.join(name);
+    if target.exists() {
+        return Err(anyhow!("{} already exists", target.display()));
+    }
+    Ok((source, target))
+}
+
+/// Drop the current file from the folder list and open the entry taking its place.
+fn remove_current_entry(model: &mut AppModel) {
+    if let Some(index) = model.current_index
+        && index < model.folder_entries.len()
+    {
+        model.folder_entries.remove(index);
+        if !model.folder_entries.is_empty() {
+            open_entry(model, index.min(model.folder_entries.len() - 1));
+            return;
+        }
+    }
+
+    // Nothing left to show.
+    model.document = None;
+    model.metadata = None;
+    model.current_path = None;
+    model.current_mtime = None;
+    model.current_index = None;
+}
+
+// ---------------------------------------------------------------------------
+// Export operations
+// ---------------------------------------------------------------------------
+
+/// Export the current vector document as PNG next to the original.
+///
+/// Format: "NAME_WxH.png"
+/// Example: "icon.svg" → "icon_256x256.png"
+pub fn export_png(
+    doc: &DocumentContent,
+    original_path: &Path,
+    width: u32,
+    height: u32,
+) -> Result<PathBuf, String> {
+    let DocumentContent::Vector(vector_doc) = doc else {
+        return Err("PNG export is only supported for vector documents".to_string());
+    };
+
+    let stem = original_path
+        .file_stem()
+        .ok_or_else(|| "Invalid path".to_string())?
+        .to_string_lossy();
+    let new_path = original_path.with_file_name(format!("{stem}_{width}x{height}.png"));
+    if new_path.exists() {
+        return Err(format!("{} already exists", new_path.display()));
+    }
+
+    vector_doc
+        .export_png(&new_path, width, height)
+        .map_err(|e| e.to_string())?;
+    Ok(new_path)
+}
+
+/// Write the document metadata as JSON next to the file ("photo.jpg" → "photo.jpg.json").
+///
+/// Returns the path of the JSON file.
+pub fn write_metadata_json(meta: &DocumentMeta, path: &Path) -> Result<PathBuf, String> {
+    let mut name = path
+        .file_name()
+        .ok_or_else(|| "Invalid path".to_string())?
+        .to_os_string();
+    name.push(".json");
+    let json_path = path.with_file_name(name);
+
+    let json = meta.to_json().map_err(|e| e.to_string())?;
+    fs::write(&json_path, json).map_err(|e| e.to_string())?;
+    Ok(json_path)
+}
+
 // ---------------------------------------------------------------------------
 // File metadata helpers
 // ---------------------------------------------------------------------------
@@ -196,6 +1116,11 @@ pub fn file_size(path: &Path) -> u64 {
     fs::metadata(path).map(|m| m.len()).unwrap_or(0)
 }
 
+/// Retrieve the last modification time. Returns None if unavailable.
+pub fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
 /// Read raw bytes from a file for metadata extraction (e.g., EXIF).
 /// Returns None if the file cannot be read.
 pub fn read_file_bytes(path: &Path) -> Option<Vec<u8>> {
@@ -232,20 +1157,58 @@ pub fn save_crop_as(
         .with_file_name(&new_filename)
         .with_extension(ext.as_ref());
 
+    // The original extension selects the encoder, which only raster formats have.
+    crop_image(doc, x, y, width, height)?
+        .save(&new_path)
+        .map_err(|e| e.to_string())?;
+
+    Ok(new_path)
+}
+
+/// Crop the document and store the result as wallpaper in the cache.
+///
+/// Returns the path of the wallpaper file.
+pub fn crop_to_wallpaper(
+    doc: &DocumentContent,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+) -> Result<PathBuf, String> {
+    let image = crop_image(doc, x, y, width, height)?;
+    super::cache::save_wallpaper(&image).ok_or_else(|| "Failed to save wallpaper".to_string())
+}
+
+/// Crop the rendered raster document to the given rectangle.
+fn crop_image(
+    doc: &DocumentContent,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+) -> Result<image::DynamicImage, String> {
     match doc {
-        DocumentContent::Raster(raster_doc) => {
-            let cropped_image = raster_doc
-                .crop_to_image(x, y, width, height)
-                .map_err(|e| e.to_string())?;
-            cropped_image.save(&new_path).map_err(|e| e.to_string())?;
-        }
+        DocumentContent::Raster(_) => {}
         DocumentContent::Vector(_) => {
             return Err("Crop not supported for vector documents".to_string());
         }
+        #[cfg(feature = "portable")]
         DocumentContent::Portable(_) => {
             return Err("Crop not supported for PDF documents".to_string());
         }
+        #[cfg(feature = "djvu")]
+        DocumentContent::Djvu(_) => {
+            return Err("Crop not supported for DjVu documents".to_string());
+        }
     }
 
-    Ok(new_path)
+    let image = doc.render_to_image();
+    let (img_width, img_height) = (image.width(), image.height());
+    if x + width > img_width || y + height > img_height {
+        return Err(format!(
+            "Crop rectangle out of bounds: {width}x{height} at ({x}, {y}) exceeds image size {img_width}x{img_height}"
+        ));
+    }
+
+    Ok(image.crop_imm(x, y, width, height))
 }
@@ -0,0 +1,72 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// src/app/document/icon.rs
+//
+// ICO/CUR container parsing: enumerate all embedded resolutions.
+
+use image::{DynamicImage, ImageFormat};
+
+/// Size of the ICONDIR header in bytes.
+const HEADER_SIZE: usize = 6;
+/// Size of one ICONDIRENTRY in bytes.
+const ENTRY_SIZE: usize = 16;
+
+/// Decode every image embedded in an ICO/CUR file, largest first.
+///
+/// Each entry is re-wrapped as a single-image ICO so the `image` crate's
+/// decoder handles both PNG and BMP payloads.
+pub fn decode_entries(bytes: &[u8]) -> anyhow::Result<Vec<DynamicImage>> {
+    if bytes.len() < HEADER_SIZE {
+        return Err(anyhow::anyhow!("Icon file is truncated"));
+    }
+
+    let kind = u16::from_le_bytes([bytes[2], bytes[3]]);
+    if kind != 1 && kind != 2 {
+        return Err(anyhow::anyhow!("Not an ICO/CUR file"));
+    }
+    let count = usize::from(u16::from_le_bytes([bytes[4], bytes[5]]));
+
+    let mut images = Vec::with_capacity(count);
+    for index in 0..count {
+        let start = HEADER_SIZE + index * ENTRY_SIZE;
+        let Some(entry) = bytes.get(start..start + ENTRY_SIZE) else {
+            break;
+        };
+
+        let size = u32::from_le_bytes([entry[8], entry[9], entry[10], entry[11]]) as usize;
+        let offset = u32::from_le_bytes([entry[12], entry[13], entry[14], entry[15]]) as usize;
+        let Some(data) = bytes.get(offset..offset + size) else {
+            log::warn!("Icon entry {index} points outside the file");
+            continue;
+        };
+
+        match decode_entry(entry, data) {
+            Ok(img) => images.push(img),
+            Err(e) => log::warn!("Failed to decode icon entry {index}: {e}"),
+        }
+    }
+
+    if images.is_empty() {
+        return Err(anyhow::anyhow!("Icon file contains no decodable images"));
+    }
+
+    images.sort_by_key(|img| std::cmp::Reverse(img.width() * img.height()));
+    Ok(images)
+}
+
+/// Decode a single entry by wrapping it into a one-image ICO container.
+fn decode_entry(entry: &[u8], data: &[u8]) -> image::ImageResult<DynamicImage> {
+    #[allow(clippy::cast_possible_truncation)]
+    let data_offset = (HEADER_SIZE + ENTRY_SIZE) as u32;
+
+    let mut single = Vec::with_capacity(HEADER_SIZE + ENTRY_SIZE + data.len());
+    // ICONDIR: reserved, type (always ICO), count.
+    single.extend_from_slice(&[0, 0, 1, 0, 1, 0]);
+    // ICONDIRENTRY: keep size/color fields, normalize planes/bpp for CUR hotspots.
+    single.extend_from_slice(&entry[0..4]);
+    single.extend_from_slice(&[1, 0, 32, 0]);
+    single.extend_from_slice(&entry[8..12]);
+    single.extend_from_slice(&data_offset.to_le_bytes());
+    single.extend_from_slice(data);
+
+    image::load_from_memory_with_format(&single, ImageFormat::Ico)
+}
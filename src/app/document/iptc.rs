@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// src/app/document/iptc.rs
+//
+// Minimal IPTC-IIM parsing from Photoshop image resource blocks.
+
+use super::meta::DescriptiveMeta;
+
+/// Photoshop image resource signature followed by the IPTC-NAA resource id (0x0404).
+const IPTC_RESOURCE: &[u8] = b"8BIM\x04\x04";
+/// IPTC dataset tag marker.
+const TAG_MARKER: u8 = 0x1C;
+/// Application record number (descriptive datasets).
+const APPLICATION_RECORD: u8 = 2;
+
+// Application record dataset numbers.
+const DATASET_OBJECT_NAME: u8 = 5;
+const DATASET_KEYWORDS: u8 = 25;
+const DATASET_BYLINE: u8 = 80;
+const DATASET_COPYRIGHT: u8 = 116;
+const DATASET_CAPTION: u8 = 120;
+
+/// Parse IPTC descriptive metadata from raw file bytes.
+///
+/// Returns None if the file contains no IPTC resource block.
+pub fn parse(data: &[u8]) -> Option<DescriptiveMeta> {
+    let block = find_resource_block(data)?;
+
+    let mut meta = DescriptiveMeta::default();
+    let mut creators = Vec::new();
+    let mut pos = 0;
+
+    while pos + 5 <= block.len() {
+        if block[pos] != TAG_MARKER {
+            break;
+        }
+        let record = block[pos + 1];
+        let dataset = block[pos + 2];
+        let len = usize::from(u16::from_be_bytes([block[pos + 3], block[pos + 4]]));
+        let start = pos + 5;
+        let end = start + len;
+        if end > block.len() {
+            break;
+        }
+
+        if record == APPLICATION_RECORD {
+            let value = String::from_utf8_lossy(&block[start..end])
+                .trim()
+                .to_string();
+            if !value.is_empty() {
+                match dataset {
+                    DATASET_OBJECT_NAME => meta.title = Some(value),
+                    DATASET_KEYWORDS => meta.keywords.push(value),
+                    DATASET_BYLINE => creators.push(value),
+                    DATASET_COPYRIGHT => meta.copyright = Some(value),
+                    DATASET_CAPTION => meta.description = Some(value),
+                    _ => {}
+                }
+            }
+        }
+
+        pos = end;
+    }
+
+    if !creators.is_empty() {
+        meta.creator = Some(creators.join(", "));
+    }
+
+    Some(meta)
+}
+
+/// Locate the IPTC-NAA image resource and return its data.
+fn find_resource_block(data: &[u8]) -> Option<&[u8]> {
+    let pos = data
+        .windows(IPTC_RESOURCE.len())
+        .position(|window| window == IPTC_RESOURCE)?;
+    let mut cursor = pos + IPTC_RESOURCE.len();
+
+    // Pascal string name, padded to an even total length.
+    let name_len = usize::from(*data.get(cursor)?);
+    let padded = if (name_len + 1) % 2 == 0 {
+        name_len + 1
+    } else {
+        name_len + 2
+    };
+    cursor += padded;
+
+    let size_bytes = data.get(cursor..cursor + 4)?;
+    let size = u32::from_be_bytes([size_bytes[0], size_bytes[1], size_bytes[2], size_bytes[3]]);
+    cursor += 4;
+
+    data.get(cursor..cursor + size as usize)
+}
@@ -1,19 +1,20 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // src/app/document/meta.rs
 //
-// Document metadata extraction (basic info and EXIF).
+// Document metadata extraction (basic info, EXIF, IPTC and XMP).
 
 use std::io::Cursor;
 use std::path::Path;
 
 use image::DynamicImage;
 use exif::{In, Reader as ExifReader, Tag, Value};
+use serde::Serialize;
 
-use super::file;
-use crate::constant::{MINUTES_PER_DEGREE, SECONDS_PER_DEGREE};
+use super::{file, iptc, xmp};
+use crate::constant::{CM_PER_INCH, MINUTES_PER_DEGREE, SECONDS_PER_DEGREE};
 
 /// Basic document metadata (always available).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct BasicMeta {
     /// File name (without path).
     pub file_name: String,
@@ -29,39 +30,77 @@ pub struct BasicMeta {
     pub file_size: u64,
     /// Color type description (e.g., "RGBA8", "RGB8", "Grayscale").
     pub color_type: String,
+    /// Displayed size if the image was downscaled to fit the max texture size.
+    pub display_size: Option<(u32, u32)>,
+    /// Frame count of an animated image (GIF, APNG, WebP); only the first frame is shown.
+    pub frame_count: Option<u32>,
 }
 
 impl BasicMeta {
     /// Format file size as human-readable string.
     pub fn file_size_display(&self) -> String {
-        const KB: u64 = 1024;
-        const MB: u64 = KB * 1024;
-        const GB: u64 = MB * 1024;
-
-        #[allow(clippy::cast_precision_loss)]
-        if self.file_size >= GB {
-            let size_gb = self.file_size as f64 / GB as f64;
-            format!("{size_gb:.2} GB")
-        } else if self.file_size >= MB {
-            let size_mb = self.file_size as f64 / MB as f64;
-            format!("{size_mb:.2} MB")
-        } else if self.file_size >= KB {
-            let size_kb = self.file_size as f64 / KB as f64;
-            format!("{size_kb:.1} KB")
-        } else {
-            let size = self.file_size;
-            format!("{size} B")
-        }
+        format_file_size(self.file_size)
     }
 
     /// Format resolution as "W × H".
     pub fn resolution_display(&self) -> String {
         format!("{} × {}", self.width, self.height)
     }
+
+    /// Format the downscaled display size, if any.
+    pub fn display_size_display(&self) -> Option<String> {
+        self.display_size.map(|(w, h)| format!("{w} × {h}"))
+    }
+
+    /// Format the aspect ratio in lowest terms (e.g. "3:2").
+    pub fn aspect_ratio_display(&self) -> Option<String> {
+        if self.width == 0 || self.height == 0 {
+            return None;
+        }
+        let divisor = gcd(self.width, self.height);
+        let (w, h) = (self.width / divisor, self.height / divisor);
+        Some(format!("{w}:{h}"))
+    }
+
+    /// Format the total pixel count in megapixels (e.g. "24.0 MP").
+    pub fn megapixels_display(&self) -> String {
+        #[allow(clippy::cast_precision_loss)]
+        let megapixels = (u64::from(self.width) * u64::from(self.height)) as f64 / 1_000_000.0;
+        format!("{megapixels:.1} MP")
+    }
+}
+
+/// Greatest common divisor (Euclid).
+fn gcd(mut a: u32, mut b: u32) -> u32 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Format a byte count as human-readable string (e.g. "1.50 MB").
+pub fn format_file_size(bytes: u64) -> String {
+    const KB: u64 = 1024;
+    const MB: u64 = KB * 1024;
+    const GB: u64 = MB * 1024;
+
+    #[allow(clippy::cast_precision_loss)]
+    if bytes >= GB {
+        let size_gb = bytes as f64 / GB as f64;
+        format!("{size_gb:.2} GB")
+    } else if bytes >= MB {
+        let size_mb = bytes as f64 / MB as f64;
+        format!("{size_mb:.2} MB")
+    } else if bytes >= KB {
+        let size_kb = bytes as f64 / KB as f64;
+        format!("{size_kb:.1} KB")
+    } else {
+        format!("{bytes} B")
+    }
 }
 
 /// EXIF metadata (optional, mainly for JPEG/TIFF).
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct ExifMeta {
     pub camera_make: Option<String>,
     pub camera_model: Option<String>,
@@ -70,8 +109,17 @@ pub struct ExifMeta {
     pub f_number: Option<String>,
     pub iso: Option<u32>,
     pub focal_length: Option<String>,
+    pub lens_model: Option<String>,
+    pub flash: Option<String>,
+    pub white_balance: Option<String>,
+    pub metering_mode: Option<String>,
+    pub exposure_program: Option<String>,
+    /// Raw EXIF orientation value (1-8).
+    pub orientation: Option<u32>,
     pub gps_latitude: Option<f64>,
     pub gps_longitude: Option<f64>,
+    /// Horizontal print resolution, if the file states one.
+    pub pixels_per_inch: Option<f64>,
 }
 
 impl ExifMeta {
@@ -91,6 +139,22 @@ impl ExifMeta {
         }
     }
 
+    /// Describe the EXIF orientation for display.
+    pub fn orientation_display(&self) -> Option<String> {
+        let text = match self.orientation? {
+            1 => "Normal",
+            2 => "Mirrored horizontally",
+            3 => "Rotated 180°",
+            4 => "Mirrored vertically",
+            5 => "Mirrored horizontally, rotated 270° CW",
+            6 => "Rotated 90° CW",
+            7 => "Mirrored horizontally, rotated 90° CW",
+            8 => "Rotated 270° CW",
+            _ => return None,
+        };
+        Some(text.to_string())
+    }
+
     /// Format GPS coordinates for display.
     pub fn gps_display(&self) -> Option<String> {
         match (self.gps_latitude, self.gps_longitude) {
@@ -100,11 +164,112 @@ impl ExifMeta {
     }
 }
 
+/// Descriptive metadata from IPTC and XMP blocks.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DescriptiveMeta {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub keywords: Vec<String>,
+    pub copyright: Option<String>,
+    pub creator: Option<String>,
+    /// Star rating (0-5).
+    pub rating: Option<u8>,
+    /// Marked as rejected (XMP rating -1).
+    pub rejected: bool,
+    /// Color label (e.g. "Red").
+    pub label: Option<String>,
+    /// Fields taken from a sidecar `.xmp` file instead of the image.
+    pub sidecar: SidecarFields,
+}
+
+/// Which descriptive fields come from a sidecar `.xmp` file.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct SidecarFields {
+    pub keywords: bool,
+    pub rating: bool,
+    pub label: bool,
+}
+
+impl DescriptiveMeta {
+    /// Fill fields missing in `self` from `other`.
+    #[must_use]
+    pub fn merge(mut self, other: DescriptiveMeta) -> Self {
+        self.title = self.title.or(other.title);
+        self.description = self.description.or(other.description);
+        if self.keywords.is_empty() {
+            self.keywords = other.keywords;
+        }
+        self.copyright = self.copyright.or(other.copyright);
+        self.creator = self.creator.or(other.creator);
+        if self.rating.is_none() && !self.rejected {
+            self.rating = other.rating;
+            self.rejected = other.rejected;
+        }
+        self.label = self.label.or(other.label);
+        self
+    }
+
+    /// Override keywords, rating and label with the values from a sidecar file.
+    ///
+    /// Catalog tools write these to the sidecar, so it is more current than
+    /// the metadata embedded in the image.
+    #[must_use]
+    pub fn merge_sidecar(mut self, sidecar: DescriptiveMeta) -> Self {
+        if !sidecar.keywords.is_empty() {
+            self.keywords = sidecar.keywords;
+            self.sidecar.keywords = true;
+        }
+        if sidecar.rating.is_some() || sidecar.rejected {
+            self.rating = sidecar.rating;
+            self.rejected = sidecar.rejected;
+            self.sidecar.rating = true;
+        }
+        if sidecar.label.is_some() {
+            self.label = sidecar.label;
+            self.sidecar.label = true;
+        }
+        self
+    }
+
+    /// Check whether any field carries a value.
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.description.is_none()
+            && self.keywords.is_empty()
+            && self.copyright.is_none()
+            && self.creator.is_none()
+            && self.rating.is_none()
+            && !self.rejected
+            && self.label.is_none()
+    }
+
+    /// Keywords joined for display.
+    pub fn keywords_display(&self) -> Option<String> {
+        (!self.keywords.is_empty()).then(|| self.keywords.join(", "))
+    }
+
+    /// Rating as stars for display (e.g. "★★★☆☆").
+    pub fn rating_display(&self) -> Option<String> {
+        self.rating.map(|r| {
+            let filled = usize::from(r.min(5));
+            format!("{}{}", "★".repeat(filled), "☆".repeat(5 - filled))
+        })
+    }
+}
+
 /// Complete document metadata container.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct DocumentMeta {
     pub basic: BasicMeta,
     pub exif: Option<ExifMeta>,
+    pub descriptive: Option<DescriptiveMeta>,
+}
+
+impl DocumentMeta {
+    /// Serialize all fields as pretty-printed JSON (for cataloging scripts).
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -136,9 +301,75 @@ fn extract_basic_meta(
         height,
         file_size,
         color_type,
+        display_size: None,
+        frame_count: None,
     }
 }
 
+/// Read the embedded EXIF thumbnail (JPEG bytes), if present.
+pub fn read_exif_thumbnail(path: &Path) -> Option<Vec<u8>> {
+    let file = std::fs::File::open(path).ok()?;
+    let mut reader = std::io::BufReader::new(file);
+    let exif = ExifReader::new().read_from_container(&mut reader).ok()?;
+
+    let offset = exif
+        .get_field(Tag::JPEGInterchangeFormat, In::THUMBNAIL)?
+        .value
+        .get_uint(0)? as usize;
+    let len = exif
+        .get_field(Tag::JPEGInterchangeFormatLength, In::THUMBNAIL)?
+        .value
+        .get_uint(0)? as usize;
+
+    exif.buf().get(offset..offset + len).map(<[u8]>::to_vec)
+}
+
+/// Read the EXIF orientation (1-8), if present.
+pub fn read_exif_orientation(path: &Path) -> Option<u32> {
+    let file = std::fs::File::open(path).ok()?;
+    let mut reader = std::io::BufReader::new(file);
+    let exif = ExifReader::new().read_from_container(&mut reader).ok()?;
+
+    exif.get_field(Tag::Orientation, In::PRIMARY)?
+        .value
+        .get_uint(0)
+}
+
+/// Read the EXIF capture date (`DateTimeOriginal`) as seconds since the Unix epoch.
+///
+/// The EXIF time has no zone and is interpreted as UTC; this is only used
+/// for ordering. Returns None if the file has no capture date.
+pub fn read_capture_timestamp(path: &Path) -> Option<i64> {
+    let file = std::fs::File::open(path).ok()?;
+    let mut reader = std::io::BufReader::new(file);
+    let exif = ExifReader::new().read_from_container(&mut reader).ok()?;
+
+    let field = exif.get_field(Tag::DateTimeOriginal, In::PRIMARY)?;
+    let Value::Ascii(ref vals) = field.value else {
+        return None;
+    };
+    let dt = exif::DateTime::from_ascii(vals.first()?).ok()?;
+
+    let days = days_from_civil(i64::from(dt.year), i64::from(dt.month), i64::from(dt.day));
+    Some(
+        days * 86_400
+            + i64::from(dt.hour) * 3_600
+            + i64::from(dt.minute) * 60
+            + i64::from(dt.second),
+    )
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date (Howard Hinnant's algorithm).
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
 /// Extract EXIF metadata from file bytes.
 fn extract_exif_from_bytes(data: &[u8]) -> Option<ExifMeta> {
     let mut cursor = Cursor::new(data);
@@ -163,28 +394,162 @@ fn extract_exif_from_bytes(data: &[u8]) -> Option<ExifMeta> {
 
     // Exposure settings.
     if let Some(field) = exif.get_field(Tag::ExposureTime, In::PRIMARY) {
-        meta.exposure_time = Some(field.display_value().to_string());
+        meta.exposure_time =
+            format_exposure_time(&field.value).or_else(|| Some(field.display_value().to_string()));
     }
     if let Some(field) = exif.get_field(Tag::FNumber, In::PRIMARY) {
-        meta.f_number = Some(format!("f/{}", field.display_value()));
-    }
-    if let Some(field) = exif.get_field(Tag::PhotographicSensitivity, In::PRIMARY)
-        && let Value::Short(ref vals) = field.value
-        && let Some(&iso) = vals.first()
-    {
-        meta.iso = Some(u32::from(iso));
+        meta.f_number = value_number(&field.value)
+            .map(|f| format!("f/{}", (f * 10.0).round() / 10.0))
+            .or_else(|| Some(format!("f/{}", field.display_value())));
     }
+    meta.iso = extract_iso(&exif);
     if let Some(field) = exif.get_field(Tag::FocalLength, In::PRIMARY) {
         meta.focal_length = Some(field.display_value().to_string());
     }
+    if let Some(field) = exif.get_field(Tag::ExposureProgram, In::PRIMARY) {
+        meta.exposure_program = Some(field.display_value().to_string());
+    }
+    if let Some(field) = exif.get_field(Tag::MeteringMode, In::PRIMARY) {
+        meta.metering_mode = Some(field.display_value().to_string());
+    }
+    if let Some(field) = exif.get_field(Tag::Flash, In::PRIMARY) {
+        meta.flash = Some(field.display_value().to_string());
+    }
+    if let Some(field) = exif.get_field(Tag::WhiteBalance, In::PRIMARY) {
+        meta.white_balance = Some(field.display_value().to_string());
+    }
+
+    // Lens.
+    if let Some(field) = exif.get_field(Tag::LensModel, In::PRIMARY) {
+        meta.lens_model = Some(field.display_value().to_string());
+    }
+
+    // Orientation.
+    if let Some(field) = exif.get_field(Tag::Orientation, In::PRIMARY) {
+        meta.orientation = field.value.get_uint(0);
+    }
 
     // GPS coordinates.
     meta.gps_latitude = extract_gps_coord(&exif, Tag::GPSLatitude, Tag::GPSLatitudeRef);
     meta.gps_longitude = extract_gps_coord(&exif, Tag::GPSLongitude, Tag::GPSLongitudeRef);
 
+    // Resolution (for physical measurements).
+    meta.pixels_per_inch = extract_pixels_per_inch(&exif);
+
     Some(meta)
 }
 
+/// Extract IPTC and XMP descriptive metadata from file bytes.
+/// XMP values take precedence; IPTC fills in anything XMP lacks.
+fn extract_descriptive_from_bytes(data: &[u8]) -> Option<DescriptiveMeta> {
+    let from_xmp = xmp::find_packet(data).map(xmp::parse);
+    let from_iptc = iptc::parse(data);
+
+    let meta = match (from_xmp, from_iptc) {
+        (Some(x), Some(i)) => x.merge(i),
+        (Some(x), None) => x,
+        (None, Some(i)) => i,
+        (None, None) => return None,
+    };
+
+    (!meta.is_empty()).then_some(meta)
+}
+
+/// First value of a numeric EXIF field as a number.
+///
+/// Cameras disagree on value types (e.g. ISO as SHORT or LONG, exposure as
+/// RATIONAL or text), so every numeric type and numeric text is accepted.
+fn value_number(value: &Value) -> Option<f64> {
+    let number = match value {
+        Value::Byte(v) => f64::from(*v.first()?),
+        Value::Short(v) => f64::from(*v.first()?),
+        Value::Long(v) => f64::from(*v.first()?),
+        Value::SByte(v) => f64::from(*v.first()?),
+        Value::SShort(v) => f64::from(*v.first()?),
+        Value::SLong(v) => f64::from(*v.first()?),
+        Value::Rational(v) => v.first()?.to_f64(),
+        Value::SRational(v) => v.first()?.to_f64(),
+        Value::Float(v) => f64::from(*v.first()?),
+        Value::Double(v) => *v.first()?,
+        Value::Ascii(v) => parse_number(&String::from_utf8_lossy(v.first()?))?,
+        _ => return None,
+    };
+    number.is_finite().then_some(number)
+}
+
+/// Parse numeric text such as "200", "2.8", "F2.8", "f/2.8" or "1/250".
+fn parse_number(text: &str) -> Option<f64> {
+    let text = text
+        .trim()
+        .trim_start_matches(['f', 'F'])
+        .trim_start_matches('/')
+        .trim_end_matches(['s', ' ']);
+    match text.split_once('/') {
+        Some((num, den)) => {
+            let den: f64 = den.trim().parse().ok()?;
+            (den != 0.0).then_some(num.trim().parse::<f64>().ok()? / den)
+        }
+        None => text.parse().ok(),
+    }
+}
+
+/// Format an exposure time as a fraction ("1/250") or in seconds ("2").
+fn format_exposure_time(value: &Value) -> Option<String> {
+    let seconds = value_number(value).filter(|s| *s > 0.0)?;
+    if seconds < 1.0 {
+        Some(format!("1/{}", (1.0 / seconds).round()))
+    } else {
+        Some(format!("{}", (seconds * 10.0).round() / 10.0))
+    }
+}
+
+/// ISO speed, falling back to the tags some cameras use instead.
+///
+/// 65535 in PhotographicSensitivity means the value didn't fit in a SHORT;
+/// the actual value then is in one of the other tags.
+fn extract_iso(exif: &exif::Exif) -> Option<u32> {
+    let values: Vec<f64> = [
+        Tag::PhotographicSensitivity,
+        Tag::RecommendedExposureIndex,
+        Tag::ISOSpeed,
+        Tag::StandardOutputSensitivity,
+    ]
+    .into_iter()
+    .filter_map(|tag| exif.get_field(tag, In::PRIMARY))
+    .filter_map(|field| value_number(&field.value))
+    .filter(|iso| *iso > 0.0 && *iso <= f64::from(u32::MAX))
+    .collect();
+
+    let iso = values
+        .iter()
+        .copied()
+        .find(|iso| *iso != f64::from(u16::MAX))
+        .or_else(|| values.first().copied())?;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Some(iso.round() as u32)
+}
+
+/// Read the horizontal resolution in pixels per inch (XResolution, ResolutionUnit).
+fn extract_pixels_per_inch(exif: &exif::Exif) -> Option<f64> {
+    let field = exif.get_field(Tag::XResolution, In::PRIMARY)?;
+    let Value::Rational(rats) = &field.value else {
+        return None;
+    };
+    let resolution = rats.first()?.to_f64();
+
+    // ResolutionUnit: 2 = inch (default), 3 = centimeter, 1 = no absolute unit.
+    let unit = exif
+        .get_field(Tag::ResolutionUnit, In::PRIMARY)
+        .and_then(|field| field.value.get_uint(0))
+        .unwrap_or(2);
+    let ppi = match unit {
+        2 => resolution,
+        3 => resolution * CM_PER_INCH,
+        _ => return None,
+    };
+    (ppi.is_finite() && ppi > 0.0).then_some(ppi)
+}
+
 /// Extract a GPS coordinate (latitude or longitude) from EXIF data.
 fn extract_gps_coord(exif: &exif::Exif, coord_tag: Tag, ref_tag: Tag) -> Option<f64> {
     let field = exif.get_field(coord_tag, In::PRIMARY)?;
@@ -214,6 +579,99 @@ fn extract_gps_coord(exif: &exif::Exif, coord_tag: Tag, ref_tag: Tag) -> Option<
     Some(degrees * sign)
 }
 
+/// Number of frames if the data is an animated GIF, PNG or WebP.
+///
+/// Walks the container structure only, without decoding any frame.
+fn animation_frames(data: &[u8]) -> Option<u32> {
+    let frames = if data.starts_with(b"GIF8") {
+        gif_frame_count(data)
+    } else if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        apng_frame_count(data)
+    } else if data.starts_with(b"RIFF") && data.get(8..12) == Some(b"WEBP".as_slice()) {
+        webp_frame_count(data)
+    } else {
+        None
+    };
+    frames.filter(|&n| n > 1)
+}
+
+/// Count the image descriptors of a GIF.
+fn gif_frame_count(data: &[u8]) -> Option<u32> {
+    // Header (6) and logical screen descriptor (7), then the global color table.
+    let flags = *data.get(10)?;
+    let mut pos = 13;
+    if flags & 0x80 != 0 {
+        pos += 3 << ((flags & 0x07) + 1);
+    }
+
+    // A truncated file still reports the frames read so far.
+    let mut frames = 0;
+    while let Some(&block) = data.get(pos) {
+        let next = match block {
+            // Extension: label, then data sub-blocks.
+            0x21 => skip_gif_sub_blocks(data, pos + 2),
+            // Image descriptor (10), local color table, LZW code size, then data sub-blocks.
+            0x2C => data.get(pos + 9).and_then(|&flags| {
+                let mut start = pos + 10;
+                if flags & 0x80 != 0 {
+                    start += 3 << ((flags & 0x07) + 1);
+                }
+                frames += 1;
+                skip_gif_sub_blocks(data, start + 1)
+            }),
+            // Trailer (or an unknown block).
+            _ => None,
+        };
+        let Some(next) = next else { break };
+        pos = next;
+    }
+    Some(frames)
+}
+
+/// Position after the GIF sub-block chain starting at `pos`.
+fn skip_gif_sub_blocks(data: &[u8], mut pos: usize) -> Option<usize> {
+    loop {
+        let len = usize::from(*data.get(pos)?);
+        pos += 1 + len;
+        if len == 0 {
+            return Some(pos);
+        }
+    }
+}
+
+/// Frame count from the animation control chunk (`acTL`) of an APNG.
+fn apng_frame_count(data: &[u8]) -> Option<u32> {
+    let mut pos = 8;
+    while let Some(header) = data.get(pos..pos + 8) {
+        let len = u32::from_be_bytes(header[..4].try_into().ok()?) as usize;
+        match &header[4..] {
+            b"acTL" => {
+                let frames = data.get(pos + 8..pos + 12)?;
+                return Some(u32::from_be_bytes(frames.try_into().ok()?));
+            }
+            // The animation control chunk must precede the image data.
+            b"IDAT" => return None,
+            _ => pos += 12 + len,
+        }
+    }
+    None
+}
+
+/// Count the animation frames (`ANMF` chunks) of a WebP.
+fn webp_frame_count(data: &[u8]) -> Option<u32> {
+    let mut pos = 12;
+    let mut frames = 0;
+    while let Some(header) = data.get(pos..pos + 8) {
+        let len = u32::from_le_bytes(header[4..].try_into().ok()?) as usize;
+        if &header[..4] == b"ANMF" {
+            frames += 1;
+        }
+        // Chunks are padded to an even size.
+        pos += 8 + len + (len & 1);
+    }
+    Some(frames)
+}
+
 /// Determine color type string from DynamicImage.
 fn color_type_string(img: &DynamicImage) -> String {
     use image::DynamicImage::{
@@ -250,19 +708,69 @@ fn format_from_extension(path: &Path) -> String {
 pub fn build_raster_meta(path: &Path, img: &DynamicImage, width: u32, height: u32) -> DocumentMeta {
     let format = format_from_extension(path);
     let color_type = color_type_string(img);
-    let basic = extract_basic_meta(path, width, height, &format, color_type);
+    let mut basic = extract_basic_meta(path, width, height, &format, color_type);
+
+    // Try to extract EXIF (mainly for JPEG/TIFF) and IPTC/XMP.
+    let bytes = file::read_file_bytes(path);
+    basic.frame_count = bytes.as_deref().and_then(animation_frames);
+    let exif = bytes.as_deref().and_then(extract_exif_from_bytes);
+    let embedded = bytes.as_deref().and_then(extract_descriptive_from_bytes);
+    let descriptive = match xmp::read_sidecar(path) {
+        Some(sidecar) => {
+            let merged = embedded.unwrap_or_default().merge_sidecar(sidecar);
+            (!merged.is_empty()).then_some(merged)
+        }
+        None => embedded,
+    };
 
-    // Try to extract EXIF (mainly for JPEG/TIFF).
-    let exif = file::read_file_bytes(path).and_then(|bytes| extract_exif_from_bytes(&bytes));
+    DocumentMeta {
+        basic,
+        exif,
+        descriptive,
+    }
+}
+
+/// Build metadata for a raster document decoded from memory (stdin, URL).
+pub fn build_memory_meta(
+    name: &str,
+    bytes: &[u8],
+    img: &DynamicImage,
+    width: u32,
+    height: u32,
+) -> DocumentMeta {
+    let format = image::guess_format(bytes).map_or_else(
+        |_| "Unknown".to_string(),
+        |f| format!("{f:?}").to_uppercase(),
+    );
+
+    let basic = BasicMeta {
+        file_name: name.to_string(),
+        file_path: name.to_string(),
+        format,
+        width,
+        height,
+        file_size: bytes.len() as u64,
+        color_type: color_type_string(img),
+        display_size: None,
+        frame_count: animation_frames(bytes),
+    };
 
-    DocumentMeta { basic, exif }
+    DocumentMeta {
+        basic,
+        exif: extract_exif_from_bytes(bytes),
+        descriptive: extract_descriptive_from_bytes(bytes),
+    }
 }
 
 /// Build metadata for a vector document.
 pub fn build_vector_meta(path: &Path, width: u32, height: u32) -> DocumentMeta {
     let basic = extract_basic_meta(path, width, height, "SVG", "Vector".to_string());
 
-    DocumentMeta { basic, exif: None }
+    DocumentMeta {
+        basic,
+        exif: None,
+        descriptive: None,
+    }
 }
 
 /// Build metadata for a portable document.
@@ -270,5 +778,164 @@ pub fn build_portable_meta(path: &Path, width: u32, height: u32, page_count: u32
     let format = format!("PDF ({page_count} pages)");
     let basic = extract_basic_meta(path, width, height, &format, "Rendered".to_string());
 
-    DocumentMeta { basic, exif: None }
+    DocumentMeta {
+        basic,
+        exif: None,
+        descriptive: None,
+    }
+}
+
+/// Build metadata for a DjVu document.
+pub fn build_djvu_meta(path: &Path, width: u32, height: u32, page_count: u32) -> DocumentMeta {
+    let format = format!("DjVu ({page_count} pages)");
+    let basic = extract_basic_meta(path, width, height, &format, "Rendered".to_string());
+
+    DocumentMeta {
+        basic,
+        exif: None,
+        descriptive: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHORT: u16 = 3;
+    const LONG: u16 = 4;
+
+    /// Raw EXIF (TIFF structure) with `Make` in IFD0 and the given
+    /// `(tag, type, value)` entries in the Exif IFD, sorted by tag.
+    fn exif_with(big_endian: bool, make: &str, entries: &[(u16, u16, u32)]) -> exif::Exif {
+        let u16_bytes = |v: u16| {
+            if big_endian {
+                v.to_be_bytes()
+            } else {
+                v.to_le_bytes()
+            }
+        };
+        let u32_bytes = |v: u32| {
+            if big_endian {
+                v.to_be_bytes()
+            } else {
+                v.to_le_bytes()
+            }
+        };
+        let count = |n: usize| u32_bytes(u32::try_from(n).unwrap());
+
+        let mut make = make.as_bytes().to_vec();
+        make.push(0);
+        // Header (8), IFD0 with two entries (30), then the make string.
+        let make_offset = 38;
+        let exif_offset = (make_offset + make.len() + 1) & !1;
+
+        let mut blob = Vec::new();
+        blob.extend_from_slice(if big_endian { b"MM" } else { b"II" });
+        blob.extend_from_slice(&u16_bytes(42));
+        blob.extend_from_slice(&count(8));
+
+        blob.extend_from_slice(&u16_bytes(2));
+        blob.extend_from_slice(&u16_bytes(0x010F));
+        blob.extend_from_slice(&u16_bytes(2));
+        blob.extend_from_slice(&count(make.len()));
+        blob.extend_from_slice(&count(make_offset));
+        blob.extend_from_slice(&u16_bytes(0x8769));
+        blob.extend_from_slice(&u16_bytes(LONG));
+        blob.extend_from_slice(&count(1));
+        blob.extend_from_slice(&count(exif_offset));
+        blob.extend_from_slice(&count(0));
+
+        blob.extend_from_slice(&make);
+        blob.resize(exif_offset, 0);
+
+        blob.extend_from_slice(&u16_bytes(u16::try_from(entries.len()).unwrap()));
+        for &(tag, kind, value) in entries {
+            blob.extend_from_slice(&u16_bytes(tag));
+            blob.extend_from_slice(&u16_bytes(kind));
+            blob.extend_from_slice(&count(1));
+            if kind == SHORT {
+                blob.extend_from_slice(&u16_bytes(u16::try_from(value).unwrap()));
+                blob.extend_from_slice(&[0, 0]);
+            } else {
+                blob.extend_from_slice(&u32_bytes(value));
+            }
+        }
+        blob.extend_from_slice(&count(0));
+
+        ExifReader::new().read_raw(blob).expect("valid EXIF blob")
+    }
+
+    #[test]
+    fn iso_from_photographic_sensitivity() {
+        // Canon writes little-endian EXIF with a SHORT ISO.
+        let exif = exif_with(false, "Canon", &[(0x8827, SHORT, 400)]);
+        assert_eq!(extract_iso(&exif), Some(400));
+
+        // Nikon writes big-endian EXIF.
+        let exif = exif_with(true, "NIKON CORPORATION", &[(0x8827, SHORT, 6400)]);
+        assert_eq!(extract_iso(&exif), Some(6400));
+    }
+
+    #[test]
+    fn iso_beyond_short_range() {
+        // Sony: PhotographicSensitivity saturates, SensitivityType 2 and
+        // RecommendedExposureIndex hold the actual value.
+        let exif = exif_with(
+            false,
+            "SONY",
+            &[
+                (0x8827, SHORT, 65535),
+                (0x8830, SHORT, 2),
+                (0x8832, LONG, 102_400),
+            ],
+        );
+        assert_eq!(extract_iso(&exif), Some(102_400));
+
+        // Pentax: ISOSpeed instead.
+        let exif = exif_with(
+            false,
+            "RICOH IMAGING COMPANY, LTD.",
+            &[(0x8827, SHORT, 65535), (0x8833, LONG, 204_800)],
+        );
+        assert_eq!(extract_iso(&exif), Some(204_800));
+
+        // Fujifilm: StandardOutputSensitivity only.
+        let exif = exif_with(true, "FUJIFILM", &[(0x8831, LONG, 12_800)]);
+        assert_eq!(extract_iso(&exif), Some(12_800));
+    }
+
+    #[test]
+    fn iso_saturated_without_fallback() {
+        let exif = exif_with(false, "OLYMPUS", &[(0x8827, SHORT, 65535)]);
+        assert_eq!(extract_iso(&exif), Some(65535));
+    }
+
+    #[test]
+    fn iso_missing_or_zero() {
+        let exif = exif_with(false, "Apple", &[]);
+        assert_eq!(extract_iso(&exif), None);
+
+        let exif = exif_with(false, "Apple", &[(0x8827, SHORT, 0)]);
+        assert_eq!(extract_iso(&exif), None);
+    }
+
+    #[test]
+    fn parse_number_formats() {
+        assert_eq!(parse_number("200"), Some(200.0));
+        assert_eq!(parse_number(" 400 "), Some(400.0));
+        assert_eq!(parse_number("2.8"), Some(2.8));
+        assert_eq!(parse_number("F2.8"), Some(2.8));
+        assert_eq!(parse_number("f/2.8"), Some(2.8));
+        assert_eq!(parse_number("1/250"), Some(0.004));
+        assert_eq!(parse_number("1/250 s"), Some(0.004));
+        assert_eq!(parse_number("30s"), Some(30.0));
+    }
+
+    #[test]
+    fn parse_number_rejects_invalid() {
+        assert_eq!(parse_number(""), None);
+        assert_eq!(parse_number("auto"), None);
+        assert_eq!(parse_number("1/0"), None);
+        assert_eq!(parse_number("1/x"), None);
+    }
 }
@@ -3,11 +3,24 @@
 //
 // Document module root: common enums and type erasure for document kinds.
 
+pub mod archive;
+pub mod batch;
 pub mod cache;
+#[cfg(feature = "image")]
+pub mod cmyk;
 pub mod file;
+pub mod icon;
+pub mod iptc;
 pub mod meta;
+pub mod registry;
+#[cfg(feature = "image")]
+pub mod tiff;
 pub mod utils;
+pub mod watch;
+pub mod xmp;
 
+#[cfg(feature = "djvu")]
+pub mod djvu;
 #[cfg(feature = "portable")]
 pub mod portable;
 #[cfg(feature = "image")]
@@ -15,12 +28,14 @@ pub mod raster;
 #[cfg(feature = "vector")]
 pub mod vector;
 
-use cosmic::iced_renderer::graphics::image::image_rs::ImageFormat as CosmicImageFormat;
 #[cfg(feature = "image")]
 use image::GenericImageView;
 use std::fmt;
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 
+#[cfg(feature = "djvu")]
+use self::djvu::DjvuDocument;
 #[cfg(feature = "portable")]
 use self::portable::PortableDocument;
 #[cfg(feature = "image")]
@@ -104,11 +119,62 @@ pub struct TransformState {
     pub flip_v: bool,
 }
 
+impl TransformState {
+    /// Whether no rotation or flip is applied.
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Transform that displays an image with the given EXIF orientation (1-8) upright.
+    pub fn from_exif_orientation(orientation: u32) -> Self {
+        let (rotation, flip_h, flip_v) = match orientation {
+            2 => (Rotation::None, true, false),
+            3 => (Rotation::Cw180, false, false),
+            4 => (Rotation::None, false, true),
+            5 => (Rotation::Cw270, true, false),
+            6 => (Rotation::Cw90, false, false),
+            7 => (Rotation::Cw90, true, false),
+            8 => (Rotation::Cw270, false, false),
+            _ => (Rotation::None, false, false),
+        };
+        Self {
+            rotation,
+            flip_h,
+            flip_v,
+        }
+    }
+}
+
+/// Destination of a clickable link inside a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkTarget {
+    /// Internal link to a page (0-based).
+    Page(usize),
+    /// External URI, opened with the default handler.
+    Uri(String),
+}
+
+/// Clickable link area in displayed pixel coordinates (after transforms).
+#[derive(Debug, Clone)]
+pub struct LinkRegion {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub target: LinkTarget,
+}
+
+impl LinkRegion {
+    /// Check whether a point (in displayed pixel coordinates) lies inside the region.
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.x + self.width && y >= self.y && y <= self.y + self.height
+    }
+}
+
 /// Output of a render operation.
 ///
 /// Used as return type for the `Renderable::render()` trait method.
 /// Not constructed externally - only returned by trait implementations.
-#[allow(dead_code)]
 pub struct RenderOutput {
     /// Image handle for display.
     pub handle: ImageHandle,
@@ -142,12 +208,15 @@ pub struct DocumentInfo {
 /// This trait is used internally through type erasure via `DocumentContent`.
 /// The UI layer calls methods on `DocumentContent`, which delegates to the
 /// specific document type implementations (Raster, Vector, Portable).
-#[allow(dead_code)]
 pub trait Renderable {
     /// Render the document at the given scale factor.
+    ///
+    /// The scale is the window scale factor; resolution-independent documents
+    /// re-render when it changes, others return their current image.
     fn render(&mut self, scale: f64) -> DocResult<RenderOutput>;
 
     /// Get document information (dimensions, format).
+    #[allow(dead_code)]
     fn info(&self) -> DocumentInfo;
 }
 
@@ -161,6 +230,26 @@ pub trait Transformable {
 
     /// Get the current transformation state.
     fn transform_state(&self) -> TransformState;
+
+    /// Undo all rotations and flips.
+    fn reset_transform(&mut self) {
+        self.set_transform(TransformState::default());
+    }
+
+    /// Switch to the given transformation state.
+    ///
+    /// Rotation is undone first so that the flips are applied in source orientation.
+    fn set_transform(&mut self, state: TransformState) {
+        self.rotate(Rotation::None);
+        let current = self.transform_state();
+        if current.flip_h != state.flip_h {
+            self.flip(FlipDirection::Horizontal);
+        }
+        if current.flip_v != state.flip_v {
+            self.flip(FlipDirection::Vertical);
+        }
+        self.rotate(state.rotation);
+    }
 }
 
 /// Trait for documents with multiple pages.
@@ -177,7 +266,8 @@ pub trait MultiPage {
 
 /// Trait for multi-page documents that support thumbnail generation.
 ///
-/// Currently implemented only by `PortableDocument` (PDF).
+/// Implemented by `PortableDocument` (PDF), `DjvuDocument`, and `RasterDocument`
+/// for multi-resolution icons (ICO/CUR).
 /// Methods are called through `DocumentContent` type erasure.
 #[allow(dead_code)]
 pub trait MultiPageThumbnails: MultiPage {
@@ -195,6 +285,9 @@ pub trait MultiPageThumbnails: MultiPage {
 
     /// Generate all thumbnails (blocking).
     fn generate_all_thumbnails(&mut self);
+
+    /// Drop loaded thumbnails so they are generated again (e.g. at a larger size).
+    fn reset_thumbnails(&mut self);
 }
 
 // ============================================================================
@@ -207,30 +300,14 @@ pub enum DocumentKind {
     Raster,
     Vector,
     Portable,
+    Djvu,
 }
 
 impl DocumentKind {
-    /// Detect document kind from file path.
+    /// Detect document kind from the file extension (without reading the file).
     #[must_use]
     pub fn from_path(path: &Path) -> Option<Self> {
-        let ext = path.extension()?.to_str()?.to_lowercase();
-
-        // SVG
-        if ext == "svg" || ext == "svgz" {
-            return Some(Self::Vector);
-        }
-
-        // PDF
-        if ext == "pdf" {
-            return Some(Self::Portable);
-        }
-
-        // Raster: Check via cosmic/image-rs
-        if CosmicImageFormat::from_path(path).is_ok() {
-            return Some(Self::Raster);
-        }
-
-        None
+        registry::lookup(path).map(|format| format.kind)
     }
 }
 
@@ -240,6 +317,7 @@ impl fmt::Display for DocumentKind {
             Self::Raster => write!(f, "Raster"),
             Self::Vector => write!(f, "Vector"),
             Self::Portable => write!(f, "Portable"),
+            Self::Djvu => write!(f, "DjVu"),
         }
     }
 }
@@ -257,14 +335,142 @@ pub fn create_image_handle(pixels: Vec<u8>, width: u32, height: u32) -> ImageHan
     cosmic::widget::image::Handle::from_rgba(width, height, pixels)
 }
 
+/// Maximum side length of display textures (see `AppConfig::max_texture_size`).
+static MAX_TEXTURE_SIZE: AtomicU32 = AtomicU32::new(8192);
+
+/// Set the maximum side length for display textures.
+pub fn set_max_texture_size(size: u32) {
+    MAX_TEXTURE_SIZE.store(size.max(1), Ordering::Relaxed);
+}
+
+/// Largest raster image in pixels decoded without asking (see
+/// `AppConfig::max_decode_megapixels`); 0 disables the check.
+static MAX_DECODE_PIXELS: AtomicU64 = AtomicU64::new(500_000_000);
+
+/// Set the largest raster image decoded without asking, in megapixels.
+pub fn set_max_decode_megapixels(megapixels: u32) {
+    MAX_DECODE_PIXELS.store(u64::from(megapixels) * 1_000_000, Ordering::Relaxed);
+}
+
+/// Largest raster image in pixels decoded without asking (0 = no limit).
+#[must_use]
+pub fn max_decode_pixels() -> u64 {
+    MAX_DECODE_PIXELS.load(Ordering::Relaxed)
+}
+
+/// Width in pixels page thumbnails are shown at (see `AppConfig::thumbnail_width`).
+static THUMBNAIL_WIDTH: AtomicU32 = AtomicU32::new(100);
+
+/// Set the width page thumbnails are shown at.
+pub fn set_thumbnail_width(width: u32) {
+    THUMBNAIL_WIDTH.store(width.max(1), Ordering::Relaxed);
+}
+
+/// Width in pixels page thumbnails need at least to look sharp.
+#[must_use]
+pub fn thumbnail_width() -> u32 {
+    THUMBNAIL_WIDTH.load(Ordering::Relaxed)
+}
+
+/// Size at which an image of the given dimensions is displayed.
+///
+/// Images exceeding the max texture size are scaled down, keeping the aspect ratio.
+#[must_use]
+pub fn display_dimensions(width: u32, height: u32) -> (u32, u32) {
+    let max_side = MAX_TEXTURE_SIZE.load(Ordering::Relaxed);
+    if width <= max_side && height <= max_side {
+        return (width, height);
+    }
+    let ratio = f64::from(max_side) / f64::from(width.max(height));
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let scale = |v: u32| ((f64::from(v) * ratio).round() as u32).max(1);
+    (scale(width), scale(height))
+}
+
+/// Window scale factor (HiDPI) for rendering PDFs and SVGs, stored as `f32` bits.
+static DISPLAY_SCALE: AtomicU32 = AtomicU32::new(1.0_f32.to_bits());
+
+/// Set the window scale factor. Returns true if it changed.
+pub fn set_display_scale(scale: f32) -> bool {
+    let bits = if scale > 0.0 { scale } else { 1.0 }.to_bits();
+    DISPLAY_SCALE.swap(bits, Ordering::Relaxed) != bits
+}
+
+/// Window scale factor that resolution-independent documents are rendered at,
+/// so they stay sharp on HiDPI displays.
+#[must_use]
+pub fn display_scale() -> f64 {
+    f64::from(f32::from_bits(DISPLAY_SCALE.load(Ordering::Relaxed)))
+}
+
+/// Whether SVG text is rendered with system fonts (see `AppConfig::svg_system_fonts`).
+static LOAD_SYSTEM_FONTS: AtomicBool = AtomicBool::new(true);
+
+/// Enable or disable system fonts for SVG documents opened afterwards.
+pub fn set_load_system_fonts(enabled: bool) {
+    LOAD_SYSTEM_FONTS.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether SVG documents should load the system font database.
+#[must_use]
+pub fn load_system_fonts() -> bool {
+    LOAD_SYSTEM_FONTS.load(Ordering::Relaxed)
+}
+
+/// Whether PDF pages are rendered without a white background
+/// (see `AppConfig::pdf_transparent_background`).
+static PDF_TRANSPARENT_BACKGROUND: AtomicBool = AtomicBool::new(false);
+
+/// Render PDF pages onto a transparent instead of a white background.
+pub fn set_pdf_transparent_background(enabled: bool) {
+    PDF_TRANSPARENT_BACKGROUND.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether PDF pages should be rendered without a white background.
+#[must_use]
+pub fn pdf_transparent_background() -> bool {
+    PDF_TRANSPARENT_BACKGROUND.load(Ordering::Relaxed)
+}
+
 /// Create an image handle from a DynamicImage.
+///
+/// Images larger than the max texture size are downscaled for display only;
+/// the full-resolution image stays untouched for export. Samples deeper than
+/// 8 bits (16-bit PNG/TIFF) are rounded to the nearest 8-bit value, not truncated.
 #[must_use]
 pub fn create_image_handle_from_image(img: &image::DynamicImage) -> ImageHandle {
     let (width, height) = img.dimensions();
+    let (display_width, display_height) = display_dimensions(width, height);
+
+    if (display_width, display_height) != (width, height) {
+        let scaled = img.resize_exact(
+            display_width,
+            display_height,
+            image::imageops::FilterType::Triangle,
+        );
+        return create_image_handle(scaled.to_rgba8().into_raw(), display_width, display_height);
+    }
+
     let pixels = img.to_rgba8().into_raw();
     create_image_handle(pixels, width, height)
 }
 
+/// Crop a region (in pixels) from an image, clamped to the image bounds.
+///
+/// Used for high-resolution tiles at extreme zoom; also suited to sampling pixels.
+#[must_use]
+pub fn crop_region(
+    img: &image::DynamicImage,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+) -> image::DynamicImage {
+    let (img_width, img_height) = img.dimensions();
+    let (x, y) = (x.min(img_width), y.min(img_height));
+    img.crop_imm(x, y, width.min(img_width - x), height.min(img_height - y))
+}
+
 // ============================================================================
 // Document Content Enum
 // ============================================================================
@@ -278,7 +484,10 @@ pub fn create_image_handle_from_image(img: &image::DynamicImage) -> ImageHandle
 pub enum DocumentContent {
     Raster(RasterDocument),
     Vector(VectorDocument),
+    #[cfg(feature = "portable")]
     Portable(PortableDocument),
+    #[cfg(feature = "djvu")]
+    Djvu(DjvuDocument),
 }
 
 impl fmt::Debug for DocumentContent {
@@ -286,7 +495,10 @@ impl fmt::Debug for DocumentContent {
         match self {
             Self::Raster(_) => write!(f, "DocumentContent::Raster(...)"),
             Self::Vector(_) => write!(f, "DocumentContent::Vector(...)"),
+            #[cfg(feature = "portable")]
             Self::Portable(_) => write!(f, "DocumentContent::Portable(...)"),
+            #[cfg(feature = "djvu")]
+            Self::Djvu(_) => write!(f, "DocumentContent::Djvu(...)"),
         }
     }
 }
@@ -300,7 +512,10 @@ impl Renderable for DocumentContent {
         match self {
             Self::Raster(doc) => doc.render(scale),
             Self::Vector(doc) => doc.render(scale),
+            #[cfg(feature = "portable")]
             Self::Portable(doc) => doc.render(scale),
+            #[cfg(feature = "djvu")]
+            Self::Djvu(doc) => doc.render(scale),
         }
     }
 
@@ -308,7 +523,10 @@ impl Renderable for DocumentContent {
         match self {
             Self::Raster(doc) => doc.info(),
             Self::Vector(doc) => doc.info(),
+            #[cfg(feature = "portable")]
             Self::Portable(doc) => doc.info(),
+            #[cfg(feature = "djvu")]
+            Self::Djvu(doc) => doc.info(),
         }
     }
 }
@@ -318,7 +536,10 @@ impl Transformable for DocumentContent {
         match self {
             Self::Raster(doc) => doc.rotate(rotation),
             Self::Vector(doc) => doc.rotate(rotation),
+            #[cfg(feature = "portable")]
             Self::Portable(doc) => doc.rotate(rotation),
+            #[cfg(feature = "djvu")]
+            Self::Djvu(doc) => doc.rotate(rotation),
         }
     }
 
@@ -326,7 +547,10 @@ impl Transformable for DocumentContent {
         match self {
             Self::Raster(doc) => doc.flip(direction),
             Self::Vector(doc) => doc.flip(direction),
+            #[cfg(feature = "portable")]
             Self::Portable(doc) => doc.flip(direction),
+            #[cfg(feature = "djvu")]
+            Self::Djvu(doc) => doc.flip(direction),
         }
     }
 
@@ -334,7 +558,21 @@ impl Transformable for DocumentContent {
         match self {
             Self::Raster(doc) => doc.transform_state(),
             Self::Vector(doc) => doc.transform_state(),
+            #[cfg(feature = "portable")]
             Self::Portable(doc) => doc.transform_state(),
+            #[cfg(feature = "djvu")]
+            Self::Djvu(doc) => doc.transform_state(),
+        }
+    }
+
+    fn reset_transform(&mut self) {
+        match self {
+            Self::Raster(doc) => doc.reset_transform(),
+            Self::Vector(doc) => doc.reset_transform(),
+            #[cfg(feature = "portable")]
+            Self::Portable(doc) => doc.reset_transform(),
+            #[cfg(feature = "djvu")]
+            Self::Djvu(doc) => doc.reset_transform(),
         }
     }
 }
@@ -366,6 +604,46 @@ impl DocumentContent {
         self.flip(FlipDirection::Vertical);
     }
 
+    /// Flips as seen on screen (horizontal, vertical).
+    pub fn display_flips(&self) -> (bool, bool) {
+        let state = self.transform_state();
+        if self.flip_axes_swapped() {
+            (state.flip_v, state.flip_h)
+        } else {
+            (state.flip_h, state.flip_v)
+        }
+    }
+
+    /// Set both on-screen flips absolutely, keeping the current rotation.
+    pub fn set_flip(&mut self, horizontal: bool, vertical: bool) {
+        let (flip_h, flip_v) = if self.flip_axes_swapped() {
+            (vertical, horizontal)
+        } else {
+            (horizontal, vertical)
+        };
+        let state = self.transform_state();
+        if state.flip_h != flip_h || state.flip_v != flip_v {
+            self.set_transform(TransformState {
+                flip_h,
+                flip_v,
+                ..state
+            });
+        }
+    }
+
+    /// Whether the stored flips act on the other screen axis.
+    ///
+    /// Flips are kept in source orientation (applied before the rotation),
+    /// except for PDFs, which flip the rotated page. At 90°/270° a source
+    /// flip shows along the other axis.
+    fn flip_axes_swapped(&self) -> bool {
+        self.kind() != DocumentKind::Portable
+            && matches!(
+                self.transform_state().rotation,
+                Rotation::Cw90 | Rotation::Cw270
+            )
+    }
+
     /// Crop the document to the specified rectangle.
     ///
     /// Only supported for raster images. Returns an error for vector/PDF documents.
@@ -374,7 +652,56 @@ impl DocumentContent {
         match self {
             Self::Raster(doc) => doc.crop(x, y, width, height),
             Self::Vector(_) => Err(anyhow::anyhow!("Crop not supported for vector documents")),
+            #[cfg(feature = "portable")]
             Self::Portable(_) => Err(anyhow::anyhow!("Crop not supported for PDF documents")),
+            #[cfg(feature = "djvu")]
+            Self::Djvu(_) => Err(anyhow::anyhow!("Crop not supported for DjVu documents")),
+        }
+    }
+
+    /// Toggle auto levels (raster images only); the original stays untouched.
+    pub fn toggle_auto_enhance(&mut self) -> DocResult<()> {
+        match self {
+            Self::Raster(doc) => {
+                doc.set_auto_enhance(!doc.is_auto_enhanced());
+                Ok(())
+            }
+            _ => Err(anyhow::anyhow!(
+                "Auto enhance is only supported for raster images"
+            )),
+        }
+    }
+
+    /// Map the colors at `white` and `black` to pure white and black (raster images only).
+    pub fn set_levels(&mut self, white: (f32, f32), black: (f32, f32)) -> DocResult<()> {
+        match self {
+            Self::Raster(doc) => doc.set_levels(white, black),
+            _ => Err(anyhow::anyhow!(
+                "Black and white points are only supported for raster images"
+            )),
+        }
+    }
+
+    /// Drop black and white points, showing the original colors again.
+    pub fn clear_levels(&mut self) {
+        if let Self::Raster(doc) = self {
+            doc.clear_levels();
+        }
+    }
+
+    /// Toggle between the EXIF-corrected and the stored (sensor) orientation.
+    pub fn toggle_exif_orientation(&mut self) -> DocResult<()> {
+        match self {
+            Self::Raster(doc) if !doc.exif_transform().is_identity() => {
+                let target = if doc.transform_state() == doc.exif_transform() {
+                    TransformState::default()
+                } else {
+                    doc.exif_transform()
+                };
+                doc.set_transform(target);
+                Ok(())
+            }
+            _ => Err(anyhow::anyhow!("The image has no EXIF orientation")),
         }
     }
 
@@ -387,7 +714,10 @@ impl DocumentContent {
         match self {
             Self::Raster(_) => DocumentKind::Raster,
             Self::Vector(_) => DocumentKind::Vector,
+            #[cfg(feature = "portable")]
             Self::Portable(_) => DocumentKind::Portable,
+            #[cfg(feature = "djvu")]
+            Self::Djvu(_) => DocumentKind::Djvu,
         }
     }
 
@@ -401,16 +731,34 @@ impl DocumentContent {
     #[must_use]
     pub fn page_count(&self) -> Option<usize> {
         match self {
+            Self::Raster(doc) if doc.has_pages() => Some(doc.page_count()),
+            #[cfg(feature = "portable")]
             Self::Portable(doc) => Some(doc.page_count()),
+            #[cfg(feature = "djvu")]
+            Self::Djvu(doc) => Some(doc.page_count()),
             _ => None,
         }
     }
 
+    /// Display label of a page (PDF page label, else the 1-based number).
+    #[must_use]
+    pub fn page_label(&self, page: usize) -> String {
+        match self {
+            #[cfg(feature = "portable")]
+            Self::Portable(doc) => doc.page_label(page),
+            _ => (page + 1).to_string(),
+        }
+    }
+
     /// Get current page index if applicable.
     #[must_use]
     pub fn current_page(&self) -> Option<usize> {
         match self {
+            Self::Raster(doc) if doc.has_pages() => Some(doc.current_page()),
+            #[cfg(feature = "portable")]
             Self::Portable(doc) => Some(doc.current_page()),
+            #[cfg(feature = "djvu")]
+            Self::Djvu(doc) => Some(doc.current_page()),
             _ => None,
         }
     }
@@ -418,16 +766,34 @@ impl DocumentContent {
     /// Navigate to a specific page.
     pub fn go_to_page(&mut self, page: usize) -> DocResult<()> {
         match self {
+            Self::Raster(doc) if doc.has_pages() => doc.go_to_page(page),
+            #[cfg(feature = "portable")]
             Self::Portable(doc) => doc.go_to_page(page),
+            #[cfg(feature = "djvu")]
+            Self::Djvu(doc) => doc.go_to_page(page),
             _ => Err(anyhow::anyhow!("Document does not support multiple pages")),
         }
     }
 
+    /// Link regions on the current page, in displayed pixel coordinates.
+    #[must_use]
+    pub fn link_regions(&self) -> Vec<LinkRegion> {
+        match self {
+            #[cfg(feature = "portable")]
+            Self::Portable(doc) => doc.link_regions(),
+            _ => Vec::new(),
+        }
+    }
+
     /// Get cached thumbnail for a page.
     #[must_use]
     pub fn get_thumbnail(&self, page: usize) -> Option<ImageHandle> {
         match self {
+            Self::Raster(doc) if doc.has_pages() => doc.get_thumbnail(page),
+            #[cfg(feature = "portable")]
             Self::Portable(doc) => doc.get_thumbnail(page),
+            #[cfg(feature = "djvu")]
+            Self::Djvu(doc) => doc.get_thumbnail(page),
             _ => None,
         }
     }
@@ -436,7 +802,11 @@ impl DocumentContent {
     #[must_use]
     pub fn thumbnails_ready(&self) -> bool {
         match self {
+            Self::Raster(doc) if doc.has_pages() => doc.thumbnails_ready(),
+            #[cfg(feature = "portable")]
             Self::Portable(doc) => doc.thumbnails_ready(),
+            #[cfg(feature = "djvu")]
+            Self::Djvu(doc) => doc.thumbnails_ready(),
             _ => false,
         }
     }
@@ -445,7 +815,11 @@ impl DocumentContent {
     #[must_use]
     pub fn thumbnails_loaded(&self) -> usize {
         match self {
+            Self::Raster(doc) if doc.has_pages() => doc.thumbnails_loaded(),
+            #[cfg(feature = "portable")]
             Self::Portable(doc) => doc.thumbnails_loaded(),
+            #[cfg(feature = "djvu")]
+            Self::Djvu(doc) => doc.thumbnails_loaded(),
             _ => 0,
         }
     }
@@ -453,7 +827,11 @@ impl DocumentContent {
     /// Generate thumbnail for a single page.
     pub fn generate_thumbnail_page(&mut self, page: usize) -> Option<usize> {
         match self {
+            Self::Raster(doc) if doc.has_pages() => doc.generate_thumbnail_page(page),
+            #[cfg(feature = "portable")]
             Self::Portable(doc) => doc.generate_thumbnail_page(page),
+            #[cfg(feature = "djvu")]
+            Self::Djvu(doc) => doc.generate_thumbnail_page(page),
             _ => None,
         }
     }
@@ -461,11 +839,26 @@ impl DocumentContent {
     /// Generate all thumbnails (blocking).
     ///
     /// Convenience wrapper for `MultiPageThumbnails::generate_all_thumbnails()`.
-    /// Currently unused - thumbnails are generated incrementally via `generate_thumbnail_page()`.
-    #[allow(dead_code)]
+    /// Used by headless cache generation; the UI generates thumbnails
+    /// incrementally via `generate_thumbnail_page()`.
     pub fn generate_thumbnails(&mut self) {
-        if let Self::Portable(doc) = self {
-            doc.generate_all_thumbnails()
+        match self {
+            #[cfg(feature = "portable")]
+            Self::Portable(doc) => doc.generate_all_thumbnails(),
+            #[cfg(feature = "djvu")]
+            Self::Djvu(doc) => doc.generate_all_thumbnails(),
+            _ => {}
+        }
+    }
+
+    /// Drop loaded page thumbnails so they are generated again.
+    pub fn reset_thumbnails(&mut self) {
+        match self {
+            #[cfg(feature = "portable")]
+            Self::Portable(doc) => doc.reset_thumbnails(),
+            #[cfg(feature = "djvu")]
+            Self::Djvu(doc) => doc.reset_thumbnails(),
+            _ => {}
         }
     }
 
@@ -475,27 +868,62 @@ impl DocumentContent {
         match self {
             Self::Raster(doc) => doc.handle.clone(),
             Self::Vector(doc) => doc.handle.clone(),
+            #[cfg(feature = "portable")]
             Self::Portable(doc) => doc.handle.clone(),
+            #[cfg(feature = "djvu")]
+            Self::Djvu(doc) => doc.handle.clone(),
+        }
+    }
+
+    /// Current image behind `handle()` with all transforms applied.
+    ///
+    /// Canonical source of displayed pixels for export features.
+    #[must_use]
+    pub fn render_to_image(&self) -> &image::DynamicImage {
+        match self {
+            Self::Raster(doc) => doc.image(),
+            Self::Vector(doc) => &doc.rendered,
+            #[cfg(feature = "portable")]
+            Self::Portable(doc) => &doc.rendered,
+            #[cfg(feature = "djvu")]
+            Self::Djvu(doc) => &doc.rendered,
         }
     }
 
     /// Get current document dimensions.
+    ///
+    /// Always reflects the displayed (rotated) orientation, i.e. the size of
+    /// `handle()`, so fit calculations stay consistent across document kinds.
     #[must_use]
     pub fn dimensions(&self) -> (u32, u32) {
         match self {
             Self::Raster(doc) => doc.dimensions(),
             Self::Vector(doc) => doc.dimensions(),
+            #[cfg(feature = "portable")]
             Self::Portable(doc) => doc.dimensions(),
+            #[cfg(feature = "djvu")]
+            Self::Djvu(doc) => doc.dimensions(),
         }
     }
 
     /// Extract document metadata.
     pub fn extract_meta(&self, path: &Path) -> meta::DocumentMeta {
-        match self {
+        let mut meta = match self {
             Self::Raster(doc) => doc.extract_meta(path),
             Self::Vector(doc) => doc.extract_meta(path),
+            #[cfg(feature = "portable")]
             Self::Portable(doc) => doc.extract_meta(path),
+            #[cfg(feature = "djvu")]
+            Self::Djvu(doc) => doc.extract_meta(path),
+        };
+
+        // Report the display size when the texture had to be downscaled.
+        let (width, height) = self.dimensions();
+        let display = display_dimensions(width, height);
+        if display != (width, height) {
+            meta.basic.display_size = Some(display);
         }
+        meta
     }
 }
 
@@ -507,3 +935,71 @@ impl DocumentContent {
 pub fn set_as_wallpaper(path: &Path) {
     utils::set_as_wallpaper(path);
 }
+
+/// Load or generate a gallery thumbnail for a file.
+///
+/// Reuses the disk cache (page 0); raster files are decoded and downscaled
+/// on a cache miss. Like all cached thumbnails the preview is untransformed.
+/// Returns None if no preview can be produced.
+pub fn file_thumbnail(path: &Path) -> Option<ImageHandle> {
+    if let Some(handle) = cache::load_thumbnail(path, 0, 0) {
+        return Some(handle);
+    }
+
+    #[cfg(feature = "image")]
+    if DocumentKind::from_path(path) == Some(DocumentKind::Raster) {
+        use crate::constant::GALLERY_THUMBNAIL_SIZE;
+
+        // Archive entries are decoded from memory and not cached on disk.
+        if let Some((archive, entry)) = archive::split(path) {
+            let bytes = archive::read_entry(archive, &entry).ok()?;
+            let thumb = image::load_from_memory(&bytes)
+                .ok()?
+                .thumbnail(GALLERY_THUMBNAIL_SIZE, GALLERY_THUMBNAIL_SIZE);
+            return Some(create_image_handle_from_image(&thumb));
+        }
+
+        let thumb = image::open(path)
+            .ok()?
+            .thumbnail(GALLERY_THUMBNAIL_SIZE, GALLERY_THUMBNAIL_SIZE);
+        let _ = cache::save_thumbnail(path, 0, &thumb);
+        return Some(create_image_handle_from_image(&thumb));
+    }
+
+    None
+}
+
+/// Show a file in the system file manager.
+pub fn reveal_in_file_manager(path: &Path) -> DocResult<()> {
+    utils::reveal_in_file_manager(path)
+}
+
+/// Prepare printing a document.
+///
+/// PDFs on disk are printed from the original file; everything else is
+/// printed as shown (current page, with rotations and tone adjustments).
+/// The returned job saves the image and opens the print dialog; it blocks,
+/// so run it off the UI thread.
+pub fn print_job(
+    doc: &DocumentContent,
+    path: &Path,
+) -> impl FnOnce() -> DocResult<()> + Send + use<> {
+    let title = path.file_name().map_or_else(
+        || path.display().to_string(),
+        |name| name.to_string_lossy().into_owned(),
+    );
+
+    let original =
+        doc.kind() == DocumentKind::Portable && archive::split(path).is_none() && path.is_file();
+    let image = (!original).then(|| doc.render_to_image().clone());
+    let path = path.to_path_buf();
+
+    move || {
+        let file = match image {
+            Some(image) => cache::save_print_image(&image)
+                .ok_or_else(|| anyhow::anyhow!("Failed to prepare the image for printing"))?,
+            None => path,
+        };
+        utils::print_file(&file, &title)
+    }
+}
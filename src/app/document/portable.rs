@@ -3,16 +3,19 @@
 //
 // Portable documents (PDF) with poppler backend.
 
+use std::collections::HashMap;
 use std::io::Cursor;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-use cairo::{Context, Format, ImageSurface};
-use image::{imageops, DynamicImage, ImageReader};
+use cairo::{Context, Format, ImageSurface, Matrix};
+use image::{imageops, DynamicImage, GrayImage, ImageFormat, ImageReader, RgbImage};
+use lopdf::{Dictionary, Object, ObjectId};
 use poppler::PopplerDocument;
 
 use super::{
-    cache, DocResult, DocumentInfo, FlipDirection, ImageHandle, MultiPage, MultiPageThumbnails,
-    Renderable, RenderOutput, Rotation, TransformState, Transformable,
+    cache, DocResult, DocumentInfo, FlipDirection, ImageHandle, LinkRegion, LinkTarget, MultiPage,
+    MultiPageThumbnails, Renderable, RenderOutput, Rotation, TransformState, Transformable,
 };
 use crate::constant::{PDF_RENDER_QUALITY, PDF_THUMBNAIL_SIZE};
 
@@ -28,12 +31,76 @@ pub struct PortableDocument {
     page_index: usize,
     /// Current transformation state.
     transform: TransformState,
+    /// Window scale factor the current page was rendered for.
+    display_scale: f64,
+    /// The current page was rendered without the white paper background.
+    transparent: bool,
     /// Current rendered page as image.
     pub rendered: DynamicImage,
     /// Image handle for display.
     pub handle: ImageHandle,
     /// Cached thumbnail handles for each page (None = not yet generated).
     thumbnail_cache: Option<Vec<ImageHandle>>,
+    /// PDF structure read in the background; empty until it arrives.
+    structure: Arc<PdfStructure>,
+    /// The background read of the structure was started.
+    structure_requested: bool,
+}
+
+/// Data Poppler's bindings don't expose, read from the PDF with `lopdf`.
+#[derive(Default)]
+pub struct PdfStructure {
+    /// Link annotations per page.
+    links: Vec<Vec<PdfLink>>,
+    /// Page labels (e.g. "iv", "A-1"); empty if the PDF defines none.
+    page_labels: Vec<String>,
+    /// Thumbnails stored in the PDF (`/Thumb`) per page, still encoded.
+    embedded_thumbnails: Vec<Option<EmbeddedThumbnail>>,
+    /// Rotation the PDF defines per page (`/Rotate`), applied before the user's.
+    page_rotations: Vec<Rotation>,
+}
+
+impl PdfStructure {
+    /// Parse the PDF a second time with `lopdf` (blocking, run off the UI
+    /// thread). Failures only disable the data.
+    pub fn read(path: &Path, num_pages: usize) -> Self {
+        match lopdf::Document::load(path) {
+            Ok(document) => Self {
+                links: read_links(&document),
+                page_labels: read_page_labels(&document, num_pages),
+                embedded_thumbnails: read_embedded_thumbnails(&document),
+                page_rotations: read_page_rotations(&document),
+            },
+            Err(e) => {
+                log::warn!("Failed to read PDF structure: {e}");
+                Self::default()
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for PdfStructure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PdfStructure({} pages of links)", self.links.len())
+    }
+}
+
+/// Page thumbnail image embedded in the PDF, decoded on demand.
+struct EmbeddedThumbnail {
+    width: u32,
+    height: u32,
+    /// DeviceGray instead of DeviceRGB samples.
+    gray: bool,
+    /// JPEG data (DCTDecode) instead of raw 8-bit samples.
+    jpeg: bool,
+    data: Vec<u8>,
+}
+
+/// A link annotation in page space (points, origin at the bottom-left corner).
+struct PdfLink {
+    /// Rectangle as (x0, y0, x1, y1).
+    rect: (f64, f64, f64, f64),
+    target: LinkTarget,
 }
 
 impl PortableDocument {
@@ -47,21 +114,47 @@ impl PortableDocument {
             return Err(anyhow::anyhow!("PDF has no pages"));
         }
 
-        let rendered = Self::render_page(&document, 0, Rotation::None)?;
+        let display_scale = super::display_scale();
+        let transparent = super::pdf_transparent_background();
+        let rendered = Self::render_page(&document, 0, Rotation::None, display_scale, transparent)?;
         let handle = super::create_image_handle_from_image(&rendered);
 
+        // Poppler's Rust bindings expose neither link mappings, page labels
+        // nor embedded thumbnails, so they are read from the PDF structure
+        // directly. That is a second full parse, so the app runs it in the
+        // background (see `structure_request`) and the page is shown without
+        // them in the meantime.
+
         Ok(Self {
             document,
             source_path: path.to_path_buf(),
             num_pages,
             page_index: 0,
             transform: TransformState::default(),
+            display_scale,
+            transparent,
             rendered,
             handle,
             thumbnail_cache: None,
+            structure: Arc::default(),
+            structure_requested: false,
         })
     }
 
+    /// Path and page count to read the structure from, the first time this is
+    /// asked; None once the read was started.
+    pub fn structure_request(&mut self) -> Option<(PathBuf, usize)> {
+        if std::mem::replace(&mut self.structure_requested, true) {
+            return None;
+        }
+        Some((self.source_path.clone(), self.num_pages))
+    }
+
+    /// Use the structure read in the background.
+    pub fn set_structure(&mut self, structure: Arc<PdfStructure>) {
+        self.structure = structure;
+    }
+
     /// Get the number of thumbnails currently loaded.
     pub fn thumbnails_loaded(&self) -> usize {
         self.thumbnail_cache.as_ref().map_or(0, Vec::len)
@@ -101,13 +194,47 @@ impl PortableDocument {
         }
     }
 
+    /// Thumbnail stored in the PDF for a page, if any.
+    pub fn embedded_thumbnail(&self, page: usize) -> Option<DynamicImage> {
+        let thumbnail = self.structure.embedded_thumbnails.get(page)?.as_ref()?;
+        if thumbnail.jpeg {
+            return image::load_from_memory_with_format(&thumbnail.data, ImageFormat::Jpeg).ok();
+        }
+
+        let (width, height) = (thumbnail.width, thumbnail.height);
+        if thumbnail.gray {
+            GrayImage::from_raw(width, height, thumbnail.data.clone()).map(DynamicImage::ImageLuma8)
+        } else {
+            RgbImage::from_raw(width, height, thumbnail.data.clone()).map(DynamicImage::ImageRgb8)
+        }
+    }
+
     /// Load thumbnail from cache or generate and cache it.
+    ///
+    /// A thumbnail embedded in the PDF is used as is, which is much faster
+    /// than rendering the page, unless it is narrower than the configured
+    /// thumbnail width. Pages are rendered at `PDF_THUMBNAIL_SIZE`, or larger
+    /// when that falls short of the thumbnail width.
+    /// Rendered without the current transform, as the cache key doesn't include it.
     fn load_or_generate_thumbnail(&self, page: usize) -> ImageHandle {
-        if let Some(handle) = cache::load_thumbnail(&self.source_path, page) {
+        let width = super::thumbnail_width();
+        if let Some(handle) = cache::load_thumbnail(&self.source_path, page, width) {
             return handle;
         }
+        if let Some(img) = self
+            .embedded_thumbnail(page)
+            .filter(|img| img.width() >= width)
+        {
+            return super::create_image_handle_from_image(&img);
+        }
 
-        match Self::render_page_at_scale(&self.document, page, Rotation::None, PDF_THUMBNAIL_SIZE) {
+        let scale = self
+            .document
+            .get_page(page)
+            .map_or(PDF_THUMBNAIL_SIZE, |p| {
+                PDF_THUMBNAIL_SIZE.max(f64::from(width) / p.get_size().0)
+            });
+        match Self::render_page_at_scale(&self.document, page, Rotation::None, scale, false) {
             Ok(img) => {
                 let _ = cache::save_thumbnail(&self.source_path, page, &img);
                 super::create_image_handle_from_image(&img)
@@ -120,38 +247,45 @@ impl PortableDocument {
     }
 
     /// Render a specific page from the document to an image.
+    ///
+    /// The render quality is multiplied by the window scale factor (HiDPI).
     fn render_page(
         document: &PopplerDocument,
         page_index: usize,
         rotation: Rotation,
+        display_scale: f64,
+        transparent: bool,
     ) -> anyhow::Result<DynamicImage> {
-        Self::render_page_at_scale(document, page_index, rotation, PDF_RENDER_QUALITY)
+        let scale = PDF_RENDER_QUALITY * display_scale;
+        Self::render_page_at_scale(document, page_index, rotation, scale, transparent)
     }
 
     /// Render a specific page at a given scale.
+    ///
+    /// Pages are painted onto white paper unless `transparent` is set, which
+    /// keeps areas the PDF leaves unpainted transparent.
     fn render_page_at_scale(
         document: &PopplerDocument,
         page_index: usize,
         rotation: Rotation,
         scale: f64,
+        transparent: bool,
     ) -> anyhow::Result<DynamicImage> {
         let page = document
             .get_page(page_index)
             .ok_or_else(|| anyhow::anyhow!("Failed to get page {page_index}"))?;
 
         let (page_width, page_height) = page.get_size();
-        let rotation_degrees = rotation.to_degrees();
 
-        let (width, height) = if rotation_degrees == 90 || rotation_degrees == 270 {
-            (page_height, page_width)
-        } else {
-            (page_width, page_height)
+        let (width, height) = match rotation {
+            Rotation::Cw90 | Rotation::Cw270 => (page_height, page_width),
+            Rotation::None | Rotation::Cw180 => (page_width, page_height),
         };
 
         #[allow(clippy::cast_possible_truncation)]
-        let scaled_width = (width * scale) as i32;
+        let scaled_width = ((width * scale).round() as i32).max(1);
         #[allow(clippy::cast_possible_truncation)]
-        let scaled_height = (height * scale) as i32;
+        let scaled_height = ((height * scale).round() as i32).max(1);
 
         let surface = ImageSurface::create(Format::ARgb32, scaled_width, scaled_height)
             .map_err(|e| anyhow::anyhow!("Failed to create Cairo surface: {e}"))?;
@@ -160,17 +294,33 @@ impl PortableDocument {
             .map_err(|e| anyhow::anyhow!("Failed to create Cairo context: {e}"))?;
 
         // Fill with white background.
-        context.set_source_rgb(1.0, 1.0, 1.0);
-        let _ = context.paint();
-
-        context.scale(scale, scale);
+        if !transparent {
+            context.set_source_rgb(1.0, 1.0, 1.0);
+            let _ = context.paint();
+        }
 
-        if rotation != Rotation::None {
-            let center_x = width / 2.0;
-            let center_y = height / 2.0;
-            context.translate(center_x, center_y);
-            context.rotate(f64::from(rotation_degrees) * std::f64::consts::PI / 180.0);
-            context.translate(-page_width / 2.0, -page_height / 2.0);
+        // Scale each axis to the rounded surface size so the page fills it exactly.
+        context.scale(
+            f64::from(scaled_width) / width,
+            f64::from(scaled_height) / height,
+        );
+
+        // Poppler applies the page's own /Rotate when rendering, and
+        // `get_size` reports the page as displayed, so the user rotation
+        // below composes on top of it.
+        // Map page coordinates onto the rotated canvas with an exact matrix
+        // (trigonometric rotation leaves sub-pixel gaps at the edges).
+        let matrix = match rotation {
+            Rotation::None => None,
+            // (x, y) -> (page_height - y, x)
+            Rotation::Cw90 => Some(Matrix::new(0.0, 1.0, -1.0, 0.0, page_height, 0.0)),
+            // (x, y) -> (page_width - x, page_height - y)
+            Rotation::Cw180 => Some(Matrix::new(-1.0, 0.0, 0.0, -1.0, page_width, page_height)),
+            // (x, y) -> (y, page_width - x)
+            Rotation::Cw270 => Some(Matrix::new(0.0, -1.0, 1.0, 0.0, 0.0, page_width)),
+        };
+        if let Some(matrix) = matrix {
+            context.transform(matrix);
         }
 
         page.render(&context);
@@ -192,9 +342,23 @@ impl PortableDocument {
         Ok(image)
     }
 
-    /// Re-render the current page with current transform.
+    /// Switch the page background and re-render the current page right away.
+    pub fn set_transparent(&mut self, transparent: bool) {
+        if self.transparent != transparent {
+            self.transparent = transparent;
+            self.rerender();
+        }
+    }
+
+    /// Re-render the current page with current transform (and display scale).
     fn rerender(&mut self) {
-        match Self::render_page(&self.document, self.page_index, self.transform.rotation) {
+        match Self::render_page(
+            &self.document,
+            self.page_index,
+            self.transform.rotation,
+            self.display_scale,
+            self.transparent,
+        ) {
             Ok(mut rendered) => {
                 // Apply flip transformations to the rendered result
                 if self.transform.flip_h {
@@ -217,11 +381,83 @@ impl PortableDocument {
         self.handle = super::create_image_handle_from_image(&self.rendered);
     }
 
+    /// Display label of a page, falling back to its 1-based number.
+    pub fn page_label(&self, page: usize) -> String {
+        self.structure
+            .page_labels
+            .get(page)
+            .cloned()
+            .unwrap_or_else(|| (page + 1).to_string())
+    }
+
     /// Returns the dimensions of the currently rendered page.
     pub fn dimensions(&self) -> (u32, u32) {
         (self.rendered.width(), self.rendered.height())
     }
 
+    /// Link regions of the current page, mapped through the page's own
+    /// rotation, the render scale, the user rotation and flips into displayed
+    /// pixel coordinates.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn link_regions(&self) -> Vec<LinkRegion> {
+        let Some(links) = self.structure.links.get(self.page_index) else {
+            return Vec::new();
+        };
+        let Some(page) = self.document.get_page(self.page_index) else {
+            return Vec::new();
+        };
+
+        // Size of the page as displayed by Poppler (with /Rotate applied)
+        // and as stored, which is the space the link rectangles are in.
+        let (page_width, page_height) = page.get_size();
+        let page_rotation = self
+            .structure
+            .page_rotations
+            .get(self.page_index)
+            .copied()
+            .unwrap_or_default();
+        let (stored_width, stored_height) = match page_rotation {
+            Rotation::Cw90 | Rotation::Cw270 => (page_height, page_width),
+            Rotation::None | Rotation::Cw180 => (page_width, page_height),
+        };
+
+        let (width, height) = self.dimensions();
+        let (width, height) = (f64::from(width), f64::from(height));
+        let rotation = self.transform.rotation;
+        let (scale_x, scale_y) = match rotation {
+            Rotation::Cw90 | Rotation::Cw270 => (width / page_height, height / page_width),
+            Rotation::None | Rotation::Cw180 => (width / page_width, height / page_height),
+        };
+
+        // Same mapping as the render matrix, applied to a single point.
+        let map = |x: f64, y: f64| {
+            let y = stored_height - y;
+            let (x, y) = rotate_point(page_rotation, x, y, stored_width, stored_height);
+            let (x, y) = rotate_point(rotation, x, y, page_width, page_height);
+            let (x, y) = (x * scale_x, y * scale_y);
+            (
+                if self.transform.flip_h { width - x } else { x },
+                if self.transform.flip_v { height - y } else { y },
+            )
+        };
+
+        links
+            .iter()
+            .map(|link| {
+                let (x0, y0, x1, y1) = link.rect;
+                let (ax, ay) = map(x0, y0);
+                let (bx, by) = map(x1, y1);
+                LinkRegion {
+                    x: ax.min(bx) as f32,
+                    y: ay.min(by) as f32,
+                    width: (ax - bx).abs() as f32,
+                    height: (ay - by).abs() as f32,
+                    target: link.target.clone(),
+                }
+            })
+            .collect()
+    }
+
     /// Navigate to the next page.
     #[allow(dead_code)]
     pub fn next_page(&mut self) -> bool {
@@ -254,13 +490,396 @@ impl PortableDocument {
     }
 }
 
+// ============================================================================
+// Link Annotations
+// ============================================================================
+
+/// Read the link annotations of every page.
+fn read_links(document: &lopdf::Document) -> Vec<Vec<PdfLink>> {
+    let pages = document.get_pages();
+    let page_numbers: HashMap<ObjectId, usize> = pages
+        .iter()
+        .map(|(number, id)| (*id, *number as usize - 1))
+        .collect();
+
+    pages
+        .values()
+        .map(|&id| page_links(document, id, &page_numbers).unwrap_or_default())
+        .collect()
+}
+
+/// Read the link annotations of a single page.
+fn page_links(
+    document: &lopdf::Document,
+    page_id: ObjectId,
+    page_numbers: &HashMap<ObjectId, usize>,
+) -> Option<Vec<PdfLink>> {
+    let page = document.get_dictionary(page_id).ok()?;
+    let (origin_x, origin_y) = page_origin(document, page);
+    let (_, annots) = document.dereference(page.get(b"Annots").ok()?).ok()?;
+
+    let links = annots
+        .as_array()
+        .ok()?
+        .iter()
+        .filter_map(|annot| {
+            let annot = document.dereference(annot).ok()?.1.as_dict().ok()?;
+            if annot.get(b"Subtype").and_then(Object::as_name).ok()? != b"Link" {
+                return None;
+            }
+            let rect = numbers(document, annot.get(b"Rect").ok()?)?;
+            let [x0, y0, x1, y1] = rect[..] else {
+                return None;
+            };
+            Some(PdfLink {
+                rect: (x0 - origin_x, y0 - origin_y, x1 - origin_x, y1 - origin_y),
+                target: link_target(document, annot, page_numbers)?,
+            })
+        })
+        .collect();
+
+    Some(links)
+}
+
+/// Rotate a point (origin at the top-left) clockwise within a `width` x `height` area.
+fn rotate_point(rotation: Rotation, x: f64, y: f64, width: f64, height: f64) -> (f64, f64) {
+    match rotation {
+        Rotation::None => (x, y),
+        Rotation::Cw90 => (height - y, x),
+        Rotation::Cw180 => (width - x, height - y),
+        Rotation::Cw270 => (y, width - x),
+    }
+}
+
+/// Deepest page tree that is searched for an inherited `/Rotate` (guards against cycles).
+const MAX_PAGE_TREE_DEPTH: usize = 32;
+
+/// Read the `/Rotate` attribute of every page.
+fn read_page_rotations(document: &lopdf::Document) -> Vec<Rotation> {
+    document
+        .get_pages()
+        .values()
+        .map(|&id| page_rotation(document, id))
+        .collect()
+}
+
+/// Rotation of a single page, inherited from the page tree if the page has none.
+fn page_rotation(document: &lopdf::Document, page_id: ObjectId) -> Rotation {
+    let mut node = document.get_dictionary(page_id).ok();
+    for _ in 0..MAX_PAGE_TREE_DEPTH {
+        let Some(dict) = node else {
+            break;
+        };
+        if let Ok(degrees) = dict.get(b"Rotate").and_then(Object::as_i64) {
+            return match degrees.rem_euclid(360) {
+                90 => Rotation::Cw90,
+                180 => Rotation::Cw180,
+                270 => Rotation::Cw270,
+                _ => Rotation::None,
+            };
+        }
+        node = dict
+            .get(b"Parent")
+            .and_then(Object::as_reference)
+            .and_then(|id| document.get_dictionary(id))
+            .ok();
+    }
+    Rotation::None
+}
+
+/// Lower-left corner of the visible page area (crop box, else media box).
+fn page_origin(document: &lopdf::Document, page: &Dictionary) -> (f64, f64) {
+    page.get(b"CropBox")
+        .or_else(|_| page.get(b"MediaBox"))
+        .ok()
+        .and_then(|rect| numbers(document, rect))
+        .and_then(|rect| Some((*rect.first()?, *rect.get(1)?)))
+        .unwrap_or((0.0, 0.0))
+}
+
+/// Resolve an array of numbers (e.g. a rectangle).
+fn numbers(document: &lopdf::Document, object: &Object) -> Option<Vec<f64>> {
+    document
+        .dereference(object)
+        .ok()?
+        .1
+        .as_array()
+        .ok()?
+        .iter()
+        .map(|value| {
+            let (_, value) = document.dereference(value).ok()?;
+            value.as_float().ok().map(f64::from)
+        })
+        .collect()
+}
+
+/// Resolve the target of a link annotation (URI or GoTo action, or direct destination).
+fn link_target(
+    document: &lopdf::Document,
+    annot: &Dictionary,
+    page_numbers: &HashMap<ObjectId, usize>,
+) -> Option<LinkTarget> {
+    if let Ok(action) = annot.get(b"A") {
+        let action = document.dereference(action).ok()?.1.as_dict().ok()?;
+        return match action.get(b"S").and_then(Object::as_name).ok()? {
+            b"URI" => {
+                let uri = action.get(b"URI").and_then(Object::as_str).ok()?;
+                Some(LinkTarget::Uri(String::from_utf8_lossy(uri).into_owned()))
+            }
+            b"GoTo" => destination_page(document, action.get(b"D").ok()?, page_numbers),
+            _ => None,
+        };
+    }
+    destination_page(document, annot.get(b"Dest").ok()?, page_numbers)
+}
+
+/// Resolve an explicit destination (`[page /XYZ ...]`) to a page link.
+///
+/// Named destinations are not resolved.
+fn destination_page(
+    document: &lopdf::Document,
+    dest: &Object,
+    page_numbers: &HashMap<ObjectId, usize>,
+) -> Option<LinkTarget> {
+    let (_, dest) = document.dereference(dest).ok()?;
+    let page_id = dest.as_array().ok()?.first()?.as_reference().ok()?;
+    page_numbers.get(&page_id).copied().map(LinkTarget::Page)
+}
+
+// ============================================================================
+// Page Labels
+// ============================================================================
+
+/// Deepest page label tree that is followed (guards against cycles).
+const MAX_LABEL_TREE_DEPTH: usize = 16;
+
+/// Read the labels of all pages from the `/PageLabels` number tree.
+///
+/// Returns an empty list if the document defines no labels.
+fn read_page_labels(document: &lopdf::Document, num_pages: usize) -> Vec<String> {
+    let Some(tree) = document
+        .catalog()
+        .ok()
+        .and_then(|catalog| catalog.get(b"PageLabels").ok())
+    else {
+        return Vec::new();
+    };
+
+    // Label ranges as (first page, label dictionary), sorted by first page.
+    let mut ranges = Vec::new();
+    collect_label_ranges(document, tree, 0, &mut ranges);
+    ranges.sort_by_key(|(start, _)| *start);
+    if ranges.is_empty() {
+        return Vec::new();
+    }
+
+    (0..num_pages)
+        .map(|page| {
+            // Pages before the first range have no label; use the number.
+            let Some((start, style)) = ranges.iter().rev().find(|(start, _)| *start <= page) else {
+                return (page + 1).to_string();
+            };
+            page_label(document, style, page - start)
+        })
+        .collect()
+}
+
+/// Collect the `/Nums` entries of a number tree node and its kids.
+fn collect_label_ranges<'a>(
+    document: &'a lopdf::Document,
+    node: &'a Object,
+    depth: usize,
+    ranges: &mut Vec<(usize, &'a Dictionary)>,
+) {
+    if depth > MAX_LABEL_TREE_DEPTH {
+        return;
+    }
+    let Some(node) = document
+        .dereference(node)
+        .ok()
+        .and_then(|(_, node)| node.as_dict().ok())
+    else {
+        return;
+    };
+
+    if let Ok(nums) = node.get(b"Nums").and_then(Object::as_array) {
+        for pair in nums.chunks_exact(2) {
+            let start = pair[0].as_i64().ok().and_then(|n| usize::try_from(n).ok());
+            let style = document
+                .dereference(&pair[1])
+                .ok()
+                .and_then(|(_, style)| style.as_dict().ok());
+            if let (Some(start), Some(style)) = (start, style) {
+                ranges.push((start, style));
+            }
+        }
+    }
+
+    if let Ok(kids) = node.get(b"Kids").and_then(Object::as_array) {
+        for kid in kids {
+            collect_label_ranges(document, kid, depth + 1, ranges);
+        }
+    }
+}
+
+/// Label of the page at `offset` within a range: prefix plus numbering.
+fn page_label(document: &lopdf::Document, style: &Dictionary, offset: usize) -> String {
+    let prefix = style
+        .get(b"P")
+        .ok()
+        .and_then(|prefix| document.dereference(prefix).ok())
+        .and_then(|(_, prefix)| prefix.as_str().ok())
+        .map(text_string)
+        .unwrap_or_default();
+    let start = style
+        .get(b"St")
+        .and_then(Object::as_i64)
+        .ok()
+        .and_then(|start| usize::try_from(start).ok())
+        .unwrap_or(1);
+    let number = start + offset;
+
+    let numbering = match style.get(b"S").and_then(Object::as_name) {
+        Ok(b"D") => number.to_string(),
+        Ok(b"R") => roman(number),
+        Ok(b"r") => roman(number).to_lowercase(),
+        Ok(b"A") => letters(number),
+        Ok(b"a") => letters(number).to_lowercase(),
+        // No style: the label is the prefix alone.
+        _ => String::new(),
+    };
+    prefix + &numbering
+}
+
+/// Upper-case roman numeral (e.g. 14 -> "XIV").
+fn roman(mut number: usize) -> String {
+    const NUMERALS: [(usize, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+
+    let mut out = String::new();
+    for (value, numeral) in NUMERALS {
+        while number >= value {
+            out.push_str(numeral);
+            number -= value;
+        }
+    }
+    out
+}
+
+/// Upper-case letter numbering: A-Z, then AA-ZZ, AAA-ZZZ and so on.
+fn letters(number: usize) -> String {
+    let Some(index) = number.checked_sub(1) else {
+        return String::new();
+    };
+    #[allow(clippy::cast_possible_truncation)]
+    let letter = char::from(b'A' + (index % 26) as u8);
+    letter.to_string().repeat(index / 26 + 1)
+}
+
+/// Decode a PDF text string (UTF-16BE with byte order mark, else PDFDocEncoding).
+fn text_string(bytes: &[u8]) -> String {
+    match bytes {
+        [0xFE, 0xFF, rest @ ..] => {
+            let units: Vec<u16> = rest
+                .chunks_exact(2)
+                .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+        // PDFDocEncoding matches Latin-1 for printable characters.
+        _ => bytes.iter().map(|&byte| char::from(byte)).collect(),
+    }
+}
+
+// ============================================================================
+// Embedded Thumbnails
+// ============================================================================
+
+/// Read the `/Thumb` images of every page.
+///
+/// Only 8-bit DeviceRGB/DeviceGray samples (optionally Flate-compressed)
+/// and JPEGs are supported; other pages get a rendered thumbnail.
+fn read_embedded_thumbnails(document: &lopdf::Document) -> Vec<Option<EmbeddedThumbnail>> {
+    document
+        .get_pages()
+        .values()
+        .map(|&id| embedded_thumbnail(document, id))
+        .collect()
+}
+
+/// Read the `/Thumb` image of a single page.
+fn embedded_thumbnail(document: &lopdf::Document, page_id: ObjectId) -> Option<EmbeddedThumbnail> {
+    let page = document.get_dictionary(page_id).ok()?;
+    let (_, thumb) = document.dereference(page.get(b"Thumb").ok()?).ok()?;
+    let stream = thumb.as_stream().ok()?;
+    let dict = &stream.dict;
+
+    let dimension = |key: &[u8]| {
+        dict.get(key)
+            .and_then(Object::as_i64)
+            .ok()
+            .and_then(|n| u32::try_from(n).ok())
+            .filter(|n| *n > 0)
+    };
+    let (width, height) = (dimension(b"Width")?, dimension(b"Height")?);
+
+    let color_space = document.dereference(dict.get(b"ColorSpace").ok()?).ok()?.1;
+    let gray = match color_space.as_name().ok()? {
+        b"DeviceGray" => true,
+        b"DeviceRGB" => false,
+        _ => return None,
+    };
+
+    let filters = stream.filters().unwrap_or_default();
+    let jpeg = filters.as_slice() == [b"DCTDecode".as_slice()];
+    let data = if jpeg {
+        stream.content.clone()
+    } else {
+        if dict.get(b"BitsPerComponent").and_then(Object::as_i64).ok() != Some(8) {
+            return None;
+        }
+        if filters.is_empty() {
+            stream.content.clone()
+        } else {
+            stream.decompressed_content().ok()?
+        }
+    };
+
+    Some(EmbeddedThumbnail {
+        width,
+        height,
+        gray,
+        jpeg,
+        data,
+    })
+}
+
 // ============================================================================
 // Trait Implementations
 // ============================================================================
 
 impl Renderable for PortableDocument {
-    fn render(&mut self, _scale: f64) -> DocResult<RenderOutput> {
-        // PDF rendering quality is fixed for now (PDF_RENDER_QUALITY)
+    fn render(&mut self, scale: f64) -> DocResult<RenderOutput> {
+        // Pages are rendered at PDF_RENDER_QUALITY times the display scale.
+        let transparent = super::pdf_transparent_background();
+        if (self.display_scale - scale).abs() > f64::EPSILON || self.transparent != transparent {
+            self.display_scale = scale;
+            self.transparent = transparent;
+            self.rerender();
+        }
         let (width, height) = self.dimensions();
         Ok(RenderOutput {
             handle: self.handle.clone(),
@@ -351,4 +970,53 @@ impl MultiPageThumbnails for PortableDocument {
             .as_ref()
             .and_then(|cache| cache.get(page).cloned())
     }
+
+    fn reset_thumbnails(&mut self) {
+        self.thumbnail_cache = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 200 × 100 pt page painted black edge to edge.
+    fn landscape() -> PopplerDocument {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/landscape.pdf");
+        PopplerDocument::new_from_file(&path, None).expect("fixture PDF opens")
+    }
+
+    /// Render a quarter-turned page and check it fills the swapped surface.
+    fn assert_quarter_turn(rotation: Rotation) {
+        let image = PortableDocument::render_page_at_scale(&landscape(), 0, rotation, 1.0, true)
+            .expect("page renders");
+        assert_eq!((image.width(), image.height()), (100, 200));
+
+        // Unpainted areas stay transparent, so an offset page leaves a blank edge.
+        let image = image.to_rgba8();
+        let (right, bottom) = (image.width() - 1, image.height() - 1);
+        let edges = [
+            (0, 0),
+            (right, 0),
+            (0, bottom),
+            (right, bottom),
+            (right / 2, 0),
+            (right / 2, bottom),
+            (0, bottom / 2),
+            (right, bottom / 2),
+        ];
+        for (x, y) in edges {
+            assert_eq!(image.get_pixel(x, y)[3], 255, "blank at ({x}, {y})");
+        }
+    }
+
+    #[test]
+    fn render_rotated_90() {
+        assert_quarter_turn(Rotation::Cw90);
+    }
+
+    #[test]
+    fn render_rotated_270() {
+        assert_quarter_turn(Rotation::Cw270);
+    }
 }
@@ -3,14 +3,25 @@
 //
 // Raster image document support (PNG, JPEG, WebP, etc.).
 
+use std::io::Read;
 use std::path::Path;
 
-use image::{imageops, DynamicImage, GenericImageView, ImageReader};
+use image::{DynamicImage, GenericImageView, ImageReader, RgbaImage};
 
 use super::{
-    DocResult, DocumentInfo, FlipDirection, ImageHandle, Renderable, RenderOutput, Rotation,
-    TransformState, Transformable,
+    cmyk, icon, tiff, DocResult, DocumentInfo, FlipDirection, ImageHandle, MultiPage,
+    MultiPageThumbnails, Renderable, RenderOutput, Rotation, TransformState, Transformable,
 };
+use crate::constant::{AUTO_LEVELS_CLIP, PREVIEW_MAX_PIXELS, PROGRESSIVE_MIN_PIXELS};
+
+/// Tone adjustment shown instead of the original pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tone {
+    /// Stretch each channel to its clipped value range.
+    AutoLevels,
+    /// Map picked black and white values of each channel to 0 and 255.
+    Points { black: [u8; 3], white: [u8; 3] },
+}
 
 /// Represents a raster image document (PNG, JPEG, WebP, ...).
 pub struct RasterDocument {
@@ -22,29 +33,248 @@ pub struct RasterDocument {
     native_height: u32,
     /// Current transformation state.
     transform: TransformState,
+    /// Transform that corrects the EXIF orientation, applied on load.
+    exif_transform: TransformState,
+    /// Color type reported by the source format when it differs from the decoded image.
+    source_color_type: Option<String>,
+    /// All images of a multi-resolution container (ICO/CUR), empty otherwise.
+    pages: Vec<DynamicImage>,
+    /// Thumbnail handles matching `pages`.
+    page_handles: Vec<ImageHandle>,
+    /// Index of the currently shown entry in `pages`.
+    page_index: usize,
+    /// Tone adjustment applied for display, if any.
+    tone: Option<Tone>,
+    /// Tone-adjusted copy of `document` while `tone` is set.
+    enhanced: Option<DynamicImage>,
     /// Cached handle for rendering.
     pub handle: ImageHandle,
 }
 
 impl RasterDocument {
     /// Load a raster document from disk.
-    pub fn open(path: &Path) -> image::ImageResult<Self> {
-        let document = ImageReader::open(path)?.decode()?;
+    pub fn open(path: &Path) -> DocResult<Self> {
+        if is_psd(path) {
+            return Self::open_psd(path);
+        }
+        if is_icon(path) {
+            return Self::open_icon(path);
+        }
+
+        let document = Self::decode_full(path)?;
+        let mut raster = Self::from_image(document);
+        raster.apply_exif_orientation(path);
+        raster.detect_source_color_type(path);
+        Ok(raster)
+    }
+
+    /// Report CMYK JPEGs (decoded to RGB), the TIFF sample layout and
+    /// palette or low bit depth PNGs (decoded to 8 bits) in the color type metadata.
+    pub fn detect_source_color_type(&mut self, path: &Path) {
+        if is_jpeg(path) && cmyk::is_cmyk_jpeg(path) {
+            self.source_color_type = Some(cmyk::CMYK_COLOR_TYPE.to_string());
+        } else if is_tiff(path) {
+            self.source_color_type = tiff::TiffInfo::read(path).map(|info| info.describe());
+        } else if is_png(path) {
+            self.source_color_type = png_source_layout(path);
+        }
+    }
+
+    /// Show the image upright according to the EXIF orientation of `path`.
+    ///
+    /// The pixels stay in sensor orientation; the correction is a regular
+    /// transform that can be toggled off when the tag is wrong.
+    pub fn apply_exif_orientation(&mut self, path: &Path) {
+        let Some(orientation) = super::meta::read_exif_orientation(path) else {
+            return;
+        };
+        self.exif_transform = TransformState::from_exif_orientation(orientation);
+        self.set_transform(self.exif_transform);
+    }
+
+    /// Transform that corrects the EXIF orientation (identity if there is none).
+    pub fn exif_transform(&self) -> TransformState {
+        self.exif_transform
+    }
+
+    /// Check whether a file should be loaded progressively.
+    ///
+    /// True for very large images and for JPEGs with an embedded EXIF
+    /// thumbnail, which is shown instantly. Only reads the header and EXIF.
+    /// Containers with special handling (PSD, ICO/CUR) are always loaded directly.
+    pub fn supports_progressive(path: &Path) -> bool {
+        if is_psd(path) || is_icon(path) {
+            return false;
+        }
+        image::image_dimensions(path).is_ok_and(|(width, height)| {
+            u64::from(width) * u64::from(height) >= PROGRESSIVE_MIN_PIXELS
+        }) || (is_jpeg(path) && super::meta::read_exif_thumbnail(path).is_some())
+    }
+
+    /// Decode a fast low-resolution preview from the embedded EXIF thumbnail,
+    /// or from a reduced-resolution copy stored in a TIFF.
+    ///
+    /// Returns None when there is neither; the full image then appears once
+    /// it is decoded.
+    pub fn decode_preview(path: &Path) -> Option<DynamicImage> {
+        let thumbnail = super::meta::read_exif_thumbnail(path)
+            .and_then(|bytes| image::load_from_memory(&bytes).ok());
+        let Some(preview) = thumbnail else {
+            return is_tiff(path)
+                .then(|| tiff::decode_reduced(path, PREVIEW_MAX_PIXELS))
+                .flatten();
+        };
+        Some(match image::image_dimensions(path) {
+            Ok((width, height)) => trim_to_aspect(preview, width, height),
+            Err(_) => preview,
+        })
+    }
+
+    /// Image dimensions read from the file header, without decoding pixels.
+    pub fn header_dimensions(path: &Path) -> Option<(u32, u32)> {
+        ImageReader::open(path)
+            .ok()?
+            .with_guessed_format()
+            .ok()?
+            .into_dimensions()
+            .ok()
+    }
+
+    /// Image dimensions read from an in-memory header, without decoding pixels.
+    pub fn header_dimensions_from_bytes(bytes: &[u8]) -> Option<(u32, u32)> {
+        ImageReader::new(std::io::Cursor::new(bytes))
+            .with_guessed_format()
+            .ok()?
+            .into_dimensions()
+            .ok()
+    }
+
+    /// Decode the full-resolution image (blocking).
+    pub fn decode_full(path: &Path) -> DocResult<DynamicImage> {
+        if is_jpeg(path) && cmyk::is_cmyk_jpeg(path) {
+            return cmyk::decode(path);
+        }
+        if is_tiff(path)
+            && let Some(info) = tiff::TiffInfo::read(path)
+            && info.needs_own_decoder()
+        {
+            return tiff::decode(path, &info);
+        }
+        Ok(ImageReader::open(path)?.decode()?)
+    }
+
+    /// Load the flattened composite of a Photoshop document.
+    fn open_psd(path: &Path) -> DocResult<Self> {
+        let bytes = std::fs::read(path)?;
+        let psd = psd::Psd::from_bytes(&bytes)
+            .map_err(|e| anyhow::anyhow!("Failed to parse PSD: {e}"))?;
+
+        let composite = RgbaImage::from_raw(psd.width(), psd.height(), psd.rgba())
+            .ok_or_else(|| anyhow::anyhow!("PSD composite has unexpected size"))?;
+
+        let mut doc = Self::from_image(DynamicImage::ImageRgba8(composite));
+        doc.source_color_type = Some(format!("{:?} (PSD)", psd.color_mode()));
+        Ok(doc)
+    }
+
+    /// Load all resolutions of an ICO/CUR file, showing the largest one.
+    fn open_icon(path: &Path) -> DocResult<Self> {
+        let bytes = std::fs::read(path)?;
+        let pages = icon::decode_entries(&bytes)?;
+        let page_handles = pages
+            .iter()
+            .map(super::create_image_handle_from_image)
+            .collect();
+
+        let mut doc = Self::from_image(pages[0].clone());
+        doc.pages = pages;
+        doc.page_handles = page_handles;
+        Ok(doc)
+    }
+
+    /// Decode a raster document from an in-memory buffer (stdin, network).
+    pub fn from_bytes(bytes: &[u8]) -> image::ImageResult<Self> {
+        let document = image::load_from_memory(bytes)?;
+        Ok(Self::from_image(document))
+    }
+
+    /// Wrap an already decoded image.
+    pub fn from_image(document: DynamicImage) -> Self {
         let (native_width, native_height) = document.dimensions();
         let handle = super::create_image_handle_from_image(&document);
 
-        Ok(Self {
+        Self {
             document,
             native_width,
             native_height,
             transform: TransformState::default(),
+            exif_transform: TransformState::default(),
+            source_color_type: None,
+            pages: Vec::new(),
+            page_handles: Vec::new(),
+            page_index: 0,
+            tone: None,
+            enhanced: None,
             handle,
-        })
+        }
     }
 
     /// Rebuild the handle after mutating `document`.
     fn refresh_handle(&mut self) {
-        self.handle = super::create_image_handle_from_image(&self.document);
+        self.enhanced = self.tone.map(|tone| match tone {
+            Tone::AutoLevels => auto_levels(&self.document),
+            Tone::Points { black, white } => apply_ranges(
+                &self.document,
+                std::array::from_fn(|channel| (black[channel], white[channel])),
+            ),
+        });
+        self.handle = super::create_image_handle_from_image(self.image());
+    }
+
+    /// Show the image with auto levels applied, or the untouched original.
+    pub fn set_auto_enhance(&mut self, enabled: bool) {
+        if enabled == self.is_auto_enhanced() {
+            return;
+        }
+        self.tone = enabled.then_some(Tone::AutoLevels);
+        self.refresh_handle();
+    }
+
+    /// Check whether auto levels are applied.
+    pub fn is_auto_enhanced(&self) -> bool {
+        self.tone == Some(Tone::AutoLevels)
+    }
+
+    /// Show the image remapped so the colors at `white` and `black` (image
+    /// pixel coordinates) become pure white and black; the original stays untouched.
+    pub fn set_levels(&mut self, white: (f32, f32), black: (f32, f32)) -> DocResult<()> {
+        let (Some(white), Some(black)) = (
+            sample_rgb(&self.document, white),
+            sample_rgb(&self.document, black),
+        ) else {
+            return Err(anyhow::anyhow!("Level points must lie inside the image"));
+        };
+        self.tone = Some(Tone::Points { black, white });
+        self.refresh_handle();
+        Ok(())
+    }
+
+    /// Drop black and white points set with `set_levels`.
+    pub fn clear_levels(&mut self) {
+        if matches!(self.tone, Some(Tone::Points { .. })) {
+            self.tone = None;
+            self.refresh_handle();
+        }
+    }
+
+    /// Check whether this image is a multi-resolution container (ICO/CUR).
+    pub fn has_pages(&self) -> bool {
+        self.pages.len() > 1
+    }
+
+    /// Returns the current (transformed, possibly enhanced) full-resolution image.
+    pub fn image(&self) -> &DynamicImage {
+        self.enhanced.as_ref().unwrap_or(&self.document)
     }
 
     /// Returns the current pixel dimensions (width, height) after transforms.
@@ -60,7 +290,27 @@ impl RasterDocument {
 
     /// Extract metadata for this raster document.
     pub fn extract_meta(&self, path: &Path) -> super::meta::DocumentMeta {
-        super::meta::build_raster_meta(path, &self.document, self.native_width, self.native_height)
+        let mut meta = super::meta::build_raster_meta(
+            path,
+            &self.document,
+            self.native_width,
+            self.native_height,
+        );
+        if let Some(ref color_type) = self.source_color_type {
+            meta.basic.color_type = color_type.clone();
+        }
+        meta
+    }
+
+    /// Extract metadata for a raster document decoded from memory.
+    pub fn extract_meta_from_bytes(&self, name: &str, bytes: &[u8]) -> super::meta::DocumentMeta {
+        super::meta::build_memory_meta(
+            name,
+            bytes,
+            &self.document,
+            self.native_width,
+            self.native_height,
+        )
     }
 
     /// Crop the image to the specified rectangle.
@@ -76,8 +326,8 @@ impl RasterDocument {
             ));
         }
 
-        let cropped = imageops::crop_imm(&self.document, x, y, width, height).to_image();
-        self.document = DynamicImage::ImageRgba8(cropped);
+        // DynamicImage operations keep the bit depth (16-bit TIFF/PNG) for saving.
+        self.document = self.document.crop_imm(x, y, width, height);
 
         self.native_width = width;
         self.native_height = height;
@@ -88,28 +338,6 @@ impl RasterDocument {
 
         Ok(())
     }
-
-    /// Crop the image to the specified rectangle and return as DynamicImage.
-    ///
-    /// This does NOT modify the document - it's used for exporting cropped images.
-    pub fn crop_to_image(
-        &self,
-        x: u32,
-        y: u32,
-        width: u32,
-        height: u32,
-    ) -> DocResult<DynamicImage> {
-        let (img_width, img_height) = self.document.dimensions();
-
-        if x + width > img_width || y + height > img_height {
-            return Err(anyhow::anyhow!(
-                "Crop rectangle out of bounds: {width}x{height} at ({x}, {y}) exceeds image size {img_width}x{img_height}"
-            ));
-        }
-
-        let cropped = imageops::crop_imm(&self.document, x, y, width, height).to_image();
-        Ok(DynamicImage::ImageRgba8(cropped))
-    }
 }
 
 // ============================================================================
@@ -137,6 +365,55 @@ impl Renderable for RasterDocument {
     }
 }
 
+impl MultiPage for RasterDocument {
+    fn page_count(&self) -> usize {
+        self.pages.len().max(1)
+    }
+
+    fn current_page(&self) -> usize {
+        self.page_index
+    }
+
+    fn go_to_page(&mut self, page: usize) -> DocResult<()> {
+        let Some(image) = self.pages.get(page) else {
+            return Err(anyhow::anyhow!(
+                "Page {} out of range (0-{})",
+                page,
+                self.page_count() - 1
+            ));
+        };
+        self.document = image.clone();
+        (self.native_width, self.native_height) = self.document.dimensions();
+        self.transform = TransformState::default();
+        self.page_index = page;
+        self.refresh_handle();
+        Ok(())
+    }
+}
+
+impl MultiPageThumbnails for RasterDocument {
+    fn get_thumbnail(&self, page: usize) -> Option<ImageHandle> {
+        self.page_handles.get(page).cloned()
+    }
+
+    fn thumbnails_ready(&self) -> bool {
+        // Embedded images are decoded up front.
+        true
+    }
+
+    fn thumbnails_loaded(&self) -> usize {
+        self.page_handles.len()
+    }
+
+    fn generate_thumbnail_page(&mut self, _page: usize) -> Option<usize> {
+        None
+    }
+
+    fn generate_all_thumbnails(&mut self) {}
+
+    fn reset_thumbnails(&mut self) {}
+}
+
 impl Transformable for RasterDocument {
     fn rotate(&mut self, rotation: Rotation) {
         let current_deg = self.transform.rotation.to_degrees();
@@ -145,15 +422,9 @@ impl Transformable for RasterDocument {
 
         match diff_deg {
             0 => {}
-            90 => {
-                self.document = DynamicImage::ImageRgba8(imageops::rotate90(&self.document));
-            }
-            180 => {
-                self.document = DynamicImage::ImageRgba8(imageops::rotate180(&self.document));
-            }
-            270 => {
-                self.document = DynamicImage::ImageRgba8(imageops::rotate270(&self.document));
-            }
+            90 => self.document = self.document.rotate90(),
+            180 => self.document = self.document.rotate180(),
+            270 => self.document = self.document.rotate270(),
             _ => unreachable!("Invalid rotation diff: {}", diff_deg),
         }
         self.transform.rotation = rotation;
@@ -161,13 +432,18 @@ impl Transformable for RasterDocument {
     }
 
     fn flip(&mut self, direction: FlipDirection) {
+        // Flips act on the displayed (rotated) image. Keep the state in
+        // source orientation: at 90°/270° a visual flip swaps axes.
+        let swapped = matches!(self.transform.rotation, Rotation::Cw90 | Rotation::Cw270);
         match direction {
-            FlipDirection::Horizontal => {
-                self.document = DynamicImage::ImageRgba8(imageops::flip_horizontal(&self.document));
+            FlipDirection::Horizontal => self.document = self.document.fliph(),
+            FlipDirection::Vertical => self.document = self.document.flipv(),
+        }
+        match (direction, swapped) {
+            (FlipDirection::Horizontal, false) | (FlipDirection::Vertical, true) => {
                 self.transform.flip_h = !self.transform.flip_h;
             }
-            FlipDirection::Vertical => {
-                self.document = DynamicImage::ImageRgba8(imageops::flip_vertical(&self.document));
+            (FlipDirection::Vertical, false) | (FlipDirection::Horizontal, true) => {
                 self.transform.flip_v = !self.transform.flip_v;
             }
         }
@@ -177,4 +453,199 @@ impl Transformable for RasterDocument {
     fn transform_state(&self) -> TransformState {
         self.transform
     }
+
+    /// Return to the orientation the image was opened in (EXIF-corrected).
+    fn reset_transform(&mut self) {
+        self.set_transform(self.exif_transform);
+    }
+}
+
+/// Stretch each color channel to the full tonal range (auto levels).
+///
+/// The darkest and brightest `AUTO_LEVELS_CLIP` fraction of pixels is
+/// clipped so isolated outliers don't limit the stretch. Fully transparent
+/// pixels are ignored and alpha is kept.
+fn auto_levels(image: &DynamicImage) -> DynamicImage {
+    let mut histograms = [[0u64; 256]; 3];
+    for pixel in image.to_rgba8().pixels().filter(|p| p[3] > 0) {
+        for (channel, histogram) in histograms.iter_mut().enumerate() {
+            histogram[usize::from(pixel[channel])] += 1;
+        }
+    }
+    apply_ranges(image, histograms.map(|histogram| stretch_range(&histogram)))
+}
+
+/// Map each color channel's `(low, high)` range (8-bit scale) onto the full
+/// range, keeping alpha.
+///
+/// Images deeper than 8 bits are mapped at 16 bits, so crops saved from the
+/// adjusted image keep their precision.
+#[allow(clippy::cast_possible_truncation)]
+fn apply_ranges(image: &DynamicImage, ranges: [(u8, u8); 3]) -> DynamicImage {
+    if is_high_bit_depth(image) {
+        let mut rgba = image.to_rgba16();
+        let ranges = ranges.map(|(low, high)| (u32::from(low) * 257, u32::from(high) * 257));
+        for pixel in rgba.pixels_mut() {
+            for (channel, &(low, high)) in ranges.iter().enumerate() {
+                pixel[channel] = remap(u32::from(pixel[channel]), low, high, 65535) as u16;
+            }
+        }
+        return DynamicImage::ImageRgba16(rgba);
+    }
+
+    let tables = ranges.map(|(low, high)| {
+        std::array::from_fn::<u8, 256, _>(|value| {
+            remap(value as u32, u32::from(low), u32::from(high), 255) as u8
+        })
+    });
+    let mut rgba = image.to_rgba8();
+    for pixel in rgba.pixels_mut() {
+        for (channel, table) in tables.iter().enumerate() {
+            pixel[channel] = table[usize::from(pixel[channel])];
+        }
+    }
+    DynamicImage::ImageRgba8(rgba)
+}
+
+/// Map `low..=high` linearly onto `0..=max`, clipping values outside.
+///
+/// An empty range leaves the value as is.
+fn remap(value: u32, low: u32, high: u32, max: u32) -> u32 {
+    if high > low {
+        (value.clamp(low, high) - low) * max / (high - low)
+    } else {
+        value
+    }
+}
+
+/// Check whether an image has more than 8 bits per channel (16-bit or float).
+fn is_high_bit_depth(image: &DynamicImage) -> bool {
+    let color = image.color();
+    color.bytes_per_pixel() > color.channel_count()
+}
+
+/// Clipped value range `(low, high)` of a histogram.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+fn stretch_range(histogram: &[u64; 256]) -> (u8, u8) {
+    let total: u64 = histogram.iter().sum();
+    let clip = (total as f64 * AUTO_LEVELS_CLIP) as u64;
+
+    // First value where the cumulative count exceeds the clipped amount.
+    let bound = |values: &mut dyn Iterator<Item = usize>| {
+        let mut seen = 0;
+        values.find(|&value| {
+            seen += histogram[value];
+            seen > clip
+        })
+    };
+    let low = bound(&mut (0..256)).unwrap_or(0);
+    let high = bound(&mut (0..256).rev()).unwrap_or(255);
+    (low as u8, high as u8)
+}
+
+/// Average color of the 3x3 pixels around `point`, so noise doesn't skew a picked level.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn sample_rgb(image: &DynamicImage, (x, y): (f32, f32)) -> Option<[u8; 3]> {
+    let (width, height) = image.dimensions();
+    if x < 0.0 || y < 0.0 || x >= width as f32 || y >= height as f32 {
+        return None;
+    }
+    let (x, y) = (x as u32, y as u32);
+
+    let mut sum = [0u32; 3];
+    let mut count = 0;
+    for py in y.saturating_sub(1)..=(y + 1).min(height - 1) {
+        for px in x.saturating_sub(1)..=(x + 1).min(width - 1) {
+            let pixel = image.get_pixel(px, py);
+            for (total, value) in sum.iter_mut().zip(pixel.0) {
+                *total += u32::from(value);
+            }
+            count += 1;
+        }
+    }
+    Some(sum.map(|total| (total / count) as u8))
+}
+
+/// Check whether a path refers to a Photoshop document.
+fn is_psd(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("psd"))
+}
+
+/// Check whether a path refers to a JPEG file.
+fn is_jpeg(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("jpg") || e.eq_ignore_ascii_case("jpeg"))
+}
+
+/// Check whether a path refers to a PNG file.
+fn is_png(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("png"))
+}
+
+/// Describe a PNG whose samples the decoder expands to 8 bits (palette or
+/// 1/2/4-bit grayscale), e.g. "Indexed 4-bit", from its IHDR chunk.
+///
+/// 8 and 16-bit PNGs keep their depth when decoded and return None.
+fn png_source_layout(path: &Path) -> Option<String> {
+    let mut header = [0u8; 26];
+    std::fs::File::open(path)
+        .ok()?
+        .read_exact(&mut header)
+        .ok()?;
+    if &header[12..16] != b"IHDR" {
+        return None;
+    }
+
+    let (bit_depth, color_type) = (header[24], header[25]);
+    let kind = match color_type {
+        3 => "Indexed",
+        0 if bit_depth < 8 => "Grayscale",
+        _ => return None,
+    };
+    Some(format!("{kind} {bit_depth}-bit"))
+}
+
+/// Check whether a path refers to a TIFF file.
+fn is_tiff(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("tif") || e.eq_ignore_ascii_case("tiff"))
+}
+
+/// Crop a preview to the aspect ratio of the full image.
+///
+/// Cameras often pad EXIF thumbnails to a fixed size (e.g. 160x120) with
+/// black bars; trimming them keeps the preview aligned with the final image.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn trim_to_aspect(preview: DynamicImage, width: u32, height: u32) -> DynamicImage {
+    let (preview_width, preview_height) = preview.dimensions();
+    if width == 0 || height == 0 || preview_width == 0 || preview_height == 0 {
+        return preview;
+    }
+
+    let ratio = f64::from(width) / f64::from(height);
+    let preview_ratio = f64::from(preview_width) / f64::from(preview_height);
+    if preview_ratio > ratio {
+        let trimmed = ((f64::from(preview_height) * ratio).round() as u32).max(1);
+        preview.crop_imm((preview_width - trimmed) / 2, 0, trimmed, preview_height)
+    } else {
+        let trimmed = ((f64::from(preview_width) / ratio).round() as u32).max(1);
+        preview.crop_imm(0, (preview_height - trimmed) / 2, preview_width, trimmed)
+    }
+}
+
+/// Check whether a path refers to a multi-resolution icon (ICO/CUR).
+fn is_icon(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("ico") || e.eq_ignore_ascii_case("cur"))
 }
@@ -0,0 +1,111 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// src/app/document/registry.rs
+//
+// Registry of document formats: extensions, kinds and loaders.
+
+use std::path::Path;
+
+use cosmic::iced_renderer::graphics::image::image_rs::ImageFormat as CosmicImageFormat;
+
+#[cfg(feature = "djvu")]
+use super::djvu::DjvuDocument;
+#[cfg(feature = "portable")]
+use super::portable::PortableDocument;
+use super::raster::RasterDocument;
+use super::vector::VectorDocument;
+use super::{DocResult, DocumentContent, DocumentKind};
+
+/// Opens a file as a document.
+pub type Loader = fn(&Path) -> DocResult<DocumentContent>;
+
+/// A document format known to the viewer.
+pub struct Format {
+    /// Kind of document the loader produces.
+    pub kind: DocumentKind,
+    /// Lowercase file extensions (without the dot).
+    pub extensions: &'static [&'static str],
+    /// Opens a file of this format.
+    pub loader: Loader,
+}
+
+/// Formats matched by extension, checked in order.
+///
+/// Adding a format means adding an entry here; anything else image-rs
+/// can read falls back to `RASTER`.
+static FORMATS: &[Format] = &[
+    Format {
+        kind: DocumentKind::Vector,
+        extensions: &["svg", "svgz"],
+        loader: load_vector,
+    },
+    Format {
+        kind: DocumentKind::Portable,
+        extensions: &["pdf"],
+        loader: load_portable,
+    },
+    Format {
+        kind: DocumentKind::Djvu,
+        extensions: &["djvu", "djv"],
+        loader: load_djvu,
+    },
+    // PSD: flattened composite, decoded separately from image-rs.
+    // CUR: cursor files share the ICO container.
+    Format {
+        kind: DocumentKind::Raster,
+        extensions: &["psd", "cur"],
+        loader: load_raster,
+    },
+];
+
+/// Fallback for every extension image-rs recognizes.
+static RASTER: Format = Format {
+    kind: DocumentKind::Raster,
+    extensions: &[],
+    loader: load_raster,
+};
+
+/// Find the format of a file from its extension (without reading the file).
+#[must_use]
+pub fn lookup(path: &Path) -> Option<&'static Format> {
+    let ext = path.extension()?.to_str()?.to_lowercase();
+
+    if let Some(format) = FORMATS
+        .iter()
+        .find(|format| format.extensions.contains(&ext.as_str()))
+    {
+        return Some(format);
+    }
+
+    // Raster: Check via cosmic/image-rs
+    CosmicImageFormat::from_path(path)
+        .is_ok()
+        .then_some(&RASTER)
+}
+
+fn load_raster(path: &Path) -> DocResult<DocumentContent> {
+    Ok(DocumentContent::Raster(RasterDocument::open(path)?))
+}
+
+fn load_vector(path: &Path) -> DocResult<DocumentContent> {
+    Ok(DocumentContent::Vector(VectorDocument::open(path)?))
+}
+
+#[cfg(feature = "portable")]
+fn load_portable(path: &Path) -> DocResult<DocumentContent> {
+    Ok(DocumentContent::Portable(PortableDocument::open(path)?))
+}
+
+#[cfg(not(feature = "portable"))]
+fn load_portable(_path: &Path) -> DocResult<DocumentContent> {
+    Err(super::file::OpenError::MissingBackend(super::file::Backend::Poppler).into())
+}
+
+#[cfg(feature = "djvu")]
+fn load_djvu(path: &Path) -> DocResult<DocumentContent> {
+    Ok(DocumentContent::Djvu(DjvuDocument::open(path)?))
+}
+
+#[cfg(not(feature = "djvu"))]
+fn load_djvu(_path: &Path) -> DocResult<DocumentContent> {
+    Err(super::file::OpenError::MissingBackend(super::file::Backend::Djvulibre).into())
+}
@@ -0,0 +1,406 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// src/app/document/tiff.rs
+//
+// TIFF layouts the image crate misrenders (high bit depths, float samples, WhiteIsZero).
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use image::{DynamicImage, ImageBuffer, Luma, LumaA, Rgb, Rgba};
+use tiff::decoder::{Decoder, DecodingResult};
+use tiff::tags::Tag;
+use tiff::ColorType;
+
+/// PhotometricInterpretation value for grayscale where 0 is white.
+const PHOTOMETRIC_WHITE_IS_ZERO: u16 = 0;
+/// SampleFormat value for IEEE floating point samples.
+const SAMPLE_FORMAT_FLOAT: u16 = 3;
+/// NewSubfileType bit marking a reduced-resolution copy of another image.
+const SUBFILE_REDUCED_RESOLUTION: u32 = 1;
+
+/// Header facts of a TIFF file, read without decoding pixels.
+#[derive(Debug, Clone, Copy)]
+pub struct TiffInfo {
+    color_type: ColorType,
+    float: bool,
+    tiled: bool,
+    white_is_zero: bool,
+}
+
+impl TiffInfo {
+    /// Read the header of a TIFF file; None if it is not a readable TIFF.
+    pub fn read(path: &Path) -> Option<Self> {
+        let file = File::open(path).ok()?;
+        let mut decoder = Decoder::new(BufReader::new(file)).ok()?;
+        let color_type = decoder.colortype().ok()?;
+        let float = decoder
+            .find_tag_unsigned_vec::<u16>(Tag::SampleFormat)
+            .ok()
+            .flatten()
+            .is_some_and(|formats| formats.contains(&SAMPLE_FORMAT_FLOAT));
+        let tiled = decoder.find_tag(Tag::TileWidth).ok().flatten().is_some();
+        let white_is_zero = decoder
+            .find_tag_unsigned::<u16>(Tag::PhotometricInterpretation)
+            .ok()
+            .flatten()
+            == Some(PHOTOMETRIC_WHITE_IS_ZERO);
+
+        Some(Self {
+            color_type,
+            float,
+            tiled,
+            white_is_zero,
+        })
+    }
+
+    /// Check whether the image crate would reject or misrender this layout.
+    ///
+    /// It handles 8/16-bit integer and 32-bit float RGB(A) in strips and tiles,
+    /// but not wider or float grayscale samples, and ignores WhiteIsZero.
+    /// Bilevel (1-bit) WhiteIsZero images are left to it.
+    pub fn needs_own_decoder(&self) -> bool {
+        match self.color_type {
+            ColorType::Gray(bits) | ColorType::GrayA(bits) => {
+                bits > 16 || self.float || (self.white_is_zero && bits >= 8)
+            }
+            ColorType::RGB(bits) | ColorType::RGBA(bits) => {
+                bits > 16 && !(bits == 32 && self.float)
+            }
+            _ => false,
+        }
+    }
+
+    /// Source color type shown in the properties panel (e.g. "Grayscale 32-bit float, tiled").
+    pub fn describe(&self) -> String {
+        let (model, bits) = match self.color_type {
+            ColorType::Gray(bits) => ("Grayscale", bits),
+            ColorType::GrayA(bits) => ("Grayscale+Alpha", bits),
+            ColorType::RGB(bits) => ("RGB", bits),
+            ColorType::RGBA(bits) => ("RGBA", bits),
+            ColorType::Palette(bits) => ("Palette", bits),
+            ColorType::CMYK(bits) => ("CMYK", bits),
+            ColorType::CMYKA(bits) => ("CMYK+Alpha", bits),
+            ColorType::YCbCr(bits) => ("YCbCr", bits),
+            _ => ("Multiband", 0),
+        };
+
+        let mut out = model.to_string();
+        if bits > 0 {
+            out.push_str(&format!(" {bits}-bit"));
+        }
+        if self.float {
+            out.push_str(" float");
+        }
+        if self.tiled {
+            out.push_str(", tiled");
+        }
+        out
+    }
+}
+
+/// Decode a TIFF the image crate can't display correctly.
+///
+/// 8- and 16-bit samples are kept as they are; wider integer and float
+/// samples are stretched from their actual range to 16 bits, since
+/// scientific data rarely fills the type's range. WhiteIsZero is inverted.
+pub fn decode(path: &Path, info: &TiffInfo) -> anyhow::Result<DynamicImage> {
+    let file = File::open(path)?;
+    let mut decoder = Decoder::new(BufReader::new(file))?;
+    let (width, height) = decoder.dimensions()?;
+
+    let channels: u8 = match info.color_type {
+        ColorType::Gray(_) => 1,
+        ColorType::GrayA(_) => 2,
+        ColorType::RGB(_) => 3,
+        ColorType::RGBA(_) => 4,
+        other => anyhow::bail!("Unsupported TIFF color type: {other:?}"),
+    };
+
+    let mut image = match decoder.read_image()? {
+        DecodingResult::U8(samples) => from_samples(width, height, channels, samples),
+        DecodingResult::U16(samples) => from_samples(width, height, channels, samples),
+        DecodingResult::U32(samples) => stretched(width, height, channels, &samples),
+        DecodingResult::U64(samples) => stretched(width, height, channels, &samples),
+        DecodingResult::I8(samples) => stretched(width, height, channels, &samples),
+        DecodingResult::I16(samples) => stretched(width, height, channels, &samples),
+        DecodingResult::I32(samples) => stretched(width, height, channels, &samples),
+        DecodingResult::F32(samples) => stretched(width, height, channels, &samples),
+        DecodingResult::F64(samples) => stretched(width, height, channels, &samples),
+        _ => None,
+    }
+    .ok_or_else(|| anyhow::anyhow!("Unsupported TIFF sample layout"))?;
+
+    if info.white_is_zero {
+        image.invert();
+    }
+    Ok(image)
+}
+
+/// Decode the largest reduced-resolution copy of the image, as pyramidal and
+/// many scanner TIFFs store after the full image, with at most `max_pixels`.
+///
+/// Only 8- and 16-bit gray and RGB(A) copies are used; None if there is none.
+pub fn decode_reduced(path: &Path, max_pixels: u64) -> Option<DynamicImage> {
+    let file = File::open(path).ok()?;
+    let mut decoder = Decoder::new(BufReader::new(file)).ok()?;
+
+    let mut best: Option<(usize, u64)> = None;
+    let mut index = 0;
+    while decoder.more_images() {
+        decoder.next_image().ok()?;
+        index += 1;
+        let reduced = decoder
+            .find_tag_unsigned::<u32>(Tag::NewSubfileType)
+            .ok()
+            .flatten()
+            .is_some_and(|kind| kind & SUBFILE_REDUCED_RESOLUTION != 0);
+        let Ok((width, height)) = decoder.dimensions() else {
+            continue;
+        };
+        let pixels = u64::from(width) * u64::from(height);
+        if reduced && pixels <= max_pixels && best.is_none_or(|(_, most)| pixels > most) {
+            best = Some((index, pixels));
+        }
+    }
+
+    let (index, _) = best?;
+    decoder.seek_to_image(index).ok()?;
+    let (width, height) = decoder.dimensions().ok()?;
+    let channels: u8 = match decoder.colortype().ok()? {
+        ColorType::Gray(8 | 16) => 1,
+        ColorType::GrayA(8 | 16) => 2,
+        ColorType::RGB(8 | 16) => 3,
+        ColorType::RGBA(8 | 16) => 4,
+        _ => return None,
+    };
+    let white_is_zero = decoder
+        .find_tag_unsigned::<u16>(Tag::PhotometricInterpretation)
+        .ok()
+        .flatten()
+        == Some(PHOTOMETRIC_WHITE_IS_ZERO);
+
+    let mut image = match decoder.read_image().ok()? {
+        DecodingResult::U8(samples) => from_samples(width, height, channels, samples),
+        DecodingResult::U16(samples) => from_samples(width, height, channels, samples),
+        _ => None,
+    }?;
+    if white_is_zero {
+        image.invert();
+    }
+    Some(image)
+}
+
+/// Sample types that can be stretched to 16 bits.
+trait Sample: Copy {
+    fn to_f64(self) -> f64;
+}
+
+impl Sample for u32 {
+    fn to_f64(self) -> f64 {
+        f64::from(self)
+    }
+}
+
+impl Sample for u64 {
+    #[allow(clippy::cast_precision_loss)]
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Sample for i8 {
+    fn to_f64(self) -> f64 {
+        f64::from(self)
+    }
+}
+
+impl Sample for i16 {
+    fn to_f64(self) -> f64 {
+        f64::from(self)
+    }
+}
+
+impl Sample for i32 {
+    fn to_f64(self) -> f64 {
+        f64::from(self)
+    }
+}
+
+impl Sample for f32 {
+    fn to_f64(self) -> f64 {
+        f64::from(self)
+    }
+}
+
+impl Sample for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
+}
+
+/// Map samples linearly from their finite min..max range to 0..65535.
+fn stretched<T: Sample>(
+    width: u32,
+    height: u32,
+    channels: u8,
+    samples: &[T],
+) -> Option<DynamicImage> {
+    let (min, max) = samples
+        .iter()
+        .map(|s| s.to_f64())
+        .filter(|v| v.is_finite())
+        .fold((f64::MAX, f64::MIN), |(lo, hi), v| (lo.min(v), hi.max(v)));
+    let range = if max > min { max - min } else { 1.0 };
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let scaled: Vec<u16> = samples
+        .iter()
+        .map(|s| {
+            let v = s.to_f64();
+            if v.is_finite() {
+                ((v - min) / range * f64::from(u16::MAX)).round() as u16
+            } else {
+                0
+            }
+        })
+        .collect();
+    from_samples(width, height, channels, scaled)
+}
+
+/// Wrap interleaved 8- or 16-bit samples in the matching `DynamicImage` variant.
+fn from_samples<T>(width: u32, height: u32, channels: u8, samples: Vec<T>) -> Option<DynamicImage>
+where
+    T: image::Primitive,
+    DynamicImage: From<ImageBuffer<Luma<T>, Vec<T>>>
+        + From<ImageBuffer<LumaA<T>, Vec<T>>>
+        + From<ImageBuffer<Rgb<T>, Vec<T>>>
+        + From<ImageBuffer<Rgba<T>, Vec<T>>>,
+{
+    Some(match channels {
+        1 => ImageBuffer::<Luma<T>, _>::from_raw(width, height, samples)?.into(),
+        2 => ImageBuffer::<LumaA<T>, _>::from_raw(width, height, samples)?.into(),
+        3 => ImageBuffer::<Rgb<T>, _>::from_raw(width, height, samples)?.into(),
+        _ => ImageBuffer::<Rgba<T>, _>::from_raw(width, height, samples)?.into(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(name)
+    }
+
+    fn info(color_type: ColorType, float: bool, white_is_zero: bool) -> TiffInfo {
+        TiffInfo {
+            color_type,
+            float,
+            tiled: false,
+            white_is_zero,
+        }
+    }
+
+    #[test]
+    fn decode_gray16() {
+        let path = fixture("gray16.tiff");
+        let info = TiffInfo::read(&path).expect("fixture TIFF header");
+        assert!(!info.needs_own_decoder());
+        assert_eq!(info.describe(), "Grayscale 16-bit");
+
+        let image = decode(&path, &info).expect("fixture TIFF decodes");
+        assert_eq!(image.color(), image::ColorType::L16);
+        let image = image.into_luma16();
+        assert_eq!(image.dimensions(), (4, 2));
+        let samples: Vec<u16> = image.pixels().map(|p| p[0]).collect();
+        assert_eq!(samples, [0, 1000, 30000, 65535, 256, 512, 4096, 40000]);
+    }
+
+    #[test]
+    fn decode_tiled_rgb() {
+        let path = fixture("tiled_rgb.tiff");
+        let info = TiffInfo::read(&path).expect("fixture TIFF header");
+        assert!(!info.needs_own_decoder());
+        assert_eq!(info.describe(), "RGB 8-bit, tiled");
+
+        let image = decode(&path, &info).expect("fixture TIFF decodes");
+        assert_eq!(image.color(), image::ColorType::Rgb8);
+        let image = image.into_rgb8();
+        // 20 × 20 pixels in four 16 × 16 tiles, each a single color.
+        assert_eq!(image.dimensions(), (20, 20));
+        assert_eq!(image.get_pixel(0, 0).0, [255, 0, 0]);
+        assert_eq!(image.get_pixel(19, 0).0, [0, 255, 0]);
+        assert_eq!(image.get_pixel(0, 19).0, [0, 0, 255]);
+        assert_eq!(image.get_pixel(19, 19).0, [255, 255, 0]);
+    }
+
+    #[test]
+    fn decode_reduced_copy() {
+        // An 8 × 4 red image followed by a 2 × 1 green reduced-resolution copy.
+        let path = fixture("reduced_rgb.tiff");
+        let image = decode_reduced(&path, 100).expect("fixture has a reduced copy");
+        let image = image.into_rgb8();
+        assert_eq!(image.dimensions(), (2, 1));
+        assert_eq!(image.get_pixel(0, 0).0, [0, 255, 0]);
+
+        // Copies above the pixel budget are not used.
+        assert!(decode_reduced(&path, 1).is_none());
+        assert!(decode_reduced(&fixture("gray16.tiff"), 100).is_none());
+    }
+
+    #[test]
+    fn own_decoder_for_gray() {
+        assert!(!info(ColorType::Gray(8), false, false).needs_own_decoder());
+        assert!(!info(ColorType::Gray(16), false, false).needs_own_decoder());
+        assert!(info(ColorType::Gray(32), false, false).needs_own_decoder());
+        assert!(info(ColorType::Gray(32), true, false).needs_own_decoder());
+        assert!(info(ColorType::GrayA(16), true, false).needs_own_decoder());
+        assert!(info(ColorType::Gray(8), false, true).needs_own_decoder());
+        assert!(info(ColorType::Gray(16), false, true).needs_own_decoder());
+        // Bilevel WhiteIsZero is handled by the image crate.
+        assert!(!info(ColorType::Gray(1), false, true).needs_own_decoder());
+    }
+
+    #[test]
+    fn own_decoder_for_rgb() {
+        assert!(!info(ColorType::RGB(8), false, false).needs_own_decoder());
+        assert!(!info(ColorType::RGBA(16), false, false).needs_own_decoder());
+        assert!(!info(ColorType::RGB(32), true, false).needs_own_decoder());
+        assert!(info(ColorType::RGB(32), false, false).needs_own_decoder());
+        assert!(info(ColorType::RGBA(64), true, false).needs_own_decoder());
+        assert!(!info(ColorType::Palette(8), false, false).needs_own_decoder());
+    }
+
+    #[test]
+    fn stretch_integer_range() {
+        let image = stretched(3, 1, 1, &[10u32, 20, 30]).unwrap();
+        assert_eq!(image.into_luma16().into_raw(), [0, 32768, 65535]);
+    }
+
+    #[test]
+    fn stretch_skips_non_finite() {
+        let image = stretched(4, 1, 1, &[f32::NAN, -1.0, 1.0, f32::INFINITY]).unwrap();
+        assert_eq!(image.into_luma16().into_raw(), [0, 0, 65535, 0]);
+    }
+
+    #[test]
+    fn stretch_constant_samples() {
+        let image = stretched(2, 1, 1, &[-5i16, -5]).unwrap();
+        assert_eq!(image.into_luma16().into_raw(), [0, 0]);
+    }
+
+    #[test]
+    fn stretch_rgb_channels_share_range() {
+        let image = stretched(1, 1, 3, &[0.0f64, 0.5, 1.0]).unwrap();
+        assert_eq!(image.color(), image::ColorType::Rgb16);
+        assert_eq!(image.into_rgb16().into_raw(), [0, 32768, 65535]);
+    }
+
+    #[test]
+    fn stretch_rejects_short_buffer() {
+        assert!(stretched(2, 2, 1, &[1u32, 2, 3]).is_none());
+    }
+}
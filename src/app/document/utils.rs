@@ -157,3 +157,195 @@ fn try_feh_wallpaper(path_str: &str) -> bool {
         false
     }
 }
+
+/// Move a file, falling back to copy and delete across file systems.
+pub fn move_file(source: &Path, target: &Path) -> std::io::Result<()> {
+    match std::fs::rename(source, target) {
+        Err(e) if e.kind() == std::io::ErrorKind::CrossesDevices => {
+            std::fs::copy(source, target)?;
+            std::fs::remove_file(source)
+        }
+        result => result,
+    }
+}
+
+/// Open the system file manager with the given file selected.
+///
+/// Attempts the following methods in order:
+/// 1. `org.freedesktop.FileManager1.ShowItems` over D-Bus (selects the file)
+/// 2. Default handler for the parent directory (xdg-open)
+pub fn reveal_in_file_manager(path: &Path) -> anyhow::Result<()> {
+    let abs_path = path.canonicalize()?;
+
+    // Method 1: Try the FileManager1 D-Bus interface.
+    if try_file_manager_dbus(&abs_path) {
+        return Ok(());
+    }
+
+    // Method 2: Open the parent directory with the default handler.
+    let parent = abs_path
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("{} has no parent directory", abs_path.display()))?;
+    open::that(parent)?;
+    log::info!("Opened folder via default handler: {}", parent.display());
+    Ok(())
+}
+
+/// Try revealing a file via the FileManager1 D-Bus interface.
+fn try_file_manager_dbus(path: &Path) -> bool {
+    let items = format!("['{}']", file_uri(path));
+
+    let output = match std::process::Command::new("gdbus")
+        .args([
+            "call",
+            "--session",
+            "--dest",
+            "org.freedesktop.FileManager1",
+            "--object-path",
+            "/org/freedesktop/FileManager1",
+            "--method",
+            "org.freedesktop.FileManager1.ShowItems",
+            &items,
+            "",
+        ])
+        .output()
+    {
+        Ok(o) => o,
+        Err(e) => {
+            log::warn!("gdbus command failed: {e}");
+            return false;
+        }
+    };
+
+    if output.status.success() {
+        log::info!("Revealed file via FileManager1: {}", path.display());
+        true
+    } else {
+        log::warn!(
+            "FileManager1.ShowItems failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        false
+    }
+}
+
+/// Build a percent-encoded `file://` URI for an absolute path.
+pub fn file_uri(path: &Path) -> String {
+    let mut uri = String::from("file://");
+    for byte in path.to_string_lossy().bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' => {
+                uri.push(char::from(byte));
+            }
+            _ => uri.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    uri
+}
+
+/// Print a file, letting the user pick the printer and settings.
+///
+/// Attempts the following methods in order:
+/// 1. `org.freedesktop.portal.Print.Print` over D-Bus (shows the print dialog)
+/// 2. The default printer via CUPS (`lp`), scaled to fit the page
+///
+/// `title` names the print job (the temporary file name means nothing to users).
+pub fn print_file(path: &Path, title: &str) -> anyhow::Result<()> {
+    // Method 1: Try the print portal.
+    if try_print_portal(path, title) {
+        return Ok(());
+    }
+
+    // Method 2: Send the file straight to the default printer.
+    print_with_lp(path, title)
+}
+
+/// Try printing a file via the xdg-desktop-portal Print interface.
+///
+/// The file is passed as gdbus' stdin (`handle 0`). Without a `token` from
+/// `PreparePrint` the portal shows its own print dialog before printing.
+fn try_print_portal(path: &Path, title: &str) -> bool {
+    let file = match std::fs::File::open(path) {
+        Ok(f) => f,
+        Err(e) => {
+            log::warn!("Failed to open {} for printing: {e}", path.display());
+            return false;
+        }
+    };
+    let title = format!("'{}'", title.replace('\\', "\\\\").replace('\'', "\\'"));
+
+    let output = match std::process::Command::new("gdbus")
+        .args([
+            "call",
+            "--session",
+            "--dest",
+            "org.freedesktop.portal.Desktop",
+            "--object-path",
+            "/org/freedesktop/portal/desktop",
+            "--method",
+            "org.freedesktop.portal.Print.Print",
+            "",
+            &title,
+            "handle 0",
+            "@a{sv} {}",
+        ])
+        .stdin(file)
+        .output()
+    {
+        Ok(o) => o,
+        Err(e) => {
+            log::warn!("gdbus command failed: {e}");
+            return false;
+        }
+    };
+
+    if output.status.success() {
+        log::info!("Opened print dialog via portal: {}", path.display());
+        true
+    } else {
+        log::warn!(
+            "Print portal failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        false
+    }
+}
+
+/// Send a file to the default printer with CUPS (`lp`), scaled to fit the page.
+fn print_with_lp(path: &Path, title: &str) -> anyhow::Result<()> {
+    let output = std::process::Command::new("lp")
+        .args(["-o", "fit-to-page", "-t", title])
+        .arg(path)
+        .output()
+        .map_err(|e| anyhow::anyhow!("Failed to run lp (is CUPS installed?): {e}"))?;
+
+    if !output.status.success() {
+        anyhow::bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    log::info!(
+        "Printed {}: {}",
+        path.display(),
+        String::from_utf8_lossy(&output.stdout).trim()
+    );
+    Ok(())
+}
+
+/// Launch an external editor command with the given file as last argument.
+///
+/// The command may contain arguments separated by whitespace
+/// (e.g. "flatpak run org.gimp.GIMP").
+pub fn spawn_external_editor(command: &str, path: &Path) -> anyhow::Result<std::process::Child> {
+    let mut parts = command.split_whitespace();
+    let program = parts
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("External editor command is empty"))?;
+
+    let child = std::process::Command::new(program)
+        .args(parts)
+        .arg(path)
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("Failed to launch {program}: {e}"))?;
+
+    log::info!("Launched external editor {program} for {}", path.display());
+    Ok(child)
+}
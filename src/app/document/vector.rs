@@ -3,11 +3,13 @@
 //
 // Vector documents (SVG, etc.).
 
+use std::fmt;
 use std::path::Path;
+use std::sync::{Arc, OnceLock};
 
-use image::{imageops, DynamicImage, RgbaImage};
+use image::{imageops, DynamicImage, ImageFormat, RgbaImage};
 use resvg::tiny_skia::{self, Pixmap};
-use resvg::usvg::{Options, Tree};
+use resvg::usvg::{fontdb, Options, Tree};
 
 use super::{
     DocResult, DocumentInfo, FlipDirection, ImageHandle, Renderable, RenderOutput, Rotation,
@@ -15,6 +17,20 @@ use super::{
 };
 use crate::constant::MIN_PIXMAP_SIZE;
 
+/// The file could not be parsed as an SVG document.
+///
+/// The parser message is only logged; it rarely helps users.
+#[derive(Debug)]
+pub struct InvalidSvg;
+
+impl fmt::Display for InvalidSvg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Invalid SVG document")
+    }
+}
+
+impl std::error::Error for InvalidSvg {}
+
 /// Represents a vector document such as SVG.
 pub struct VectorDocument {
     /// Parsed SVG document for re-rendering at different scales.
@@ -40,11 +56,14 @@ pub struct VectorDocument {
 impl VectorDocument {
     /// Load a vector document from disk.
     pub fn open(path: &Path) -> anyhow::Result<Self> {
-        let raw_data = std::fs::read_to_string(path)?;
+        let raw_data = std::fs::read(path)?;
 
-        // Parse SVG with default options.
-        let options = Options::default();
-        let document = Tree::from_str(&raw_data, &options)?;
+        // `from_data` detects the gzip header and inflates SVGZ files first.
+        let options = parse_options(path);
+        let document = Tree::from_data(&raw_data, &options).map_err(|e| {
+            log::debug!("Failed to parse {}: {e}", path.display());
+            InvalidSvg
+        })?;
 
         // Get native size from the parsed document.
         let size = document.size();
@@ -53,16 +72,17 @@ impl VectorDocument {
 
         let transform = TransformState::default();
 
-        // Render at native scale (1.0).
+        // Render at native size, multiplied by the window scale factor (HiDPI).
+        let scale = super::display_scale();
         let (rendered, width, height) =
-            render_document(&document, native_width, native_height, 1.0, transform)?;
+            render_document(&document, native_width, native_height, scale, transform)?;
         let handle = super::create_image_handle_from_image(&rendered);
 
         Ok(Self {
             document,
             native_width,
             native_height,
-            current_scale: 1.0,
+            current_scale: scale,
             transform,
             rendered,
             handle,
@@ -76,9 +96,16 @@ impl VectorDocument {
         (self.width, self.height)
     }
 
+    /// Native size in displayed (rotated) orientation.
+    pub fn native_dimensions(&self) -> (u32, u32) {
+        match self.transform.rotation {
+            Rotation::Cw90 | Rotation::Cw270 => (self.native_height, self.native_width),
+            Rotation::None | Rotation::Cw180 => (self.native_width, self.native_height),
+        }
+    }
+
     /// Re-render the SVG at a new scale, preserving transformations.
     /// Returns true if re-rendering occurred.
-    #[allow(dead_code)]
     pub fn render_at_scale(&mut self, scale: f64) -> bool {
         // Skip if scale hasn't changed
         if (self.current_scale - scale).abs() < f64::EPSILON {
@@ -123,6 +150,29 @@ impl VectorDocument {
         }
     }
 
+    /// Rasterize to a PNG file that fits within `width` × `height`.
+    ///
+    /// The target size is in displayed orientation; the aspect ratio is kept.
+    pub fn export_png(&self, path: &Path, width: u32, height: u32) -> DocResult<()> {
+        if width == 0 || height == 0 {
+            return Err(anyhow::anyhow!("Export size must not be zero"));
+        }
+
+        let (shown_width, shown_height) = self.native_dimensions();
+        let scale = (f64::from(width) / f64::from(shown_width.max(1)))
+            .min(f64::from(height) / f64::from(shown_height.max(1)));
+
+        let (image, _, _) = render_document(
+            &self.document,
+            self.native_width,
+            self.native_height,
+            scale,
+            self.transform,
+        )?;
+        image.save_with_format(path, ImageFormat::Png)?;
+        Ok(())
+    }
+
     /// Extract metadata for this vector document.
     pub fn extract_meta(&self, path: &Path) -> super::meta::DocumentMeta {
         // Report native dimensions in metadata.
@@ -130,6 +180,30 @@ impl VectorDocument {
     }
 }
 
+/// Parse options that resolve relative resources next to the SVG file.
+fn parse_options(path: &Path) -> Options<'static> {
+    let mut options = Options {
+        resources_dir: path.parent().map(Path::to_path_buf),
+        ..Options::default()
+    };
+    if super::load_system_fonts() {
+        options.fontdb = system_fonts();
+    }
+    options
+}
+
+/// System font database, loaded once on first use since scanning is slow.
+fn system_fonts() -> Arc<fontdb::Database> {
+    static FONTS: OnceLock<Arc<fontdb::Database>> = OnceLock::new();
+    FONTS
+        .get_or_init(|| {
+            let mut database = fontdb::Database::new();
+            database.load_system_fonts();
+            Arc::new(database)
+        })
+        .clone()
+}
+
 // ============================================================================
 // Trait Implementations
 // ============================================================================
@@ -242,3 +316,15 @@ fn pixmap_to_dynamic_image(pixmap: &Pixmap) -> DynamicImage {
 
     DynamicImage::ImageRgba8(rgba_image)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_gzipped_svg() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/badge.svgz");
+        let document = VectorDocument::open(&path).expect("SVGZ fixture opens");
+        assert_eq!((document.native_width, document.native_height), (120, 80));
+    }
+}
@@ -0,0 +1,63 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// src/app/document/watch.rs
+//
+// File system watcher for the currently displayed document.
+
+use std::path::{Path, PathBuf};
+
+use cosmic::iced::futures::Stream;
+use futures_util::SinkExt;
+use notify::{EventKind, RecursiveMode, Watcher};
+
+use crate::app::AppMessage;
+
+/// Watch a file and emit `FileChangedOnDisk` whenever it is modified.
+///
+/// The parent directory is watched because many editors save by writing a
+/// temporary file and renaming it over the original.
+///
+/// Events carry absolute paths, so a relative `path` is canonicalized for
+/// matching; the message still reports `path` as given.
+pub fn watch_file(path: PathBuf) -> impl Stream<Item = AppMessage> {
+    cosmic::iced::stream::channel(4, move |mut output| async move {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let watched = path.canonicalize().unwrap_or_else(|_| path.clone());
+
+        let watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            let _ = tx.send(res);
+        })
+        .and_then(|mut watcher| {
+            let dir = watched.parent().unwrap_or_else(|| Path::new("."));
+            watcher.watch(dir, RecursiveMode::NonRecursive)?;
+            Ok(watcher)
+        });
+
+        // Keep the watcher alive for as long as the subscription runs.
+        let _watcher = match watcher {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                log::warn!("Failed to watch {}: {e}", path.display());
+                return std::future::pending().await;
+            }
+        };
+
+        while let Some(res) = rx.recv().await {
+            match res {
+                Ok(event)
+                    if is_change(&event.kind) && event.paths.iter().any(|p| p == &watched) =>
+                {
+                    let _ = output
+                        .send(AppMessage::FileChangedOnDisk(path.clone()))
+                        .await;
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("File watcher error: {e}"),
+            }
+        }
+    })
+}
+
+/// Check whether an event kind may have altered the file contents.
+fn is_change(kind: &EventKind) -> bool {
+    matches!(kind, EventKind::Create(_) | EventKind::Modify(_))
+}
@@ -0,0 +1,245 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// src/app/document/xmp.rs
+//
+// Minimal XMP packet parsing (Dublin Core and XMP basic properties).
+
+use std::path::{Path, PathBuf};
+
+use super::meta::DescriptiveMeta;
+
+/// Opening marker of an XMP packet root element.
+const XMP_START: &[u8] = b"<x:xmpmeta";
+/// Closing marker of an XMP packet root element.
+const XMP_END: &[u8] = b"</x:xmpmeta>";
+/// Namespace of the XMP basic properties (`xmp:Rating`).
+const XMP_BASIC_NS: &str = "http://ns.adobe.com/xap/1.0/";
+
+/// Locate an embedded XMP packet in raw file bytes.
+///
+/// XMP is stored as plain UTF-8 XML in all common containers (JPEG APP1,
+/// PNG iTXt, TIFF tag 700, WebP XMP chunk), so a byte search is sufficient.
+pub fn find_packet(data: &[u8]) -> Option<&str> {
+    let start = find_bytes(data, XMP_START)?;
+    let end = find_bytes(&data[start..], XMP_END)? + start + XMP_END.len();
+    std::str::from_utf8(&data[start..end]).ok()
+}
+
+/// Read the sidecar `.xmp` file next to an image, if there is one.
+///
+/// Both naming schemes are checked: `photo.xmp` (Lightroom) and
+/// `photo.jpg.xmp` (darktable, digiKam).
+pub fn read_sidecar(image_path: &Path) -> Option<DescriptiveMeta> {
+    sidecar_candidates(image_path)
+        .into_iter()
+        .find(|candidate| candidate.is_file())
+        .and_then(|sidecar| std::fs::read(sidecar).ok())
+        .and_then(|data| find_packet(&data).map(parse))
+}
+
+/// Store a rating in the sidecar `.xmp` file of an image.
+///
+/// `rating` is 0-5 stars or -1 for rejected. An existing sidecar is updated
+/// in place; otherwise `photo.jpg.xmp` is created.
+pub fn write_rating(image_path: &Path, rating: i8) -> std::io::Result<()> {
+    let candidates = sidecar_candidates(image_path);
+    match candidates.iter().find(|candidate| candidate.is_file()) {
+        Some(sidecar) => {
+            let xml = std::fs::read_to_string(sidecar)?;
+            let updated = set_rating(&xml, rating).ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("no XMP description in {}", sidecar.display()),
+                )
+            })?;
+            std::fs::write(sidecar, updated)
+        }
+        None => std::fs::write(&candidates[1], new_packet(rating)),
+    }
+}
+
+/// Sidecar names for an image: `photo.xmp` (Lightroom) and `photo.jpg.xmp`.
+fn sidecar_candidates(image_path: &Path) -> [PathBuf; 2] {
+    let mut appended = image_path.as_os_str().to_owned();
+    appended.push(".xmp");
+    [image_path.with_extension("xmp"), PathBuf::from(appended)]
+}
+
+/// Replace or add `xmp:Rating` in an XMP document.
+///
+/// Returns None if there is no `rdf:Description` to add the property to.
+fn set_rating(xml: &str, rating: i8) -> Option<String> {
+    // Attribute form: xmp:Rating="3".
+    let attr = "xmp:Rating=\"";
+    if let Some(pos) = xml.find(attr) {
+        let start = pos + attr.len();
+        let end = start + xml[start..].find('"')?;
+        return Some(format!("{}{rating}{}", &xml[..start], &xml[end..]));
+    }
+
+    // Element form: <xmp:Rating>3</xmp:Rating>.
+    let open = "<xmp:Rating>";
+    if let Some(pos) = xml.find(open) {
+        let start = pos + open.len();
+        let end = start + xml[start..].find("</xmp:Rating>")?;
+        return Some(format!("{}{rating}{}", &xml[..start], &xml[end..]));
+    }
+
+    // Add the attribute to the first description.
+    let description = "<rdf:Description";
+    let at = xml.find(description)? + description.len();
+    let namespace = if xml.contains("xmlns:xmp=") {
+        String::new()
+    } else {
+        format!(" xmlns:xmp=\"{XMP_BASIC_NS}\"")
+    };
+    Some(format!(
+        "{}{namespace} xmp:Rating=\"{rating}\"{}",
+        &xml[..at],
+        &xml[at..]
+    ))
+}
+
+/// Minimal sidecar packet holding only a rating.
+fn new_packet(rating: i8) -> String {
+    format!(
+        "<x:xmpmeta xmlns:x=\"adobe:ns:meta/\">\n \
+         <rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\n  \
+         <rdf:Description rdf:about=\"\"\n    \
+         xmlns:xmp=\"{XMP_BASIC_NS}\"\n    \
+         xmp:Rating=\"{rating}\"/>\n \
+         </rdf:RDF>\n\
+         </x:xmpmeta>\n"
+    )
+}
+
+/// Parse descriptive properties from an XMP packet.
+pub fn parse(packet: &str) -> DescriptiveMeta {
+    let rating = property(packet, "xmp:Rating").and_then(|r| r.trim().parse::<f32>().ok());
+    DescriptiveMeta {
+        title: first_item(packet, "dc:title"),
+        description: first_item(packet, "dc:description"),
+        keywords: element_items(packet, "dc:subject").unwrap_or_default(),
+        copyright: first_item(packet, "dc:rights"),
+        creator: element_items(packet, "dc:creator").map(|items| items.join(", ")),
+        rating: rating.and_then(stars),
+        rejected: rating.is_some_and(|r| r < 0.0),
+        label: property(packet, "xmp:Label"),
+    }
+}
+
+/// Convert an XMP rating into a 0-5 star value.
+///
+/// XMP uses -1 for rejected images (no stars); fractional values are rounded.
+fn stars(rating: f32) -> Option<u8> {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    (rating >= 0.0).then(|| rating.round().min(5.0) as u8)
+}
+
+/// Read a simple property written either as attribute or as element.
+fn property(xml: &str, name: &str) -> Option<String> {
+    let attr = format!("{name}=\"");
+    if let Some(pos) = xml.find(&attr) {
+        let rest = &xml[pos + attr.len()..];
+        let end = rest.find('"')?;
+        return non_empty(decode_entities(&rest[..end]));
+    }
+    element_inner(xml, name).and_then(|inner| non_empty(decode_entities(strip_tags(inner))))
+}
+
+/// Return the first list item of an element (e.g. the default language of `rdf:Alt`).
+fn first_item(xml: &str, name: &str) -> Option<String> {
+    element_items(xml, name)?.into_iter().next()
+}
+
+/// Return all `rdf:li` items of an element, or its plain text content.
+fn element_items(xml: &str, name: &str) -> Option<Vec<String>> {
+    let inner = element_inner(xml, name)?;
+
+    let mut items = Vec::new();
+    let mut rest = inner;
+    while let Some(li) = element_inner(rest, "rdf:li") {
+        if let Some(value) = non_empty(decode_entities(strip_tags(li))) {
+            items.push(value);
+        }
+        // Advance past the closing tag of this item.
+        let Some(close) = rest.find("</rdf:li>") else {
+            break;
+        };
+        rest = &rest[close + "</rdf:li>".len()..];
+    }
+
+    if items.is_empty() {
+        non_empty(decode_entities(strip_tags(inner))).map(|v| vec![v])
+    } else {
+        Some(items)
+    }
+}
+
+/// Return the inner content of the first `<name ...>...</name>` element.
+fn element_inner<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    let open = format!("<{name}");
+    let close = format!("</{name}>");
+
+    let mut search_from = 0;
+    loop {
+        let pos = xml[search_from..].find(&open)? + search_from;
+        let after = &xml[pos + open.len()..];
+
+        // Make sure we matched the full tag name (not a prefix of a longer one).
+        match after.chars().next() {
+            Some('>' | ' ' | '\t' | '\r' | '\n') => {}
+            Some('/') => return None,
+            _ => {
+                search_from = pos + open.len();
+                continue;
+            }
+        }
+
+        let tag_end = after.find('>')?;
+        if after[..tag_end].ends_with('/') {
+            // Self-closing element has no content.
+            return None;
+        }
+        let content = &after[tag_end + 1..];
+        let end = content.find(&close)?;
+        return Some(&content[..end]);
+    }
+}
+
+/// Remove any nested markup, keeping only text content.
+fn strip_tags(xml: &str) -> String {
+    let mut out = String::with_capacity(xml.len());
+    let mut in_tag = false;
+    for ch in xml.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => out.push(ch),
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Decode the predefined XML entities.
+fn decode_entities(text: impl AsRef<str>) -> String {
+    text.as_ref()
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Trim and discard empty strings.
+fn non_empty(text: String) -> Option<String> {
+    let trimmed = text.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
+/// Find the first occurrence of `needle` in `haystack`.
+fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
@@ -4,9 +4,27 @@
 // Application messages: events, user actions, and internal signals.
 
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use cosmic::iced::Size;
+use image::DynamicImage;
 
 use crate::app::ContextPage;
-use crate::app::view::crop::DragHandle;
+use crate::app::document::file::OpenError;
+use crate::app::document::meta::DocumentMeta;
+#[cfg(feature = "portable")]
+use crate::app::document::portable::PdfStructure;
+use crate::app::view::crop::{CropAspect, DragHandle};
+use crate::config::{CanvasBackground, CropGrid, DisplayFilter};
+
+/// A decoded image handed over from a background task.
+///
+/// Wrapped so the message stays cheap to clone; the receiver takes the image out.
+pub type DecodedImage = Arc<Mutex<Option<DynamicImage>>>;
+
+/// A decoded archive entry with its metadata, handed over like `DecodedImage`.
+pub type DecodedEntry = Arc<Mutex<Option<(DynamicImage, DocumentMeta)>>>;
 
 #[derive(Debug, Clone)]
 pub enum AppMessage {
@@ -15,25 +33,95 @@ pub enum AppMessage {
     OpenPath(PathBuf),
     NextDocument,
     PrevDocument,
+    FirstDocument,
+    LastDocument,
+    /// Reopen an entry of the recent files list.
+    OpenRecent(usize),
+    RemoteLoaded {
+        url: String,
+        result: Result<Vec<u8>, String>,
+    },
+
+    // Compare (split view).
+    /// Show a second document beside the current one.
+    OpenSecondary(PathBuf),
+    CloseSecondary,
+    /// Compare with the next file in the folder, or close the comparison.
+    ToggleCompare,
+    /// Tie the zoom and pan of both documents together.
+    SyncViews(bool),
+    PreviewReady {
+        path: PathBuf,
+        image: DecodedImage,
+    },
+    FullImageReady {
+        path: PathBuf,
+        result: Result<DecodedImage, OpenError>,
+    },
+    ArchiveEntryReady {
+        path: PathBuf,
+        result: Result<DecodedEntry, OpenError>,
+    },
+    /// The structure of an open PDF (links, page labels) was read in the background.
+    #[cfg(feature = "portable")]
+    PdfStructureReady {
+        path: PathBuf,
+        modified: Option<SystemTime>,
+        structure: Arc<PdfStructure>,
+    },
     GotoPage(usize),
+    FirstPage,
+    LastPage,
+    NextPage,
+    PrevPage,
     GenerateThumbnailPage(usize),
 
+    // Gallery.
+    ToggleGalleryView,
+    GenerateGalleryThumbnail(usize),
+    GalleryMove(isize),
+    GalleryOpenSelected,
+    OpenGalleryEntry(usize),
+
     // Transformations.
     RotateCW,
     RotateCCW,
     FlipHorizontal,
     FlipVertical,
+    SetFlip {
+        horizontal: bool,
+        vertical: bool,
+    },
+    ResetTransform,
+    ToggleExifOrientation,
+    AutoEnhance,
 
     // View / zoom.
     ZoomIn,
     ZoomOut,
     ZoomReset,
     ZoomFit,
+    ZoomFitHeight,
+    SetZoom(f32),
     ViewerStateChanged {
         scale: f32,
         offset_x: f32,
         offset_y: f32,
     },
+    /// Zoom/pan of the second document in the split view changed.
+    SecondaryViewerStateChanged {
+        scale: f32,
+        offset_x: f32,
+        offset_y: f32,
+    },
+    RequestTile {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+    },
+    ToggleCheckerboard,
+    ToggleDebugOverlay,
 
     // Pan control.
     PanLeft,
@@ -41,10 +129,29 @@ pub enum AppMessage {
     PanUp,
     PanDown,
     PanReset,
+    PanTo {
+        x: f32,
+        y: f32,
+    },
 
     // Tool modes.
     ToggleCropMode,
     ToggleScaleMode,
+    ToggleMeasureMode,
+    /// Measure tool click, in image pixel coordinates.
+    MeasurePoint {
+        x: f32,
+        y: f32,
+    },
+    /// Pick a white and then a black point to remap the image tones.
+    ToggleLevelsMode,
+    /// Levels tool click, in image pixel coordinates.
+    LevelsPoint {
+        x: f32,
+        y: f32,
+    },
+    /// Show the original colors again after picking levels.
+    ResetLevels,
 
     // Crop operations.
     StartCrop,
@@ -60,29 +167,114 @@ pub enum AppMessage {
         y: f32,
     },
     CropDragEnd,
+    /// Keyboard nudge in image pixels: `Move` shifts the whole selection,
+    /// a side handle moves only that edge.
+    NudgeCrop {
+        dx: f32,
+        dy: f32,
+        edge: DragHandle,
+    },
+    SetCropAspect(CropAspect),
+    ToggleCropPreview,
+    /// Crop to the screen aspect ratio and set the result as wallpaper.
+    CropAsWallpaper,
+    /// Monitor size in logical pixels, if known.
+    ScreenSize(Option<Size>),
+
+    // Window.
+    /// New window size in logical pixels.
+    WindowResized(Size),
+    /// The window is about to close; persist the session.
+    CloseRequested,
 
     // Panels.
     ToggleContextPage(ContextPage),
     ToggleNavBar,
+    /// Hide or show the header, footer and panels together.
+    ToggleChrome,
 
     // Metadata.
     #[allow(dead_code)]
     RefreshMetadata,
+    CopyMetadata,
+    CopyMetadataJson,
+    ExportMetadataJson,
+
+    // Rating (stored in the XMP sidecar).
+    SetRating(u8),
+    ToggleReject,
 
     // Save operations.
     SaveAs,
 
+    // File operations.
+    SetTransferDir(String),
+    CopyTo(PathBuf),
+    MoveTo(PathBuf),
+    CopyToTransferDir,
+    MoveToTransferDir,
+
     // Wallpaper.
     SetAsWallpaper,
 
+    // Export.
+    OpenExportDialog,
+    ExportWidthChanged(String),
+    ExportHeightChanged(String),
+    ExportPng,
+    CancelExport,
+
+    // Desktop integration.
+    RevealInFileManager,
+    /// Print the current document through the system print dialog.
+    Print,
+    OpenExternal,
+    OpenUrl(String),
+    ExternalEditorClosed {
+        path: PathBuf,
+        modified: Option<SystemTime>,
+    },
+
+    FileChangedOnDisk(PathBuf),
+    ReloadCurrent,
+    RescanFolder,
+    OpenOversized,
+
+    // Settings.
+    SetExternalEditor(String),
+    /// Save the external editor command typed so far.
+    SaveExternalEditor,
+    ToggleLockView,
+    ToggleInertialPanning,
+    ToggleFitUpscale,
+    SetPanStep(f32),
+    SetZoomSensitivity(f32),
+    SetThumbnailWidth(f32),
+    SetNavPanelWidth(f32),
+    SetFilterMethod(DisplayFilter),
+    SetCropGrid(CropGrid),
+    CycleFilterMethod,
+    SetCanvasBackground(CanvasBackground),
+    SetCanvasColor(String),
+    ToggleSvgSystemFonts,
+    TogglePdfTransparentBackground,
+    ToggleRestoreSession,
+    ToggleSortByDate,
+    ToggleShowHidden,
+    ToggleCrossFolderNavigation,
+    SetDefaultFolder,
+
+    // Cache.
+    ClearCache,
+
     // Errors.
     #[allow(dead_code)]
     ShowError(String),
-    #[allow(dead_code)]
     ClearError,
 
     // UI refresh.
     RefreshView,
+    ScaleFactorChanged(f32),
 
     // Fallback.
     #[allow(dead_code)]
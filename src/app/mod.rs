@@ -10,10 +10,14 @@ pub mod update;
 
 mod view;
 
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use cosmic::app::{context_drawer, Core};
 use cosmic::cosmic_config::{self, CosmicConfigEntry};
+use cosmic::iced::event::{self, Event};
+use cosmic::iced::futures::stream;
 use cosmic::iced::keyboard::{self, key::Named, Key, Modifiers};
 use cosmic::iced::time;
 use cosmic::iced::window;
@@ -24,7 +28,12 @@ use cosmic::{Action, Element, Task};
 pub use message::AppMessage;
 pub use model::AppModel;
 
-use crate::config::AppConfig;
+use document::file::OpenError;
+use model::ToolMode;
+use view::crop::DragHandle;
+
+use crate::config::{AppConfig, CanvasBackground, FolderPrefs};
+use crate::constant::{CROP_NUDGE_LARGE_STEP, ERROR_DISMISS_SECS};
 use crate::Args;
 
 /// Flags passed from `main` into the application.
@@ -38,6 +47,7 @@ pub enum Flags {
 pub enum ContextPage {
     #[default]
     Properties,
+    Settings,
 }
 
 /// Main application type.
@@ -76,23 +86,69 @@ impl cosmic::Application for Noctua {
                 Err(_) => (AppConfig::default(), None),
             };
 
+        document::set_max_texture_size(config.max_texture_size);
+        document::set_max_decode_megapixels(config.max_decode_megapixels);
+        document::set_thumbnail_width(thumbnail_pixels(config.thumbnail_width));
+        document::set_display_scale(core.scale_factor());
+        document::set_load_system_fonts(config.svg_system_fonts);
+        document::set_pdf_transparent_background(config.pdf_transparent_background);
         let mut model = AppModel::new(config.clone());
 
         let Flags::Args(args) = flags;
+        model.show_debug_overlay = args.debug;
 
-        // Determine initial path: CLI argument takes priority.
-        // Fall back to configured default directory only if it exists.
-        let initial_path = args.file.or_else(|| {
-            config
-                .default_image_dir
-                .as_ref()
-                .filter(|p| p.exists())
-                .cloned()
+        // A session start directory must exist to override the default folder.
+        let start_dir = args.start_dir.filter(|dir| {
+            let valid = dir.is_dir();
+            if !valid {
+                log::warn!("Ignoring --start-dir, not a directory: {}", dir.display());
+            }
+            valid
         });
 
-        if let Some(path) = initial_path {
-            document::file::open_initial_path(&mut model, &path);
+        // Determine initial paths: CLI arguments take priority, then the
+        // session start directory and the file from the last session. Fall
+        // back to configured default directory only if it exists.
+        let initial_paths: Vec<PathBuf> = if args.files.is_empty() {
+            start_dir
+                .or_else(|| {
+                    config
+                        .last_opened_path
+                        .as_ref()
+                        .filter(|p| config.restore_last_session && p.is_file())
+                        .cloned()
+                })
+                .or_else(|| {
+                    config
+                        .default_image_dir
+                        .as_ref()
+                        .filter(|p| p.exists())
+                        .cloned()
+                })
+                .into_iter()
+                .collect()
+        } else {
+            args.files
+        };
+
+        let mut load_task = Task::none();
+        match initial_paths.as_slice() {
+            [] => {}
+            [path] if document::file::is_stdin_arg(path) => match document::file::read_stdin() {
+                Ok(bytes) => document::file::open_from_bytes(&mut model, "stdin", &bytes),
+                Err(e) => model.set_error(format!("Failed to read stdin: {e}")),
+            },
+            [path] if document::file::is_remote_url(path) => {
+                load_task = fetch_remote_task(path.to_string_lossy().into_owned());
+            }
+            paths => document::file::open_initial_path(&mut model, paths),
+        }
+
+        if let Some(path) = model.loading.clone() {
+            load_task = background_load_task(&model, path);
         }
+        let structure_task = pdf_structure_task(&mut model);
+        update::refresh_render(&mut model);
 
         // Initialize nav bar model (required for COSMIC to show toggle icon).
         let nav = nav_bar::Model::default();
@@ -104,24 +160,123 @@ impl cosmic::Application for Noctua {
         // Start thumbnail generation for initial document if applicable.
         let init_task = start_thumbnail_generation(&model);
 
+        let mut app = Self {
+            core,
+            model,
+            nav,
+            context_page: ContextPage::default(),
+            config,
+            config_handler,
+        };
+
+        // Restore how the initial folder was last viewed.
+        let prefs_task = app
+            .current_folder()
+            .map_or_else(Task::none, |folder| app.apply_folder_prefs(&folder));
+
         (
-            Self {
-                core,
-                model,
-                nav,
-                context_page: ContextPage::default(),
-                config,
-                config_handler,
-            },
-            init_task,
+            app,
+            Task::batch([load_task, structure_task, init_task, prefs_task]),
         )
     }
 
     fn on_close_requested(&self, _id: window::Id) -> Option<Self::Message> {
-        None
+        Some(AppMessage::CloseRequested)
     }
 
     fn update(&mut self, message: Self::Message) -> Task<Action<Self::Message>> {
+        let loading_before = self.model.loading.clone();
+        let folder_before = self.current_folder();
+        let prefs_before = self.current_folder_prefs();
+        let task = self.update_inner(message);
+        let prefs_task = self.switch_folder_prefs(folder_before, prefs_before);
+        let structure_task = pdf_structure_task(&mut self.model);
+        let task = Task::batch([task, prefs_task, structure_task]);
+        update::refresh_render(&mut self.model);
+        self.remember_last_opened();
+        self.remember_recent_files();
+
+        // Spawn background decoding when a large image started loading.
+        match self.model.loading.clone() {
+            Some(path) if loading_before.as_ref() != Some(&path) => {
+                Task::batch([task, background_load_task(&self.model, path)])
+            }
+            _ => task,
+        }
+    }
+
+    fn header_start(&self) -> Vec<Element<'_, Self::Message>> {
+        view::header::start(&self.model)
+    }
+
+    fn header_end(&self) -> Vec<Element<'_, Self::Message>> {
+        view::header::end(&self.model)
+    }
+
+    fn view(&self) -> Element<'_, Self::Message> {
+        view::view(&self.model, &self.config)
+    }
+
+    fn context_drawer(&self) -> Option<context_drawer::ContextDrawer<'_, Self::Message>> {
+        if !self.core.window.show_context || !self.model.chrome_visible {
+            return None;
+        }
+        let content = match self.context_page {
+            ContextPage::Properties => view::panels::view(&self.model),
+            ContextPage::Settings => view::settings::view(&self.model, &self.config),
+        };
+        Some(context_drawer::context_drawer(
+            content,
+            AppMessage::ToggleContextPage(self.context_page),
+        ))
+    }
+
+    fn nav_model(&self) -> Option<&nav_bar::Model> {
+        Some(&self.nav)
+    }
+
+    fn nav_bar(&self) -> Option<Element<'_, Action<Self::Message>>> {
+        if !self.core.nav_bar_active() || !self.model.chrome_visible {
+            return None;
+        }
+        view::nav_bar(&self.model, &self.config)
+    }
+
+    fn dialog(&self) -> Option<Element<'_, Self::Message>> {
+        view::export::dialog(&self.model)
+    }
+
+    fn footer(&self) -> Option<Element<'_, Self::Message>> {
+        if !self.model.chrome_visible {
+            return None;
+        }
+        Some(view::footer::view(&self.model))
+    }
+
+    fn subscription(&self) -> Subscription<Self::Message> {
+        Subscription::batch([
+            if self.model.gallery_visible {
+                keyboard::on_key_press(handle_gallery_key_press)
+            } else if self.model.tool_mode == ToolMode::Crop
+                && self.model.crop_preview.is_none()
+                && self.model.crop_selection.has_selection()
+            {
+                keyboard::on_key_press(handle_crop_key_press)
+            } else {
+                keyboard::on_key_press(handle_key_press)
+            },
+            thumbnail_refresh_subscription(self),
+            error_dismiss_subscription(self),
+            file_watch_subscription(self),
+            rescale_subscription(),
+            window_resize_subscription(),
+        ])
+    }
+}
+
+impl Noctua {
+    /// Apply a message to the application state.
+    fn update_inner(&mut self, message: AppMessage) -> Task<Action<AppMessage>> {
         match &message {
             AppMessage::ToggleNavBar => {
                 self.core.nav_bar_toggle();
@@ -135,6 +290,13 @@ impl cosmic::Application for Noctua {
                 return Task::none();
             }
 
+            AppMessage::ToggleChrome => {
+                // Panel states are left alone, so they come back as they were.
+                self.model.chrome_visible = !self.model.chrome_visible;
+                self.core.window.show_headerbar = self.model.chrome_visible;
+                return Task::none();
+            }
+
             AppMessage::ToggleContextPage(page) => {
                 if self.context_page == *page {
                     self.core.window.show_context = !self.core.window.show_context;
@@ -143,11 +305,243 @@ impl cosmic::Application for Noctua {
                     self.core.window.show_context = true;
                 }
                 self.config.context_drawer_visible = self.core.window.show_context;
+                // Keep an external editor command typed without pressing Enter.
+                self.apply_external_editor_input();
+                self.save_config();
+
+                // Refresh cache size whenever the settings page is shown.
+                if self.context_page == ContextPage::Settings && self.core.window.show_context {
+                    self.model.cache_size = document::cache::cache_size();
+                    self.model.cache_freed = None;
+                }
+                return Task::none();
+            }
+
+            AppMessage::SetExternalEditor(command) => {
+                self.model.external_editor_input.clone_from(command);
+                return Task::none();
+            }
+
+            AppMessage::SaveExternalEditor => {
+                self.apply_external_editor_input();
                 self.save_config();
                 return Task::none();
             }
 
-            AppMessage::OpenPath(_) | AppMessage::NextDocument | AppMessage::PrevDocument => {
+            AppMessage::ToggleLockView => {
+                self.model.lock_view = !self.model.lock_view;
+                self.config.lock_view_on_navigate = self.model.lock_view;
+                self.save_config();
+                return Task::none();
+            }
+
+            AppMessage::ToggleInertialPanning => {
+                self.config.inertial_panning = !self.config.inertial_panning;
+                self.save_config();
+                return Task::none();
+            }
+
+            AppMessage::ToggleFitUpscale => {
+                self.config.fit_upscale = !self.config.fit_upscale;
+                self.save_config();
+                return Task::none();
+            }
+
+            AppMessage::SetPanStep(step) => {
+                self.config.pan_step = *step;
+                self.save_config();
+                return Task::none();
+            }
+
+            AppMessage::SetZoomSensitivity(step) => {
+                self.config.zoom_sensitivity = *step;
+                self.save_config();
+                return Task::none();
+            }
+
+            AppMessage::SetThumbnailWidth(width) => {
+                let grew = *width > self.config.thumbnail_width;
+                self.config.thumbnail_width = *width;
+                self.save_config();
+                document::set_thumbnail_width(thumbnail_pixels(*width));
+
+                // Smaller thumbnails are just drawn smaller; larger ones are
+                // rendered again so they stay sharp.
+                if grew && let Some(doc) = self.model.document.as_mut() {
+                    doc.reset_thumbnails();
+                    return start_thumbnail_generation_task(&self.model);
+                }
+                return Task::none();
+            }
+
+            AppMessage::SetNavPanelWidth(width) => {
+                self.config.nav_panel_width = *width;
+                self.save_config();
+                return Task::none();
+            }
+
+            AppMessage::SetFilterMethod(filter) => {
+                self.config.filter_method = *filter;
+                self.save_config();
+                return Task::none();
+            }
+
+            AppMessage::SetCropGrid(grid) => {
+                self.config.crop_grid = *grid;
+                self.save_config();
+                return Task::none();
+            }
+
+            AppMessage::CycleFilterMethod => {
+                self.config.filter_method = self.config.filter_method.next();
+                self.save_config();
+                return Task::none();
+            }
+
+            AppMessage::SetCanvasBackground(background) => {
+                if let CanvasBackground::Custom(rgb) = background {
+                    self.model.canvas_color_input = CanvasBackground::hex(*rgb);
+                }
+                self.config.canvas_background = *background;
+                self.save_config();
+                return Task::none();
+            }
+
+            AppMessage::SetCanvasColor(text) => {
+                self.model.canvas_color_input.clone_from(text);
+                // Apply once the text is a complete color.
+                if let Some(rgb) = CanvasBackground::parse_hex(text) {
+                    self.config.canvas_background = CanvasBackground::Custom(rgb);
+                    self.save_config();
+                }
+                return Task::none();
+            }
+
+            AppMessage::ToggleSvgSystemFonts => {
+                self.config.svg_system_fonts = !self.config.svg_system_fonts;
+                document::set_load_system_fonts(self.config.svg_system_fonts);
+                self.save_config();
+                // Lay out the text of open SVGs with the new font database.
+                document::file::reparse_vectors(&mut self.model);
+                return Task::none();
+            }
+
+            AppMessage::WindowResized(size) => {
+                // Written to disk when the window closes, not on every resize step.
+                self.config.window_size = Some((size.width, size.height));
+                return Task::none();
+            }
+
+            AppMessage::CloseRequested => {
+                // Nothing is edited in place yet, so there are no changes to
+                // ask about; only persist what is normally saved on the way.
+                if let Some(folder) = self.current_folder() {
+                    let prefs = self.current_folder_prefs();
+                    self.config.set_folder_prefs(&folder, prefs);
+                }
+                if self.config.restore_last_session {
+                    self.config.last_opened_path = self.model.current_path.clone();
+                }
+                self.save_config();
+                return Task::none();
+            }
+
+            AppMessage::TogglePdfTransparentBackground => {
+                let transparent = !self.config.pdf_transparent_background;
+                self.config.pdf_transparent_background = transparent;
+                document::set_pdf_transparent_background(transparent);
+                self.save_config();
+
+                // Show the open PDFs with the new background right away.
+                #[cfg(feature = "portable")]
+                for doc in [&mut self.model.document, &mut self.model.secondary_document]
+                    .into_iter()
+                    .flatten()
+                {
+                    if let document::DocumentContent::Portable(pdf) = doc {
+                        pdf.set_transparent(transparent);
+                    }
+                }
+                return Task::none();
+            }
+
+            AppMessage::ToggleRestoreSession => {
+                self.config.restore_last_session = !self.config.restore_last_session;
+                if !self.config.restore_last_session {
+                    self.config.last_opened_path = None;
+                }
+                self.save_config();
+                return Task::none();
+            }
+
+            AppMessage::SetDefaultFolder => {
+                match self.model.current_path.as_ref().and_then(|p| p.parent()) {
+                    Some(dir) if dir.is_dir() => {
+                        self.config.default_image_dir = Some(dir.to_path_buf());
+                        self.save_config();
+                    }
+                    _ => self.model.set_error("No folder to use as default"),
+                }
+                return Task::none();
+            }
+
+            AppMessage::ToggleSortByDate => {
+                self.model.sort_by_capture_date = !self.model.sort_by_capture_date;
+                self.config.sort_by_capture_date = self.model.sort_by_capture_date;
+                self.save_config();
+
+                // Re-sort the current folder.
+                if let Some(path) = self.model.current_path.clone()
+                    && let Some(parent) = path.parent()
+                {
+                    document::file::refresh_folder_entries(&mut self.model, parent, &path);
+                }
+                return Task::none();
+            }
+
+            AppMessage::ToggleShowHidden => {
+                self.model.show_hidden = !self.model.show_hidden;
+                self.config.show_hidden = self.model.show_hidden;
+                self.save_config();
+
+                if let Some(path) = self.model.current_path.clone()
+                    && let Some(parent) = path.parent()
+                {
+                    document::file::refresh_folder_entries(&mut self.model, parent, &path);
+                }
+                return Task::none();
+            }
+
+            AppMessage::ToggleCrossFolderNavigation => {
+                self.model.cross_folder_navigation = !self.model.cross_folder_navigation;
+                self.config.cross_folder_navigation = self.model.cross_folder_navigation;
+                self.save_config();
+                return Task::none();
+            }
+
+            AppMessage::CropAsWallpaper => {
+                let result = update::update(&mut self.model, &message, &self.config);
+                // The crop ratio follows the monitor the window is on.
+                let screen_task = self.core.main_window_id().map_or_else(Task::none, |id| {
+                    window::monitor_size(id).map(|size| Action::App(AppMessage::ScreenSize(size)))
+                });
+                return match result {
+                    update::UpdateResult::None => screen_task,
+                    update::UpdateResult::Task(task) => Task::batch([task, screen_task]),
+                };
+            }
+
+            AppMessage::OpenPath(_)
+            | AppMessage::NextDocument
+            | AppMessage::PrevDocument
+            | AppMessage::FirstDocument
+            | AppMessage::LastDocument
+            | AppMessage::OpenRecent(_)
+            | AppMessage::GalleryOpenSelected
+            | AppMessage::OpenGalleryEntry(_)
+            | AppMessage::ReloadCurrent
+            | AppMessage::OpenOversized
+            | AppMessage::RemoteLoaded { .. } => {
                 let result = update::update(&mut self.model, &message, &self.config);
                 let thumb_task = start_thumbnail_generation_task(&self.model);
                 return match result {
@@ -165,53 +559,99 @@ impl cosmic::Application for Noctua {
         }
     }
 
-    fn header_start(&self) -> Vec<Element<'_, Self::Message>> {
-        view::header::start(&self.model)
+    /// Persist the shown file so the next session can reopen it.
+    fn remember_last_opened(&mut self) {
+        if self.config.restore_last_session
+            && self.model.current_path.is_some()
+            && self.config.last_opened_path != self.model.current_path
+        {
+            self.config.last_opened_path = self.model.current_path.clone();
+            self.save_config();
+        }
     }
 
-    fn header_end(&self) -> Vec<Element<'_, Self::Message>> {
-        view::header::end(&self.model)
+    /// Persist the recent files list when opening files changed it.
+    fn remember_recent_files(&mut self) {
+        if self.config.recent_files != self.model.recent_files {
+            self.config.recent_files = self.model.recent_files.clone();
+            self.save_config();
+        }
     }
 
-    fn view(&self) -> Element<'_, Self::Message> {
-        view::view(&self.model, &self.config)
+    /// Folder of the current file (None for in-memory documents).
+    fn current_folder(&self) -> Option<PathBuf> {
+        self.model
+            .current_path
+            .as_deref()
+            .and_then(Path::parent)
+            .map(Path::to_path_buf)
     }
 
-    fn context_drawer(&self) -> Option<context_drawer::ContextDrawer<'_, Self::Message>> {
-        if !self.core.window.show_context {
-            return None;
+    /// View preferences of the current folder as they are now.
+    fn current_folder_prefs(&self) -> FolderPrefs {
+        FolderPrefs {
+            view_mode: self.model.view_mode,
+            sort_by_capture_date: self.model.sort_by_capture_date,
+            nav_bar_visible: self.core.nav_bar_active(),
+            context_drawer_visible: self.core.window.show_context,
         }
-        Some(context_drawer::context_drawer(
-            view::panels::view(&self.model),
-            AppMessage::ToggleContextPage(ContextPage::Properties),
-        ))
     }
 
-    fn nav_model(&self) -> Option<&nav_bar::Model> {
-        Some(&self.nav)
-    }
+    /// When the current file moved to another folder, remember the view
+    /// preferences of the folder left and restore those of the folder entered.
+    fn switch_folder_prefs(
+        &mut self,
+        folder_before: Option<PathBuf>,
+        prefs_before: FolderPrefs,
+    ) -> Task<Action<AppMessage>> {
+        let folder = self.current_folder();
+        if folder == folder_before {
+            return Task::none();
+        }
 
-    fn nav_bar(&self) -> Option<Element<'_, Action<Self::Message>>> {
-        if !self.core.nav_bar_active() {
-            return None;
+        if let Some(dir) = folder_before {
+            self.config.set_folder_prefs(&dir, prefs_before);
+            self.save_config();
         }
-        view::nav_bar(&self.model)
+        folder.map_or_else(Task::none, |dir| self.apply_folder_prefs(&dir))
     }
 
-    fn footer(&self) -> Option<Element<'_, Self::Message>> {
-        Some(view::footer::view(&self.model))
-    }
+    /// Restore the view preferences remembered for `folder`.
+    fn apply_folder_prefs(&mut self, folder: &Path) -> Task<Action<AppMessage>> {
+        let Some(prefs) = self.config.folder_prefs(folder) else {
+            return Task::none();
+        };
 
-    fn subscription(&self) -> Subscription<Self::Message> {
-        Subscription::batch([
-            keyboard::on_key_press(handle_key_press),
-            thumbnail_refresh_subscription(self),
-        ])
+        // Lock view keeps zoom and position across documents, folders included.
+        if !self.model.lock_view {
+            self.model.view_mode = prefs.view_mode;
+            self.model.reset_pan();
+        }
+
+        if prefs.sort_by_capture_date != self.model.sort_by_capture_date {
+            self.model.sort_by_capture_date = prefs.sort_by_capture_date;
+            if let Some(path) = self.model.current_path.clone() {
+                document::file::refresh_folder_entries(&mut self.model, folder, &path);
+            }
+        }
+
+        self.core.window.show_context = prefs.context_drawer_visible;
+        if prefs.nav_bar_visible != self.core.nav_bar_active() {
+            self.core.nav_bar_toggle();
+            if prefs.nav_bar_visible {
+                return start_thumbnail_generation_task(&self.model);
+            }
+        }
+        Task::none()
     }
-}
 
-impl Noctua {
     /// Save current config to disk.
+    /// Copy the external editor field into the config (saved by the caller).
+    fn apply_external_editor_input(&mut self) {
+        let command = self.model.external_editor_input.trim();
+        self.config.external_editor = (!command.is_empty()).then(|| command.to_string());
+    }
+
     fn save_config(&self) {
         if let Some(ref handler) = self.config_handler {
             let _ = self.config.write_entry(handler);
@@ -224,13 +664,26 @@ fn handle_key_press(key: Key, modifiers: Modifiers) -> Option<AppMessage> {
     eprintln!("DEBUG KEY: key={:?} modifiers={:?}", key, modifiers);
     use AppMessage::*;
 
-    // Handle Ctrl + arrow keys for panning.
+    // Ctrl + Shift + C copies the metadata as JSON.
+    if modifiers.control() && modifiers.shift() && !modifiers.alt() && !modifiers.logo() {
+        return match key.as_ref() {
+            Key::Character(ch) if ch.eq_ignore_ascii_case("c") => Some(CopyMetadataJson),
+            _ => None,
+        };
+    }
+
+    // Handle Ctrl + arrow keys for panning, Ctrl + 0-5 for rating, Ctrl + P for printing.
     if modifiers.control() && !modifiers.shift() && !modifiers.alt() && !modifiers.logo() {
         return match key.as_ref() {
+            Key::Character(ch) if ch.eq_ignore_ascii_case("p") => Some(Print),
             Key::Named(Named::ArrowLeft) => Some(PanLeft),
             Key::Named(Named::ArrowRight) => Some(PanRight),
             Key::Named(Named::ArrowUp) => Some(PanUp),
             Key::Named(Named::ArrowDown) => Some(PanDown),
+            Key::Character(ch) => match ch.parse::<u8>() {
+                Ok(stars @ 0..=5) => Some(SetRating(stars)),
+                _ => None,
+            },
             _ => None,
         };
     }
@@ -244,6 +697,10 @@ fn handle_key_press(key: Key, modifiers: Modifiers) -> Option<AppMessage> {
         // Navigation with arrow keys (no modifiers).
         Key::Named(Named::ArrowRight) => Some(NextDocument),
         Key::Named(Named::ArrowLeft) => Some(PrevDocument),
+        Key::Named(Named::Home) if modifiers.shift() => Some(FirstPage),
+        Key::Named(Named::End) if modifiers.shift() => Some(LastPage),
+        Key::Named(Named::Home) => Some(FirstDocument),
+        Key::Named(Named::End) => Some(LastDocument),
 
         // Transformations.
         Key::Character(ch) if ch.eq_ignore_ascii_case("h") => Some(FlipHorizontal),
@@ -260,39 +717,261 @@ fn handle_key_press(key: Key, modifiers: Modifiers) -> Option<AppMessage> {
         Key::Character("+" | "=") => Some(ZoomIn),
         Key::Character("-") => Some(ZoomOut),
         Key::Character("1") => Some(ZoomReset),
-        Key::Character(ch) if ch.eq_ignore_ascii_case("f") => Some(ZoomFit),
+        Key::Character(ch) if ch.eq_ignore_ascii_case("f") => {
+            if modifiers.shift() {
+                Some(ZoomFitHeight)
+            } else {
+                Some(ZoomFit)
+            }
+        }
 
         // Tool modes.
         Key::Character(ch) if ch.eq_ignore_ascii_case("c") => {
-            eprintln!("DEBUG MATCH: ToggleCropMode");
-            Some(ToggleCropMode)
+            if modifiers.shift() {
+                Some(ToggleCompare)
+            } else {
+                eprintln!("DEBUG MATCH: ToggleCropMode");
+                Some(ToggleCropMode)
+            }
         }
         Key::Character(ch) if ch.eq_ignore_ascii_case("s") => Some(ToggleScaleMode),
+        Key::Character(ch) if ch.eq_ignore_ascii_case("d") => Some(ToggleMeasureMode),
+        Key::Character(ch) if ch.eq_ignore_ascii_case("b") => {
+            if modifiers.shift() {
+                Some(ResetLevels)
+            } else {
+                Some(ToggleLevelsMode)
+            }
+        }
 
         // Crop mode actions (Enter/Escape handled via key press, validated in update).
         Key::Named(Named::Enter) => Some(AppMessage::ApplyCrop),
         Key::Named(Named::Escape) => Some(AppMessage::CancelCrop),
+        Key::Character(ch) if ch.eq_ignore_ascii_case("z") => Some(ToggleCropPreview),
 
         // Reset pan.
         Key::Character("0") => Some(PanReset),
 
+        // Undo all rotations and flips.
+        Key::Named(Named::Backspace) => Some(ResetTransform),
+
+        // Ignore a wrong EXIF orientation tag.
+        Key::Character(ch) if ch.eq_ignore_ascii_case("o") => Some(ToggleExifOrientation),
+
+        // Auto levels.
+        Key::Character(ch) if ch.eq_ignore_ascii_case("e") => Some(AutoEnhance),
+
         // Toggle panels.
         Key::Character(ch) if ch.eq_ignore_ascii_case("i") => {
             Some(ToggleContextPage(ContextPage::Properties))
         }
         Key::Character(ch) if ch.eq_ignore_ascii_case("n") => Some(ToggleNavBar),
+        Key::Named(Named::Tab) => Some(ToggleChrome),
 
         // Wallpaper.
-        Key::Character(ch) if ch.eq_ignore_ascii_case("w") => Some(SetAsWallpaper),
+        Key::Character(ch) if ch.eq_ignore_ascii_case("w") => {
+            if modifiers.shift() {
+                Some(CropAsWallpaper)
+            } else {
+                Some(SetAsWallpaper)
+            }
+        }
+
+        // Copy / move to the destination folder.
+        Key::Character(ch) if ch.eq_ignore_ascii_case("k") => Some(CopyToTransferDir),
+        Key::Character(ch) if ch.eq_ignore_ascii_case("m") => Some(MoveToTransferDir),
+
+        // Gallery.
+        Key::Character(ch) if ch.eq_ignore_ascii_case("g") => Some(ToggleGalleryView),
+
+        // Interpolation (linear / nearest / auto).
+        Key::Character(ch) if ch.eq_ignore_ascii_case("p") => Some(CycleFilterMethod),
+
+        // Transparency checkerboard.
+        Key::Character(ch) if ch.eq_ignore_ascii_case("t") => Some(ToggleCheckerboard),
+
+        // Reload from disk (Shift: re-read the folder).
+        Key::Named(Named::F5) if modifiers.shift() => Some(RescanFolder),
+        Key::Named(Named::F5) => Some(ReloadCurrent),
+
+        // Debug overlay.
+        Key::Named(Named::F12) => Some(ToggleDebugOverlay),
+
+        // Reject for culling.
+        Key::Character(ch) if ch.eq_ignore_ascii_case("x") => Some(ToggleReject),
+
+        // Lock view.
+        Key::Character(ch) if ch.eq_ignore_ascii_case("l") => Some(ToggleLockView),
 
         _ => None,
     }
 }
 
+/// Key bindings while the gallery grid is shown.
+fn handle_gallery_key_press(key: Key, modifiers: Modifiers) -> Option<AppMessage> {
+    use AppMessage::*;
+
+    if modifiers.command() || modifiers.alt() || modifiers.logo() || modifiers.control() {
+        return None;
+    }
+
+    match key.as_ref() {
+        Key::Named(Named::ArrowRight) => Some(GalleryMove(1)),
+        Key::Named(Named::ArrowLeft) => Some(GalleryMove(-1)),
+        Key::Named(Named::Home) => Some(GalleryMove(isize::MIN)),
+        Key::Named(Named::End) => Some(GalleryMove(isize::MAX)),
+        Key::Named(Named::Enter) => Some(GalleryOpenSelected),
+        Key::Named(Named::Escape) => Some(ToggleGalleryView),
+        Key::Character(ch) if ch.eq_ignore_ascii_case("g") => Some(ToggleGalleryView),
+        _ => None,
+    }
+}
+
+/// Map key presses while a crop selection exists: arrows nudge the selection.
+///
+/// Arrows move the whole selection, Alt + arrows the right or bottom edge;
+/// Shift makes the step larger. Other keys behave as usual.
+fn handle_crop_key_press(key: Key, modifiers: Modifiers) -> Option<AppMessage> {
+    if modifiers.control() || modifiers.logo() {
+        return handle_key_press(key, modifiers);
+    }
+
+    let (dx, dy) = match key.as_ref() {
+        Key::Named(Named::ArrowLeft) => (-1.0, 0.0),
+        Key::Named(Named::ArrowRight) => (1.0, 0.0),
+        Key::Named(Named::ArrowUp) => (0.0, -1.0),
+        Key::Named(Named::ArrowDown) => (0.0, 1.0),
+        _ => return handle_key_press(key, modifiers),
+    };
+    let step = if modifiers.shift() {
+        CROP_NUDGE_LARGE_STEP
+    } else {
+        1.0
+    };
+    let edge = match (modifiers.alt(), dx == 0.0) {
+        (false, _) => DragHandle::Move,
+        (true, false) => DragHandle::Right,
+        (true, true) => DragHandle::Bottom,
+    };
+    Some(AppMessage::NudgeCrop {
+        dx: dx * step,
+        dy: dy * step,
+        edge,
+    })
+}
+
+// =============================================================================
+// Background Loading
+// =============================================================================
+
+/// Download a remote document in the background and deliver it as a message.
+fn fetch_remote_task(url: String) -> Task<Action<AppMessage>> {
+    Task::future(async move {
+        let fetch_url = url.clone();
+        let result = tokio::task::spawn_blocking(move || document::file::fetch_url(&fetch_url))
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|r| r.map_err(|e| e.to_string()));
+        Action::App(AppMessage::RemoteLoaded { url, result })
+    })
+}
+
+/// Decode the document that started loading (`AppModel::loading`) in the background.
+fn background_load_task(model: &AppModel, path: PathBuf) -> Task<Action<AppMessage>> {
+    if document::archive::split(&path).is_some() {
+        let confirmed = model.oversized_confirmed.as_ref() == Some(&path);
+        archive_load_task(path, confirmed)
+    } else {
+        progressive_load_task(path)
+    }
+}
+
+/// Read the structure of a newly opened PDF in the background.
+#[cfg(feature = "portable")]
+fn pdf_structure_task(model: &mut AppModel) -> Task<Action<AppMessage>> {
+    let Some(document::DocumentContent::Portable(pdf)) = model.document.as_mut() else {
+        return Task::none();
+    };
+    let Some((path, num_pages)) = pdf.structure_request() else {
+        return Task::none();
+    };
+    let modified = model.current_mtime;
+    Task::future(async move {
+        let read_path = path.clone();
+        let structure = tokio::task::spawn_blocking(move || {
+            document::portable::PdfStructure::read(&read_path, num_pages)
+        })
+        .await
+        .unwrap_or_default();
+        Action::App(AppMessage::PdfStructureReady {
+            path,
+            modified,
+            structure: Arc::new(structure),
+        })
+    })
+}
+
+#[cfg(not(feature = "portable"))]
+fn pdf_structure_task(_model: &mut AppModel) -> Task<Action<AppMessage>> {
+    Task::none()
+}
+
+/// Read and decode an archive entry in the background.
+fn archive_load_task(path: PathBuf, confirmed: bool) -> Task<Action<AppMessage>> {
+    Task::future(async move {
+        let decode_path = path.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            document::file::decode_archive_entry(&decode_path, confirmed)
+        })
+        .await
+        .map_err(|e| OpenError::Other(e.to_string()))
+        .and_then(|r| r.map_err(OpenError::from));
+        Action::App(AppMessage::ArchiveEntryReady { path, result })
+    })
+}
+
+/// Decode a large image in the background: a quick preview first, then full resolution.
+fn progressive_load_task(path: PathBuf) -> Task<Action<AppMessage>> {
+    let preview_path = path.clone();
+    let preview = Task::future(async move {
+        let decode_path = preview_path.clone();
+        let image = tokio::task::spawn_blocking(move || {
+            document::raster::RasterDocument::decode_preview(&decode_path)
+        })
+        .await
+        .ok()
+        .flatten();
+        Action::App(AppMessage::PreviewReady {
+            path: preview_path,
+            image: Arc::new(Mutex::new(image)),
+        })
+    });
+
+    let full = Task::future(async move {
+        let decode_path = path.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            document::raster::RasterDocument::decode_full(&decode_path)
+        })
+        .await
+        .map_err(|e| OpenError::Other(e.to_string()))
+        .and_then(|r| r.map_err(OpenError::from))
+        .map(|image| Arc::new(Mutex::new(Some(image))));
+        Action::App(AppMessage::FullImageReady { path, result })
+    });
+
+    Task::batch([preview, full])
+}
+
 // =============================================================================
 // Thumbnail Helpers
 // =============================================================================
 
+/// Thumbnail width from the config in whole pixels.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn thumbnail_pixels(width: f32) -> u32 {
+    width.round().max(1.0) as u32
+}
+
 fn start_thumbnail_generation(model: &AppModel) -> Task<Action<AppMessage>> {
     start_thumbnail_generation_task(model)
 }
@@ -323,3 +1002,45 @@ fn thumbnail_refresh_subscription(app: &Noctua) -> Subscription<AppMessage> {
         Subscription::none()
     }
 }
+
+/// Watch the current file and reload it when changed by another program.
+fn file_watch_subscription(app: &Noctua) -> Subscription<AppMessage> {
+    match app.model.current_path {
+        Some(ref path) => {
+            Subscription::run_with_id(path.clone(), document::watch::watch_file(path.clone()))
+        }
+        None => Subscription::none(),
+    }
+}
+
+/// Re-render PDFs and SVGs when the window moves to a display with another scale factor.
+fn rescale_subscription() -> Subscription<AppMessage> {
+    event::listen_with(|event, _status, _id| match event {
+        Event::Window(window::Event::Rescaled(scale)) => {
+            Some(AppMessage::ScaleFactorChanged(scale))
+        }
+        _ => None,
+    })
+}
+
+/// Track the window size so it can be restored in the next session.
+fn window_resize_subscription() -> Subscription<AppMessage> {
+    event::listen_with(|event, _status, _id| match event {
+        Event::Window(window::Event::Resized(size)) => Some(AppMessage::WindowResized(size)),
+        _ => None,
+    })
+}
+
+/// Automatically dismiss the error banner after a timeout.
+fn error_dismiss_subscription(app: &Noctua) -> Subscription<AppMessage> {
+    if app.model.error.is_some() {
+        // Keyed on the error, so a new error restarts the countdown.
+        let dismiss = stream::once(async {
+            tokio::time::sleep(Duration::from_secs(ERROR_DISMISS_SECS)).await;
+            AppMessage::ClearError
+        });
+        Subscription::run_with_id(app.model.error_generation, dismiss)
+    } else {
+        Subscription::none()
+    }
+}
@@ -3,20 +3,27 @@
 //
 // Application state.
 
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use serde::{Deserialize, Serialize};
 
 use crate::app::document::meta::DocumentMeta;
-use crate::app::document::DocumentContent;
-use crate::app::view::crop::CropSelection;
-use crate::config::AppConfig;
+use crate::app::document::{DocumentContent, ImageHandle, RenderOutput};
+use crate::app::view::crop::{CropAspect, CropSelection};
+use crate::config::{AppConfig, CanvasBackground};
+use crate::constant::RECENT_FILES_LIMIT;
 
 // =============================================================================
 // Enums
 // =============================================================================
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum ViewMode {
     Fit,
+    /// Image height matches the viewport; wide images pan horizontally.
+    FitHeight,
     ActualSize,
     Custom(f32),
 }
@@ -24,18 +31,52 @@ pub enum ViewMode {
 impl ViewMode {
     pub fn zoom_factor(&self) -> Option<f32> {
         match self {
-            ViewMode::Fit => None,
+            ViewMode::Fit | ViewMode::FitHeight => None,
             ViewMode::ActualSize => Some(1.0),
             ViewMode::Custom(z) => Some(*z),
         }
     }
 }
 
+/// Full-resolution crop of the visible region, drawn over a downscaled display texture.
+pub struct Tile {
+    /// Display handle the tile was cut for; stale once the document handle changes.
+    pub base: ImageHandle,
+    pub handle: ImageHandle,
+    /// Covered region as fractions of the image size (x, y, width, height).
+    pub region: (f32, f32, f32, f32),
+}
+
+/// Input state of the "Export as PNG" dialog.
+pub struct ExportDialog {
+    pub width: String,
+    pub height: String,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ToolMode {
     None,
     Crop,
     Scale,
+    Measure,
+    Levels,
+}
+
+/// Two points picked with the measure tool, in image pixel coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Measurement {
+    pub start: (f32, f32),
+    /// None until the second point is clicked.
+    pub end: Option<(f32, f32)>,
+}
+
+impl Measurement {
+    /// Distance between the two points in image pixels.
+    pub fn length(&self) -> Option<f32> {
+        let (x1, y1) = self.start;
+        let (x2, y2) = self.end?;
+        Some((x2 - x1).hypot(y2 - y1))
+    }
 }
 
 // =============================================================================
@@ -47,45 +88,164 @@ pub struct AppModel {
     pub document: Option<DocumentContent>,
     pub metadata: Option<DocumentMeta>,
     pub current_path: Option<PathBuf>,
+    /// Modification time of `current_path` when it was loaded.
+    pub current_mtime: Option<SystemTime>,
+    /// Path whose full-resolution image is being decoded in the background.
+    pub loading: Option<PathBuf>,
+    /// When the background decode of `loading` started.
+    pub load_started: Option<Instant>,
+    /// Time it took to decode the current document.
+    pub decode_time: Option<Duration>,
+    /// Image held back because it exceeds the decode size limit.
+    pub oversized: Option<PathBuf>,
+    /// Oversized image the user chose to open anyway.
+    pub oversized_confirmed: Option<PathBuf>,
 
     // Navigation.
     pub folder_entries: Vec<PathBuf>,
     pub current_index: Option<usize>,
+    /// Sort folder entries by capture date (EXIF, falling back to mtime).
+    pub sort_by_capture_date: bool,
+    /// Include dot files in folder navigation.
+    pub show_hidden: bool,
+    /// Continue into sibling folders at the ends of a folder (mirrors the config).
+    pub cross_folder_navigation: bool,
+    /// Recently opened files, most recent first (mirrors the config).
+    pub recent_files: Vec<PathBuf>,
+    /// Cached sort timestamps per file, to avoid re-reading EXIF.
+    pub capture_dates: HashMap<PathBuf, i64>,
+    /// Destination folder for copy/move (remembered for the session).
+    pub transfer_dir: Option<PathBuf>,
 
     // View.
     pub view_mode: ViewMode,
     pub pan_x: f32,
     pub pan_y: f32,
+    /// Keep zoom and pan when loading another document.
+    pub lock_view: bool,
+    /// View (mode, pan) last used per file this session, restored on return.
+    pub image_views: HashMap<PathBuf, (ViewMode, f32, f32)>,
+
+    // Compare (split view).
+    /// Second document shown beside the current one.
+    pub secondary_document: Option<DocumentContent>,
+    pub secondary_path: Option<PathBuf>,
+    pub secondary_render: Option<RenderOutput>,
+    /// View (mode, pan) of the second document while views are not synced.
+    pub secondary_view: (ViewMode, f32, f32),
+    /// Both documents share zoom and pan.
+    pub sync_views: bool,
+    /// Draw a transparency checkerboard behind the image.
+    pub show_checkerboard: bool,
+    /// Latest render of the document, shown by the canvas.
+    pub render: Option<RenderOutput>,
+    /// High-resolution tile for the visible region at extreme zoom.
+    pub tile: Option<Tile>,
+    /// Show the debug overlay with document and view state.
+    pub show_debug_overlay: bool,
+    /// Show the header, footer and side panels (off for a distraction-free view).
+    pub chrome_visible: bool,
+    /// Text of the custom canvas color field (may be incomplete while typing).
+    pub canvas_color_input: String,
+    /// Text of the external editor field, saved when submitted.
+    pub external_editor_input: String,
+
+    // Gallery.
+    pub gallery_visible: bool,
+    pub gallery_selected: usize,
+    pub gallery_thumbnails: HashMap<PathBuf, ImageHandle>,
 
     // Tools.
     pub tool_mode: ToolMode,
     pub crop_selection: CropSelection,
+    pub crop_aspect: CropAspect,
+    /// View (mode, pan) to return to while zoomed onto the crop selection.
+    pub crop_preview: Option<(ViewMode, f32, f32)>,
+    /// Applying the crop sets it as wallpaper instead of saving a file.
+    pub crop_as_wallpaper: bool,
+    /// Width / height of the monitor, once queried.
+    pub screen_aspect: Option<f32>,
+    pub measurement: Option<Measurement>,
+    /// White point picked with the levels tool, waiting for the black point.
+    pub levels_white: Option<(f32, f32)>,
+
+    // Cache.
+    pub cache_size: u64,
+    pub cache_freed: Option<u64>,
+
+    // Export.
+    pub export_dialog: Option<ExportDialog>,
 
     // UI state.
     pub error: Option<String>,
+    /// Counts shown errors, so each one gets its own dismiss timer.
+    pub error_generation: u64,
     pub tick: u64,
 }
 
 impl AppModel {
-    pub fn new(_config: AppConfig) -> Self {
+    pub fn new(config: AppConfig) -> Self {
         Self {
             document: None,
             metadata: None,
             current_path: None,
+            current_mtime: None,
+            loading: None,
+            load_started: None,
+            oversized: None,
+            oversized_confirmed: None,
+            decode_time: None,
             folder_entries: Vec::new(),
             current_index: None,
+            sort_by_capture_date: config.sort_by_capture_date,
+            show_hidden: config.show_hidden,
+            cross_folder_navigation: config.cross_folder_navigation,
+            recent_files: config.recent_files.clone(),
+            capture_dates: HashMap::new(),
+            transfer_dir: None,
             view_mode: ViewMode::Fit,
             pan_x: 0.0,
             pan_y: 0.0,
+            lock_view: config.lock_view_on_navigate,
+            image_views: HashMap::new(),
+            secondary_document: None,
+            secondary_path: None,
+            secondary_render: None,
+            secondary_view: (ViewMode::Fit, 0.0, 0.0),
+            sync_views: true,
+            show_checkerboard: false,
+            render: None,
+            tile: None,
+            show_debug_overlay: false,
+            chrome_visible: true,
+            canvas_color_input: match config.canvas_background {
+                CanvasBackground::Custom(rgb) => CanvasBackground::hex(rgb),
+                _ => String::new(),
+            },
+            external_editor_input: config.external_editor.clone().unwrap_or_default(),
+            gallery_visible: false,
+            gallery_selected: 0,
+            gallery_thumbnails: HashMap::new(),
             tool_mode: ToolMode::None,
             crop_selection: CropSelection::default(),
+            crop_aspect: CropAspect::default(),
+            crop_preview: None,
+            crop_as_wallpaper: false,
+            screen_aspect: None,
+            measurement: None,
+            levels_white: None,
+            cache_size: 0,
+            cache_freed: None,
+            export_dialog: None,
             error: None,
+            error_generation: 0,
             tick: 0,
         }
     }
 
     pub fn set_error<S: Into<String>>(&mut self, msg: S) {
         self.error = Some(msg.into());
+        self.error_generation += 1;
     }
 
     pub fn clear_error(&mut self) {
@@ -97,7 +257,60 @@ impl AppModel {
         self.pan_y = 0.0;
     }
 
+    /// Leave the split view.
+    pub fn close_secondary(&mut self) {
+        self.secondary_document = None;
+        self.secondary_path = None;
+        self.secondary_render = None;
+    }
+
+    /// Move `path` to the front of the recent files, dropping the oldest beyond the limit.
+    pub fn add_recent(&mut self, path: &Path) {
+        self.recent_files.retain(|recent| recent != path);
+        self.recent_files.insert(0, path.to_path_buf());
+        self.recent_files.truncate(RECENT_FILES_LIMIT);
+    }
+
+    /// Return to the view last used for `path` this session, or fit it.
+    pub fn restore_view(&mut self, path: &Path) {
+        match self.image_views.get(path).copied() {
+            Some((view_mode, pan_x, pan_y)) => {
+                self.view_mode = view_mode;
+                self.pan_x = pan_x;
+                self.pan_y = pan_y;
+            }
+            None => {
+                self.reset_pan();
+                self.view_mode = ViewMode::Fit;
+            }
+        }
+    }
+
     pub fn zoom_factor(&self) -> Option<f32> {
         self.view_mode.zoom_factor()
     }
+
+    /// Leave the crop selection preview and return to the view before it.
+    pub fn end_crop_preview(&mut self) {
+        if let Some((view_mode, pan_x, pan_y)) = self.crop_preview.take() {
+            self.view_mode = view_mode;
+            self.pan_x = pan_x;
+            self.pan_y = pan_y;
+        }
+    }
+
+    /// Clamp the pan offset so the view stays within an image of the given size.
+    ///
+    /// The viewport size is unknown here, so the bound is half the scaled image
+    /// extent; the viewer widget tightens it on the next interaction.
+    pub fn clamp_pan(&mut self, width: u32, height: u32) {
+        let Some(zoom) = self.zoom_factor() else {
+            self.reset_pan();
+            return;
+        };
+        #[allow(clippy::cast_precision_loss)]
+        let (max_x, max_y) = (width as f32 * zoom / 2.0, height as f32 * zoom / 2.0);
+        self.pan_x = self.pan_x.clamp(-max_x, max_x);
+        self.pan_y = self.pan_y.clamp(-max_y, max_y);
+    }
 }
@@ -3,12 +3,16 @@
 //
 // Application update loop: applies messages to the global model state.
 
+use std::path::PathBuf;
+
 use cosmic::{Action, Task};
 
-use super::document;
+use super::document::{self, DocumentContent, Renderable};
 use super::message::AppMessage;
-use super::model::{AppModel, ToolMode, ViewMode};
+use super::model::{AppModel, ExportDialog, Measurement, Tile, ToolMode, ViewMode};
+use super::view::crop::CropAspect;
 use crate::config::AppConfig;
+use crate::constant::DEFAULT_SCREEN_ASPECT;
 
 // =============================================================================
 // Update Result
@@ -38,11 +42,123 @@ pub fn update(model: &mut AppModel, msg: &AppMessage, config: &AppConfig) -> Upd
             document::file::navigate_prev(model);
         }
 
+        AppMessage::FirstDocument => {
+            document::file::navigate_first(model);
+        }
+
+        AppMessage::LastDocument => {
+            document::file::navigate_last(model);
+        }
+
+        AppMessage::OpenRecent(index) => {
+            document::file::open_recent(model, *index);
+        }
+
+        // ---- Compare (split view) -------------------------------------------------
+        AppMessage::OpenSecondary(path) => {
+            document::file::open_secondary(model, path);
+        }
+        AppMessage::CloseSecondary => {
+            model.close_secondary();
+        }
+        AppMessage::ToggleCompare => {
+            if model.secondary_document.is_some() {
+                model.close_secondary();
+            } else if let Some(path) = document::file::next_entry(model) {
+                document::file::open_secondary(model, &path);
+            } else {
+                model.set_error("No other file in this folder to compare with");
+            }
+        }
+        AppMessage::SyncViews(sync) => {
+            model.sync_views = *sync;
+            // Unsynced, the second view starts where the shared one was.
+            model.secondary_view = (model.view_mode, model.pan_x, model.pan_y);
+        }
+        AppMessage::SecondaryViewerStateChanged {
+            scale,
+            offset_x,
+            offset_y,
+        } => {
+            model.secondary_view = (ViewMode::Custom(*scale), *offset_x, *offset_y);
+        }
+
+        AppMessage::RemoteLoaded { url, result } => match result {
+            Ok(bytes) => document::file::open_from_bytes(model, url, bytes),
+            Err(e) => model.set_error(e.clone()),
+        },
+
+        AppMessage::PreviewReady { path, image } => {
+            document::file::apply_preview(model, path, image);
+        }
+
+        AppMessage::FullImageReady { path, result } => {
+            document::file::finish_progressive_load(model, path, result);
+        }
+
+        AppMessage::ArchiveEntryReady { path, result } => {
+            document::file::finish_archive_load(model, path, result);
+        }
+
+        #[cfg(feature = "portable")]
+        AppMessage::PdfStructureReady {
+            path,
+            modified,
+            structure,
+        } => {
+            // Drop results for documents that were closed or changed meanwhile.
+            if model.current_path.as_ref() == Some(path)
+                && model.current_mtime == *modified
+                && let Some(DocumentContent::Portable(pdf)) = &mut model.document
+            {
+                pdf.set_structure(std::sync::Arc::clone(structure));
+            }
+        }
+
         AppMessage::GotoPage(page) => {
-            if let Some(doc) = &mut model.document
-                && let Err(e) = doc.go_to_page(*page)
+            if let Some(doc) = &mut model.document {
+                match doc.go_to_page(*page) {
+                    Ok(()) => {
+                        let page_count = doc.page_count().unwrap_or(0);
+                        // Page dimensions may differ (e.g. icon resolutions).
+                        refresh_metadata(model);
+                        return UpdateResult::Task(super::view::pages_panel::scroll_to_page(
+                            *page, page_count,
+                        ));
+                    }
+                    Err(e) => log::error!("Failed to navigate to page {page}: {e}"),
+                }
+            }
+        }
+
+        AppMessage::FirstPage => {
+            if let Some(doc) = &model.document
+                && doc.page_count().is_some()
+            {
+                return update(model, &AppMessage::GotoPage(0), config);
+            }
+        }
+
+        AppMessage::LastPage => {
+            if let Some(page_count) = model.document.as_ref().and_then(|doc| doc.page_count())
+                && page_count > 0
             {
-                log::error!("Failed to navigate to page {page}: {e}");
+                return update(model, &AppMessage::GotoPage(page_count - 1), config);
+            }
+        }
+
+        AppMessage::NextPage | AppMessage::PrevPage => {
+            if let Some(doc) = &model.document
+                && let (Some(current), Some(page_count)) = (doc.current_page(), doc.page_count())
+            {
+                let target = if matches!(msg, AppMessage::NextPage) {
+                    current + 1
+                } else {
+                    current.wrapping_sub(1)
+                };
+                if target < page_count {
+                    return update(model, &AppMessage::GotoPage(target), config);
+                }
             }
         }
 
@@ -60,9 +176,65 @@ pub fn update(model: &mut AppModel, msg: &AppMessage, config: &AppConfig) -> Upd
             }
         }
 
+        // ---- Gallery ---------------------------------------------------------------
+        AppMessage::ToggleGalleryView => {
+            model.gallery_visible = !model.gallery_visible && !model.folder_entries.is_empty();
+            if model.gallery_visible {
+                model.gallery_selected = model.current_index.unwrap_or(0);
+                return UpdateResult::Task(Task::done(Action::App(
+                    AppMessage::GenerateGalleryThumbnail(0),
+                )));
+            }
+        }
+
+        AppMessage::GenerateGalleryThumbnail(index) => {
+            if !model.gallery_visible {
+                return UpdateResult::None;
+            }
+            if let Some(path) = model.folder_entries.get(*index).cloned() {
+                if !model.gallery_thumbnails.contains_key(&path)
+                    && let Some(handle) = document::file_thumbnail(&path)
+                {
+                    model.gallery_thumbnails.insert(path, handle);
+                }
+                let next = *index + 1;
+                return UpdateResult::Task(Task::batch([
+                    Task::future(
+                        async move { Action::App(AppMessage::GenerateGalleryThumbnail(next)) },
+                    ),
+                    Task::done(Action::App(AppMessage::RefreshView)),
+                ]));
+            }
+        }
+
+        AppMessage::GalleryMove(delta) => {
+            let count = model.folder_entries.len();
+            if count > 0 {
+                model.gallery_selected = model
+                    .gallery_selected
+                    .saturating_add_signed(*delta)
+                    .min(count - 1);
+            }
+        }
+
+        AppMessage::GalleryOpenSelected | AppMessage::OpenGalleryEntry(_) => {
+            let index = match msg {
+                AppMessage::OpenGalleryEntry(index) => *index,
+                _ => model.gallery_selected,
+            };
+            model.gallery_visible = false;
+            document::file::open_entry(model, index);
+        }
+
         AppMessage::RefreshView => {
             model.tick += 1;
         }
+        AppMessage::ScaleFactorChanged(scale) => {
+            if document::set_display_scale(*scale) {
+                refresh_render(model);
+                clamp_pan_to_document(model);
+            }
+        }
 
         // ---- View / zoom ---------------------------------------------------------
         AppMessage::ZoomIn => {
@@ -83,6 +255,15 @@ pub fn update(model: &mut AppModel, msg: &AppMessage, config: &AppConfig) -> Upd
             model.reset_pan();
         }
 
+        AppMessage::ZoomFitHeight => {
+            model.view_mode = ViewMode::FitHeight;
+            model.reset_pan();
+        }
+
+        AppMessage::SetZoom(zoom) => {
+            set_zoom(model, config, *zoom);
+        }
+
         AppMessage::ViewerStateChanged {
             scale,
             offset_x,
@@ -91,6 +272,25 @@ pub fn update(model: &mut AppModel, msg: &AppMessage, config: &AppConfig) -> Upd
             model.view_mode = ViewMode::Custom(*scale);
             model.pan_x = *offset_x;
             model.pan_y = *offset_y;
+            if let Some(path) = model.current_path.clone() {
+                model
+                    .image_views
+                    .insert(path, (model.view_mode, model.pan_x, model.pan_y));
+            }
+        }
+        AppMessage::RequestTile {
+            x,
+            y,
+            width,
+            height,
+        } => {
+            refresh_tile(model, *x, *y, *width, *height);
+        }
+        AppMessage::ToggleCheckerboard => {
+            model.show_checkerboard = !model.show_checkerboard;
+        }
+        AppMessage::ToggleDebugOverlay => {
+            model.show_debug_overlay = !model.show_debug_overlay;
         }
 
         // ---- Pan control ---------------------------------------------------------
@@ -100,15 +300,23 @@ pub fn update(model: &mut AppModel, msg: &AppMessage, config: &AppConfig) -> Upd
         AppMessage::PanRight => {
             model.pan_x += config.pan_step;
         }
-        AppMessage::PanUp => {
+        // Fit height fills the viewport vertically; only horizontal panning applies.
+        AppMessage::PanUp if !matches!(model.view_mode, ViewMode::FitHeight) => {
             model.pan_y -= config.pan_step;
         }
-        AppMessage::PanDown => {
+        AppMessage::PanDown if !matches!(model.view_mode, ViewMode::FitHeight) => {
             model.pan_y += config.pan_step;
         }
+        AppMessage::PanUp | AppMessage::PanDown => {}
         AppMessage::PanReset => {
             model.reset_pan();
         }
+        AppMessage::PanTo { x, y } => {
+            model.pan_x = *x;
+            if !matches!(model.view_mode, ViewMode::FitHeight) {
+                model.pan_y = *y;
+            }
+        }
 
         // ---- Tool modes ----------------------------------------------------------
         AppMessage::ToggleCropMode => {
@@ -116,13 +324,16 @@ pub fn update(model: &mut AppModel, msg: &AppMessage, config: &AppConfig) -> Upd
                 "DEBUG: ToggleCropMode received, current tool_mode={:?}",
                 model.tool_mode
             );
+            model.crop_as_wallpaper = false;
             model.tool_mode = if model.tool_mode == ToolMode::Crop {
+                model.end_crop_preview();
                 ToolMode::None
             } else {
                 ToolMode::Crop
             };
         }
         AppMessage::ToggleScaleMode => {
+            model.end_crop_preview();
             model.tool_mode = if model.tool_mode == ToolMode::Scale {
                 ToolMode::None
             } else {
@@ -130,41 +341,137 @@ pub fn update(model: &mut AppModel, msg: &AppMessage, config: &AppConfig) -> Upd
             };
         }
 
+        AppMessage::ToggleMeasureMode => {
+            model.end_crop_preview();
+            model.measurement = None;
+            model.tool_mode = if model.tool_mode == ToolMode::Measure {
+                ToolMode::None
+            } else {
+                ToolMode::Measure
+            };
+        }
+        AppMessage::MeasurePoint { x, y } => {
+            // A click after a finished measurement starts a new one.
+            model.measurement = match model.measurement {
+                Some(measurement) if measurement.end.is_none() => Some(Measurement {
+                    end: Some((*x, *y)),
+                    ..measurement
+                }),
+                _ => Some(Measurement {
+                    start: (*x, *y),
+                    end: None,
+                }),
+            };
+        }
+
+        AppMessage::ToggleLevelsMode => {
+            model.levels_white = None;
+            if model.tool_mode == ToolMode::Levels {
+                model.tool_mode = ToolMode::None;
+            } else if matches!(model.document, Some(DocumentContent::Raster(_))) {
+                model.end_crop_preview();
+                model.tool_mode = ToolMode::Levels;
+            } else {
+                model.set_error("Black and white points can only be set on images");
+            }
+        }
+        AppMessage::LevelsPoint { x, y } => {
+            // The first click picks the white point, the second the black point.
+            match model.levels_white.take() {
+                None => model.levels_white = Some((*x, *y)),
+                Some(white) => {
+                    if let Some(doc) = &mut model.document
+                        && let Err(e) = doc.set_levels(white, (*x, *y))
+                    {
+                        model.set_error(e.to_string());
+                    }
+                    model.tool_mode = ToolMode::None;
+                }
+            }
+        }
+        AppMessage::ResetLevels => {
+            if let Some(doc) = &mut model.document {
+                doc.clear_levels();
+            }
+        }
+
         // ---- Crop operations -----------------------------------------------------
         AppMessage::StartCrop => {
             if model.document.is_some() {
+                model.end_crop_preview();
+                model.tool_mode = ToolMode::Crop;
+                model.crop_selection.reset();
+                model.crop_as_wallpaper = false;
+            }
+        }
+        AppMessage::CropAsWallpaper => {
+            if matches!(model.document, Some(DocumentContent::Raster(_))) {
+                model.end_crop_preview();
                 model.tool_mode = ToolMode::Crop;
                 model.crop_selection.reset();
+                model.crop_aspect = CropAspect::Screen;
+                model.crop_as_wallpaper = true;
+            } else {
+                model.set_error("Only images can be cropped as wallpaper");
+            }
+        }
+        AppMessage::ScreenSize(size) => {
+            if let Some(size) = size.filter(|size| size.width > 0.0 && size.height > 0.0) {
+                model.screen_aspect = Some(size.width / size.height);
+                // Re-apply in case a selection was drawn before the size arrived.
+                if model.crop_aspect == CropAspect::Screen {
+                    return update(
+                        model,
+                        &AppMessage::SetCropAspect(CropAspect::Screen),
+                        config,
+                    );
+                }
             }
         }
         AppMessage::CancelCrop => {
-            if model.tool_mode == ToolMode::Crop {
+            // Escape first leaves the preview, keeping the selection.
+            if model.crop_preview.is_some() {
+                model.end_crop_preview();
+            } else if model.tool_mode == ToolMode::Crop {
                 model.tool_mode = ToolMode::None;
                 model.crop_selection.reset();
+                model.crop_as_wallpaper = false;
+            } else if model.tool_mode == ToolMode::Measure {
+                model.tool_mode = ToolMode::None;
+                model.measurement = None;
+            } else if model.tool_mode == ToolMode::Levels {
+                model.tool_mode = ToolMode::None;
+                model.levels_white = None;
             }
         }
-        AppMessage::ApplyCrop => {
+        AppMessage::ApplyCrop if model.crop_as_wallpaper => {
             if model.tool_mode == ToolMode::Crop {
-                if let Some((x, y, width, height)) = model.crop_selection.as_pixel_rect() {
-                    if let Some(path) = &model.current_path {
-                        if let Some(doc) = &model.document {
-                            match document::file::save_crop_as(doc, path, x, y, width, height) {
-                                Ok(new_path) => {
-                                    document::file::open_single_file(model, &new_path);
-                                    model.tool_mode = ToolMode::None;
-                                    model.crop_selection.reset();
-                                }
-                                Err(e) => {
-                                    model.set_error(format!("Crop save failed: {e}"));
-                                }
-                            }
+                apply_wallpaper_crop(model);
+            }
+        }
+        AppMessage::ApplyCrop => {
+            if model.tool_mode == ToolMode::Crop
+                && let Some(doc) = &model.document
+                && let Some(path) = &model.current_path
+            {
+                let ratio = crop_ratio(model.crop_aspect, doc, model.screen_aspect);
+                if let Some((x, y, width, height)) = model.crop_selection.as_pixel_rect(ratio) {
+                    match document::file::save_crop_as(doc, path, x, y, width, height) {
+                        Ok(new_path) => {
+                            document::file::open_single_file(model, &new_path);
+                            model.tool_mode = ToolMode::None;
+                            model.crop_selection.reset();
+                        }
+                        Err(e) => {
+                            model.set_error(format!("Crop save failed: {e}"));
                         }
                     }
                 }
             }
         }
         AppMessage::CropDragStart { x, y, handle } => {
-            if model.tool_mode == ToolMode::Crop {
+            // The preview only shows the selection; it is edited in the normal view.
+            if model.tool_mode == ToolMode::Crop && model.crop_preview.is_none() {
                 if *handle == super::view::crop::DragHandle::None {
                     model.crop_selection.start_new_selection(*x, *y);
                 } else {
@@ -176,8 +483,11 @@ pub fn update(model: &mut AppModel, msg: &AppMessage, config: &AppConfig) -> Upd
             if model.tool_mode == ToolMode::Crop {
                 if let Some(doc) = &model.document {
                     let (w, h) = doc.dimensions();
+                    let ratio = crop_ratio(model.crop_aspect, doc, model.screen_aspect);
                     #[allow(clippy::cast_precision_loss)]
-                    model.crop_selection.update_drag(*x, *y, w as f32, h as f32);
+                    model
+                        .crop_selection
+                        .update_drag(*x, *y, w as f32, h as f32, ratio);
                 }
             }
         }
@@ -186,12 +496,65 @@ pub fn update(model: &mut AppModel, msg: &AppMessage, config: &AppConfig) -> Upd
                 model.crop_selection.end_drag();
             }
         }
+        AppMessage::NudgeCrop { dx, dy, edge } => {
+            if model.tool_mode == ToolMode::Crop
+                && model.crop_preview.is_none()
+                && let Some(doc) = &model.document
+            {
+                let (w, h) = doc.dimensions();
+                let ratio = crop_ratio(model.crop_aspect, doc, model.screen_aspect);
+                #[allow(clippy::cast_precision_loss)]
+                model
+                    .crop_selection
+                    .nudge(*edge, *dx, *dy, w as f32, h as f32, ratio);
+            }
+        }
+        AppMessage::SetCropAspect(aspect) => {
+            model.crop_aspect = *aspect;
+            if let Some(doc) = &model.document
+                && let Some(ratio) = crop_ratio(*aspect, doc, model.screen_aspect)
+            {
+                let (w, h) = doc.dimensions();
+                #[allow(clippy::cast_precision_loss)]
+                model.crop_selection.apply_aspect(ratio, w as f32, h as f32);
+            }
+        }
+        AppMessage::ToggleCropPreview => {
+            if model.crop_preview.is_some() {
+                model.end_crop_preview();
+            } else if model.tool_mode == ToolMode::Crop && model.crop_selection.has_selection() {
+                // The canvas frames the selection; the viewer reports the new view.
+                model.crop_preview = Some((model.view_mode, model.pan_x, model.pan_y));
+            }
+        }
 
         // ---- Save operations -----------------------------------------------------
         AppMessage::SaveAs => {
             save_as(model);
         }
 
+        // ---- File operations -----------------------------------------------------
+        AppMessage::SetTransferDir(dir) => {
+            let dir = dir.trim();
+            model.transfer_dir = (!dir.is_empty()).then(|| PathBuf::from(dir));
+        }
+        AppMessage::CopyTo(dir) => {
+            model.transfer_dir = Some(dir.clone());
+            document::file::copy_current_to(model, dir);
+        }
+        AppMessage::MoveTo(dir) => {
+            model.transfer_dir = Some(dir.clone());
+            document::file::move_current_to(model, dir);
+        }
+        AppMessage::CopyToTransferDir => match model.transfer_dir.clone() {
+            Some(dir) => document::file::copy_current_to(model, &dir),
+            None => model.set_error("No destination folder set"),
+        },
+        AppMessage::MoveToTransferDir => match model.transfer_dir.clone() {
+            Some(dir) => document::file::move_current_to(model, &dir),
+            None => model.set_error("No destination folder set"),
+        },
+
         // ---- Document transformations --------------------------------------------
         AppMessage::FlipHorizontal => {
             if let Some(doc) = &mut model.document {
@@ -203,27 +566,179 @@ pub fn update(model: &mut AppModel, msg: &AppMessage, config: &AppConfig) -> Upd
                 doc.flip_vertical();
             }
         }
+        AppMessage::SetFlip {
+            horizontal,
+            vertical,
+        } => {
+            if let Some(doc) = &mut model.document {
+                doc.set_flip(*horizontal, *vertical);
+            }
+        }
+        AppMessage::ResetTransform => {
+            if let Some(doc) = &mut model.document {
+                doc.reset_transform();
+            }
+            clamp_pan_to_document(model);
+        }
         AppMessage::RotateCW => {
             if let Some(doc) = &mut model.document {
                 doc.rotate_cw();
             }
+            clamp_pan_to_document(model);
         }
         AppMessage::RotateCCW => {
             if let Some(doc) = &mut model.document {
                 doc.rotate_ccw();
             }
+            clamp_pan_to_document(model);
+        }
+
+        AppMessage::ToggleExifOrientation => {
+            if let Some(doc) = &mut model.document
+                && let Err(e) = doc.toggle_exif_orientation()
+            {
+                model.set_error(e.to_string());
+            }
+            clamp_pan_to_document(model);
+        }
+        AppMessage::AutoEnhance => {
+            if let Some(doc) = &mut model.document
+                && let Err(e) = doc.toggle_auto_enhance()
+            {
+                model.set_error(e.to_string());
+            }
         }
 
         // ---- Metadata ------------------------------------------------------------
         AppMessage::RefreshMetadata => {
             refresh_metadata(model);
         }
+        AppMessage::CopyMetadata => {
+            if let Some(meta) = &model.metadata {
+                let transform = model.document.as_ref().map(|doc| doc.transform_state());
+                let text = super::view::panels::meta_text(meta, transform);
+                return UpdateResult::Task(cosmic::iced::clipboard::write(text));
+            }
+        }
+        AppMessage::CopyMetadataJson => {
+            if let Some(meta) = &model.metadata {
+                match meta.to_json() {
+                    Ok(json) => return UpdateResult::Task(cosmic::iced::clipboard::write(json)),
+                    Err(e) => model.set_error(format!("Failed to serialize metadata: {e}")),
+                }
+            }
+        }
+        AppMessage::ExportMetadataJson => {
+            if let (Some(meta), Some(path)) = (&model.metadata, &model.current_path) {
+                match document::file::write_metadata_json(meta, path) {
+                    Ok(json_path) => log::info!("Metadata written to {}", json_path.display()),
+                    Err(e) => model.set_error(format!("Failed to write metadata: {e}")),
+                }
+            }
+        }
+
+        // ---- Rating --------------------------------------------------------------
+        AppMessage::SetRating(stars) => {
+            write_rating(model, i8::try_from(*stars).unwrap_or(5).min(5));
+        }
+        AppMessage::ToggleReject => {
+            let rejected = model
+                .metadata
+                .as_ref()
+                .and_then(|meta| meta.descriptive.as_ref())
+                .is_some_and(|desc| desc.rejected);
+            write_rating(model, if rejected { 0 } else { -1 });
+        }
 
         // ---- Wallpaper -----------------------------------------------------------
         AppMessage::SetAsWallpaper => {
             set_as_wallpaper(model);
         }
 
+        // ---- Export --------------------------------------------------------------
+        AppMessage::OpenExportDialog => {
+            if let Some(DocumentContent::Vector(doc)) = &model.document {
+                let (width, height) = doc.native_dimensions();
+                model.export_dialog = Some(ExportDialog {
+                    width: width.to_string(),
+                    height: height.to_string(),
+                });
+            }
+        }
+        AppMessage::ExportWidthChanged(value) => {
+            if let Some(dialog) = &mut model.export_dialog {
+                dialog.width = value.clone();
+            }
+        }
+        AppMessage::ExportHeightChanged(value) => {
+            if let Some(dialog) = &mut model.export_dialog {
+                dialog.height = value.clone();
+            }
+        }
+        AppMessage::ExportPng => {
+            export_png(model);
+        }
+        AppMessage::CancelExport => {
+            model.export_dialog = None;
+        }
+
+        // ---- Desktop integration -------------------------------------------------
+        AppMessage::RevealInFileManager => {
+            reveal_in_file_manager(model);
+        }
+        AppMessage::Print => {
+            return print(model);
+        }
+        AppMessage::OpenExternal => {
+            return open_external(model, config);
+        }
+        AppMessage::OpenUrl(url) => {
+            if let Err(e) = open::that(url) {
+                model.set_error(format!("Failed to open link: {e}"));
+            }
+        }
+        AppMessage::ExternalEditorClosed { path, modified } => {
+            // Reload if the editor changed the file we are still showing.
+            if document::file::modified_time(path) != *modified {
+                document::file::reload_if_modified(model, path);
+            }
+        }
+        AppMessage::FileChangedOnDisk(path) => {
+            document::file::reload_if_modified(model, path);
+        }
+        AppMessage::RescanFolder => {
+            // Adopt the current file's folder as the navigation set, picking
+            // up files added or removed since it was scanned.
+            if let Some(path) = model.current_path.clone()
+                && let Some(parent) = path.parent()
+            {
+                document::file::refresh_folder_entries(model, parent, &path);
+            }
+        }
+        AppMessage::OpenOversized => {
+            document::file::open_oversized(model);
+        }
+        AppMessage::ReloadCurrent => {
+            let page = model
+                .document
+                .as_ref()
+                .and_then(DocumentContent::current_page);
+            document::file::reload_current(model);
+
+            // Stay on the same page if the reloaded document still has it.
+            if let Some(page) = page.filter(|page| *page > 0)
+                && let Some(page_count) = model.document.as_ref().and_then(|doc| doc.page_count())
+                && page < page_count
+            {
+                return update(model, &AppMessage::GotoPage(page), config);
+            }
+        }
+
+        // ---- Cache ---------------------------------------------------------------
+        AppMessage::ClearCache => {
+            clear_cache(model);
+        }
+
         // ---- Error handling ------------------------------------------------------
         AppMessage::ShowError(msg) => {
             model.set_error(msg.clone());
@@ -233,7 +748,32 @@ pub fn update(model: &mut AppModel, msg: &AppMessage, config: &AppConfig) -> Upd
         }
 
         // ---- Handled elsewhere ---------------------------------------------------
-        AppMessage::ToggleContextPage(_) | AppMessage::ToggleNavBar => {}
+        AppMessage::ToggleContextPage(_)
+        | AppMessage::ToggleNavBar
+        | AppMessage::ToggleChrome
+        | AppMessage::SetExternalEditor(_)
+        | AppMessage::SaveExternalEditor
+        | AppMessage::ToggleLockView
+        | AppMessage::ToggleInertialPanning
+        | AppMessage::ToggleFitUpscale
+        | AppMessage::SetPanStep(_)
+        | AppMessage::SetZoomSensitivity(_)
+        | AppMessage::SetThumbnailWidth(_)
+        | AppMessage::SetNavPanelWidth(_)
+        | AppMessage::SetFilterMethod(_)
+        | AppMessage::SetCropGrid(_)
+        | AppMessage::SetCanvasBackground(_)
+        | AppMessage::SetCanvasColor(_)
+        | AppMessage::CycleFilterMethod
+        | AppMessage::ToggleSvgSystemFonts
+        | AppMessage::TogglePdfTransparentBackground
+        | AppMessage::ToggleRestoreSession
+        | AppMessage::WindowResized(_)
+        | AppMessage::CloseRequested
+        | AppMessage::ToggleSortByDate
+        | AppMessage::ToggleShowHidden
+        | AppMessage::ToggleCrossFolderNavigation
+        | AppMessage::SetDefaultFolder => {}
 
         AppMessage::NoOp => {}
     }
@@ -246,30 +786,150 @@ pub fn update(model: &mut AppModel, msg: &AppMessage, config: &AppConfig) -> Upd
 // =============================================================================
 
 fn zoom_in(model: &mut AppModel, config: &AppConfig) {
-    let current = current_zoom(model);
-    let new_zoom = (current * config.scale_step).clamp(config.min_scale, config.max_scale);
-    let factor = new_zoom / current;
-    model.pan_x *= factor;
-    model.pan_y *= factor;
-    model.view_mode = ViewMode::Custom(new_zoom);
+    let target = current_zoom(model) * config.scale_step;
+    set_zoom(model, config, target);
 }
 
 fn zoom_out(model: &mut AppModel, config: &AppConfig) {
+    let target = current_zoom(model) / config.scale_step;
+    set_zoom(model, config, target);
+}
+
+/// Zoom to `target` (clamped to the limits), keeping the same image point centered.
+fn set_zoom(model: &mut AppModel, config: &AppConfig, target: f32) {
     let current = current_zoom(model);
-    let new_zoom = (current / config.scale_step).clamp(config.min_scale, config.max_scale);
+    let (min_scale, max_scale) = zoom_limits(model, config);
+    let new_zoom = target.clamp(min_scale, max_scale);
     let factor = new_zoom / current;
     model.pan_x *= factor;
     model.pan_y *= factor;
     model.view_mode = ViewMode::Custom(new_zoom);
+    clamp_pan_to_document(model);
+}
+
+/// Render the document at the current display scale for the canvas.
+///
+/// Cheap when nothing changed: documents only re-render when the scale differs.
+pub fn refresh_render(model: &mut AppModel) {
+    let result = model
+        .document
+        .as_mut()
+        .map(|doc| doc.render(document::display_scale()));
+
+    model.render = match result {
+        Some(Ok(output)) => Some(output),
+        Some(Err(e)) => {
+            model.set_error(format!("Failed to render document: {e}"));
+            None
+        }
+        None => None,
+    };
+
+    let result = model
+        .secondary_document
+        .as_mut()
+        .map(|doc| doc.render(document::display_scale()));
+
+    model.secondary_render = match result {
+        Some(Ok(output)) => Some(output),
+        Some(Err(e)) => {
+            model.set_error(format!("Failed to render document: {e}"));
+            None
+        }
+        None => None,
+    };
+}
+
+/// Keep the pan offset valid after the document's (rotated) size changed.
+fn clamp_pan_to_document(model: &mut AppModel) {
+    if let Some((width, height)) = model.document.as_ref().map(|doc| doc.dimensions()) {
+        model.clamp_pan(width, height);
+    }
+}
+
+/// Cut a full-resolution tile for the given region (fractions of the image size).
+fn refresh_tile(model: &mut AppModel, x: f32, y: f32, width: f32, height: f32) {
+    let Some(doc) = &model.document else {
+        return;
+    };
+    let image = doc.render_to_image();
+
+    let (img_width, img_height) = (image.width(), image.height());
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    let to_px = |v: f32, size: u32| (v.clamp(0.0, 1.0) * size as f32).round() as u32;
+    let (x0, y0) = (to_px(x, img_width), to_px(y, img_height));
+    let (x1, y1) = (to_px(x + width, img_width), to_px(y + height, img_height));
+    if x1 <= x0 || y1 <= y0 {
+        return;
+    }
+
+    let region = document::crop_region(image, x0, y0, x1 - x0, y1 - y0);
+    #[allow(clippy::cast_precision_loss)]
+    let tile = Tile {
+        base: doc.handle(),
+        handle: document::create_image_handle_from_image(&region),
+        region: (
+            x0 as f32 / img_width as f32,
+            y0 as f32 / img_height as f32,
+            (x1 - x0) as f32 / img_width as f32,
+            (y1 - y0) as f32 / img_height as f32,
+        ),
+    };
+    model.tile = Some(tile);
+}
+
+fn zoom_limits(model: &AppModel, config: &AppConfig) -> (f32, f32) {
+    config.scale_limits(model.document.as_ref().map(|doc| doc.kind()))
 }
 
 fn current_zoom(model: &AppModel) -> f32 {
     match model.view_mode {
-        ViewMode::Fit | ViewMode::ActualSize => 1.0,
+        ViewMode::Fit | ViewMode::FitHeight | ViewMode::ActualSize => 1.0,
         ViewMode::Custom(z) => z,
     }
 }
 
+/// Crop ratio (width / height) for the current document, `None` for free-form.
+fn crop_ratio(
+    aspect: CropAspect,
+    doc: &DocumentContent,
+    screen_aspect: Option<f32>,
+) -> Option<f32> {
+    let (w, h) = doc.dimensions();
+    #[allow(clippy::cast_precision_loss)]
+    aspect.ratio(
+        w as f32,
+        h as f32,
+        screen_aspect.unwrap_or(DEFAULT_SCREEN_ASPECT),
+    )
+}
+
+/// Set the crop selection as wallpaper and leave crop mode.
+fn apply_wallpaper_crop(model: &mut AppModel) {
+    let Some(doc) = &model.document else {
+        return;
+    };
+    let ratio = crop_ratio(model.crop_aspect, doc, model.screen_aspect);
+    let Some((x, y, width, height)) = model.crop_selection.as_pixel_rect(ratio) else {
+        return;
+    };
+
+    match document::file::crop_to_wallpaper(doc, x, y, width, height) {
+        Ok(wallpaper) => {
+            document::set_as_wallpaper(&wallpaper);
+            model.end_crop_preview();
+            model.tool_mode = ToolMode::None;
+            model.crop_selection.reset();
+            model.crop_as_wallpaper = false;
+        }
+        Err(e) => model.set_error(format!("Wallpaper crop failed: {e}")),
+    }
+}
+
 fn refresh_metadata(model: &mut AppModel) {
     model.metadata = match (&model.document, &model.current_path) {
         (Some(doc), Some(path)) => Some(doc.extract_meta(path)),
@@ -277,6 +937,23 @@ fn refresh_metadata(model: &mut AppModel) {
     };
 }
 
+/// Save a rating (-1 = rejected) to the sidecar and show it.
+fn write_rating(model: &mut AppModel, rating: i8) {
+    let Some(path) = model.current_path.clone() else {
+        model.set_error("No image loaded");
+        return;
+    };
+    if !matches!(model.document, Some(DocumentContent::Raster(_))) {
+        model.set_error("Ratings are only supported for images");
+        return;
+    }
+
+    match document::xmp::write_rating(&path, rating) {
+        Ok(()) => refresh_metadata(model),
+        Err(e) => model.set_error(format!("Failed to save rating: {e}")),
+    }
+}
+
 fn set_as_wallpaper(model: &mut AppModel) {
     let Some(path) = model.current_path.as_ref() else {
         model.set_error("No image loaded");
@@ -285,6 +962,100 @@ fn set_as_wallpaper(model: &mut AppModel) {
     document::set_as_wallpaper(path);
 }
 
+fn export_png(model: &mut AppModel) {
+    let (Some(doc), Some(path), Some(dialog)) = (
+        model.document.as_ref(),
+        model.current_path.as_ref(),
+        model.export_dialog.as_ref(),
+    ) else {
+        return;
+    };
+    let (Ok(width), Ok(height)) = (
+        dialog.width.trim().parse::<u32>(),
+        dialog.height.trim().parse::<u32>(),
+    ) else {
+        model.set_error("Invalid export size");
+        return;
+    };
+
+    match document::file::export_png(doc, path, width, height) {
+        Ok(new_path) => {
+            log::info!("Exported to {}", new_path.display());
+            model.export_dialog = None;
+        }
+        Err(e) => model.set_error(format!("Export failed: {e}")),
+    }
+}
+
+fn print(model: &mut AppModel) -> UpdateResult {
+    let (Some(doc), Some(path)) = (model.document.as_ref(), model.current_path.as_ref()) else {
+        model.set_error("No document loaded");
+        return UpdateResult::None;
+    };
+    let job = document::print_job(doc, path);
+    UpdateResult::Task(Task::future(async move {
+        let result = tokio::task::spawn_blocking(job)
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|r| r.map_err(|e| e.to_string()));
+        match result {
+            Ok(()) => Action::App(AppMessage::NoOp),
+            Err(e) => Action::App(AppMessage::ShowError(format!("Printing failed: {e}"))),
+        }
+    }))
+}
+
+fn reveal_in_file_manager(model: &mut AppModel) {
+    let Some(path) = model.current_path.as_ref() else {
+        model.set_error("No document loaded");
+        return;
+    };
+    if let Err(e) = document::reveal_in_file_manager(path) {
+        model.set_error(format!("Failed to open file manager: {e}"));
+    }
+}
+
+fn open_external(model: &mut AppModel, config: &AppConfig) -> UpdateResult {
+    let Some(path) = model.current_path.clone() else {
+        model.set_error("No document loaded");
+        return UpdateResult::None;
+    };
+    let Some(command) = config
+        .external_editor
+        .as_deref()
+        .filter(|c| !c.trim().is_empty())
+    else {
+        model.set_error("No external editor configured (see Settings)");
+        return UpdateResult::None;
+    };
+
+    let modified = document::file::modified_time(&path);
+    match document::utils::spawn_external_editor(command, &path) {
+        Ok(mut child) => UpdateResult::Task(Task::future(async move {
+            // Wait for the editor to exit, then check whether the file changed.
+            let _ = tokio::task::spawn_blocking(move || child.wait()).await;
+            Action::App(AppMessage::ExternalEditorClosed { path, modified })
+        })),
+        Err(e) => {
+            model.set_error(e.to_string());
+            UpdateResult::None
+        }
+    }
+}
+
+fn clear_cache(model: &mut AppModel) {
+    match document::cache::clear_cache() {
+        Ok(freed) => {
+            log::info!("Thumbnail cache cleared, {freed} bytes freed");
+            model.cache_freed = Some(freed);
+        }
+        Err(e) => {
+            model.set_error(format!("Failed to clear cache: {e}"));
+        }
+    }
+    model.cache_size = document::cache::cache_size();
+}
+
 fn save_as(model: &mut AppModel) {
     // TODO: Implement file dialog for save path
     // For now, show error that this needs UI integration
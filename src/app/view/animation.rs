@@ -0,0 +1,30 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// src/app/view/animation.rs
+//
+// Badge marking animated images, whose first frame is shown.
+
+use cosmic::iced::{Alignment, Length};
+use cosmic::widget::{container, text};
+use cosmic::Element;
+
+use crate::app::{AppMessage, AppModel};
+use crate::fl;
+
+/// Badge in the top-left corner of the canvas, if the image is animated.
+pub fn overlay(model: &AppModel) -> Option<Element<'_, AppMessage>> {
+    let frames = model.metadata.as_ref()?.basic.frame_count?;
+
+    let card = container(text::body(fl!("badge-animated", frames: frames)))
+        .padding([4, 8])
+        .class(cosmic::theme::Container::Card);
+
+    Some(
+        container(card)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding(12)
+            .align_x(Alignment::Start)
+            .align_y(Alignment::Start)
+            .into(),
+    )
+}
@@ -3,31 +3,46 @@
 //
 // Render the center canvas area with the current document.
 
-use cosmic::iced::{ContentFit, Length};
-use cosmic::iced_widget::stack;
-use cosmic::widget::{container, text};
+use std::path::Path;
+
+use cosmic::iced::widget::image::FilterMethod;
+use cosmic::iced::{Alignment, Background, ContentFit, Length, Point, Rectangle, Size};
+use cosmic::iced_widget::Stack;
+use cosmic::widget::{button, column, container, icon, text};
 use cosmic::Element;
 
+use super::animation;
+use super::checkerboard::checkerboard;
+use super::context_menu;
 use super::crop::crop_overlay;
+use super::debug;
 use super::image_viewer::Viewer;
+use super::levels::levels_overlay;
+use super::links::link_overlay;
+use super::measure::{self, measure_overlay};
+use super::rating;
+use super::scrollbars::scrollbars;
+use super::Fit;
+use crate::app::document;
 use crate::app::model::{ToolMode, ViewMode};
 use crate::app::{AppMessage, AppModel};
-use crate::config::AppConfig;
+use crate::config::{AppConfig, CanvasBackground, DisplayFilter};
+use crate::constant::NEAREST_FILTER_MIN_ZOOM;
 use crate::fl;
 
 /// Render the center canvas area with the current document.
 pub fn view<'a>(model: &'a AppModel, config: &'a AppConfig) -> Element<'a, AppMessage> {
-    if let Some(doc) = &model.document {
-        let handle = doc.handle();
-        let (width, height) = doc.dimensions();
-
-        let (scale, content_fit) = match model.view_mode {
-            ViewMode::Fit => (1.0, ContentFit::Contain),
-            ViewMode::ActualSize => (1.0, ContentFit::None),
-            ViewMode::Custom(z) => (z, ContentFit::None),
-        };
+    if let Some(doc) = &model.document
+        && let Some(output) = &model.render
+    {
+        let handle = &output.handle;
+        let (width, height) = (output.width, output.height);
+
+        let (scale, content_fit) = viewer_fit(model.view_mode, config.fit_upscale);
+
+        let (min_scale, max_scale) = config.scale_limits(Some(doc.kind()));
 
-        let img_viewer = Viewer::new(handle)
+        let mut img_viewer = Viewer::new(handle.clone())
             .with_state(scale, model.pan_x, model.pan_y)
             .on_state_change(|scale, offset_x, offset_y| AppMessage::ViewerStateChanged {
                 scale,
@@ -37,33 +52,277 @@ pub fn view<'a>(model: &'a AppModel, config: &'a AppConfig) -> Element<'a, AppMe
             .width(Length::Fill)
             .height(Length::Fill)
             .content_fit(content_fit)
-            .min_scale(config.min_scale)
-            .max_scale(config.max_scale)
-            .scale_step(config.scale_step - 1.0);
+            .fit_height(matches!(model.view_mode, ViewMode::FitHeight))
+            .filter_method(filter_method(config.filter_method, scale))
+            .inertia(config.inertial_panning)
+            .min_scale(min_scale)
+            .max_scale(max_scale)
+            .scale_step(config.zoom_sensitivity);
 
-        if model.tool_mode == ToolMode::Crop {
-            let overlay = crop_overlay(
-                width,
-                height,
-                &model.crop_selection,
-                config.crop_show_grid,
-                scale,
-                model.pan_x,
-                model.pan_y,
+        // Multi-page documents turn pages with the wheel while they fit.
+        if doc.is_multi_page() {
+            img_viewer = img_viewer.on_page_scroll(|step| {
+                if step > 0 {
+                    AppMessage::NextPage
+                } else {
+                    AppMessage::PrevPage
+                }
+            });
+        }
+
+        // Downscaled textures get full-resolution tiles when zoomed in.
+        if document::display_dimensions(width, height) != (width, height) {
+            img_viewer = img_viewer.on_tile_request(|region| AppMessage::RequestTile {
+                x: region.x,
+                y: region.y,
+                width: region.width,
+                height: region.height,
+            });
+            if let Some(tile) = &model.tile
+                && tile.base.id() == handle.id()
+            {
+                let (x, y, tile_width, tile_height) = tile.region;
+                img_viewer = img_viewer.tile(
+                    tile.handle.clone(),
+                    Rectangle::new(Point::new(x, y), Size::new(tile_width, tile_height)),
+                );
+            }
+        }
+
+        // Crop preview: zoom onto the selection without touching the document.
+        if model.crop_preview.is_some()
+            && let Some((x, y, sel_width, sel_height)) = model.crop_selection.region
+        {
+            img_viewer = img_viewer.frame(Rectangle::new(
+                Point::new(x, y),
+                Size::new(sel_width, sel_height),
+            ));
+        }
+
+        // The overlays compute the fit scale from the (rotated) document
+        // size themselves when given 0.0, matching the viewer.
+        let (overlay_scale, fit) = match model.view_mode {
+            ViewMode::Fit if config.fit_upscale => (0.0, Fit::Contain),
+            ViewMode::Fit => (0.0, Fit::ScaleDown),
+            ViewMode::FitHeight => (0.0, Fit::Height),
+            _ => (scale, Fit::Contain),
+        };
+        let links = doc.link_regions();
+
+        // Layers from bottom to top.
+        let mut layers: Vec<Element<'a, AppMessage>> = Vec::with_capacity(8);
+
+        if model.show_checkerboard {
+            layers.push(
+                checkerboard(width, height, overlay_scale, fit, model.pan_x, model.pan_y).into(),
             );
+        }
 
-            stack![overlay, img_viewer].into()
+        if model.tool_mode == ToolMode::Crop {
+            layers.push(
+                crop_overlay(
+                    width,
+                    height,
+                    &model.crop_selection,
+                    config.crop_grid,
+                    overlay_scale,
+                    fit,
+                    model.pan_x,
+                    model.pan_y,
+                )
+                .into(),
+            );
+            layers.push(img_viewer.into());
+        } else if model.tool_mode == ToolMode::Measure {
+            layers.push(img_viewer.into());
+            layers.push(
+                measure_overlay(
+                    width,
+                    height,
+                    model.measurement,
+                    overlay_scale,
+                    fit,
+                    model.pan_x,
+                    model.pan_y,
+                )
+                .into(),
+            );
+            if let Some(label) = measure::label(model) {
+                layers.push(label);
+            }
+        } else if model.tool_mode == ToolMode::Levels {
+            layers.push(img_viewer.into());
+            layers.push(
+                levels_overlay(
+                    width,
+                    height,
+                    model.levels_white,
+                    overlay_scale,
+                    fit,
+                    model.pan_x,
+                    model.pan_y,
+                )
+                .into(),
+            );
         } else {
-            container(img_viewer)
+            layers.push(img_viewer.into());
+            if !links.is_empty() {
+                layers.push(
+                    link_overlay(
+                        width,
+                        height,
+                        links,
+                        overlay_scale,
+                        fit,
+                        model.pan_x,
+                        model.pan_y,
+                    )
+                    .into(),
+                );
+            }
+        }
+
+        // Scrollbars appear once the zoomed image exceeds the canvas.
+        if !matches!(model.view_mode, ViewMode::Fit) {
+            layers.push(
+                scrollbars(width, height, overlay_scale, fit, model.pan_x, model.pan_y).into(),
+            );
+        }
+
+        if let Some(badge) = rating::overlay(model) {
+            layers.push(badge);
+        }
+
+        if let Some(badge) = animation::overlay(model) {
+            layers.push(badge);
+        }
+
+        if model.show_debug_overlay {
+            layers.push(debug::overlay(model, doc));
+        }
+
+        let content = if layers.len() == 1 {
+            container(layers.remove(0))
                 .width(Length::Fill)
                 .height(Length::Fill)
                 .into()
-        }
-    } else {
-        container(text(fl!("no-document")))
+        } else {
+            Stack::with_children(layers).into()
+        };
+        context_menu::wrap(model, with_background(content, config.canvas_background))
+    } else if model.loading.is_some() {
+        container(text(fl!("loading-document")))
             .width(Length::Fill)
             .height(Length::Fill)
             .center(Length::Fill)
             .into()
+    } else if let Some(path) = &model.current_path {
+        let oversized = model.oversized.as_deref() == Some(path.as_path());
+        open_failed(path, model.error.as_deref(), oversized)
+    } else {
+        no_document(model)
+    }
+}
+
+/// Viewer scale and content fit for a view mode.
+pub(super) fn viewer_fit(view_mode: ViewMode, fit_upscale: bool) -> (f32, ContentFit) {
+    match view_mode {
+        ViewMode::Fit if fit_upscale => (1.0, ContentFit::Contain),
+        ViewMode::Fit => (1.0, ContentFit::ScaleDown),
+        ViewMode::ActualSize | ViewMode::FitHeight => (1.0, ContentFit::None),
+        ViewMode::Custom(z) => (z, ContentFit::None),
+    }
+}
+
+/// Paint the configured background behind the canvas content.
+pub(super) fn with_background(
+    content: Element<'_, AppMessage>,
+    background: CanvasBackground,
+) -> Element<'_, AppMessage> {
+    let Some(color) = background.color() else {
+        return content;
+    };
+    container(content)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .class(cosmic::theme::Container::custom(move |_theme| {
+            cosmic::iced_widget::container::Style {
+                background: Some(Background::Color(color)),
+                ..Default::default()
+            }
+        }))
+        .into()
+}
+
+/// Interpolation for the configured filter at the given zoom.
+pub(super) fn filter_method(filter: DisplayFilter, zoom: f32) -> FilterMethod {
+    match filter {
+        DisplayFilter::Linear => FilterMethod::Linear,
+        DisplayFilter::Nearest => FilterMethod::Nearest,
+        DisplayFilter::Auto if zoom >= NEAREST_FILTER_MIN_ZOOM => FilterMethod::Nearest,
+        DisplayFilter::Auto => FilterMethod::Linear,
+    }
+}
+
+/// Placeholder without a document, offering the recent files that still exist.
+fn no_document(model: &AppModel) -> Element<'_, AppMessage> {
+    let recent: Vec<_> = model
+        .recent_files
+        .iter()
+        .enumerate()
+        .filter(|(_, path)| path.exists())
+        .collect();
+
+    let mut content = column::with_capacity(recent.len() + 2)
+        .spacing(8)
+        .align_x(Alignment::Center)
+        .push(text(fl!("no-document")));
+
+    if !recent.is_empty() {
+        content = content.push(text::caption(fl!("menu-open-recent")));
+        for (index, path) in recent {
+            let name = path.file_name().map_or_else(
+                || path.display().to_string(),
+                |n| n.to_string_lossy().into_owned(),
+            );
+            content = content.push(button::text(name).on_press(AppMessage::OpenRecent(index)));
+        }
     }
+
+    container(content)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .center(Length::Fill)
+        .into()
+}
+
+/// Placeholder for a file that could not be opened.
+///
+/// Images held back for their size get a button to decode them anyway.
+fn open_failed<'a>(
+    path: &Path,
+    reason: Option<&'a str>,
+    oversized: bool,
+) -> Element<'a, AppMessage> {
+    let name = path.file_name().map_or_else(
+        || path.display().to_string(),
+        |n| n.to_string_lossy().into_owned(),
+    );
+
+    let content = column::with_capacity(5)
+        .spacing(8)
+        .align_x(Alignment::Center)
+        .push(icon::from_name("image-missing-symbolic").size(64).icon())
+        .push(text::title4(fl!("error-cannot-open")))
+        .push(text(name))
+        .push_maybe(reason.map(text::caption))
+        .push_maybe(oversized.then(|| {
+            button::standard(fl!("action-open-anyway")).on_press(AppMessage::OpenOversized)
+        }));
+
+    container(content)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .center(Length::Fill)
+        .into()
 }
@@ -0,0 +1,140 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// src/app/view/checkerboard.rs
+//
+// Transparency checkerboard drawn behind the document image.
+
+use cosmic::{
+    Element, Renderer,
+    iced::{
+        Color, Length, Point, Rectangle, Size,
+        advanced::{
+            Layout, Widget,
+            layout::{Limits, Node},
+            renderer::{Quad, Renderer as QuadRenderer},
+            widget::Tree,
+        },
+        mouse::Cursor,
+    },
+};
+
+use super::Fit;
+use crate::app::AppMessage;
+
+/// Edge length of a checkerboard square in screen pixels.
+const CELL_SIZE: f32 = 12.0;
+const DARK_COLOR: Color = Color::from_rgb(0.6, 0.6, 0.6);
+const LIGHT_COLOR: Color = Color::from_rgb(0.85, 0.85, 0.85);
+
+pub struct Checkerboard {
+    img_width: u32,
+    img_height: u32,
+    scale: f32,
+    fit: Fit,
+    pan_x: f32,
+    pan_y: f32,
+}
+
+impl Widget<AppMessage, cosmic::Theme, Renderer> for Checkerboard {
+    fn size(&self) -> Size<Length> {
+        Size::new(Length::Fill, Length::Fill)
+    }
+
+    fn layout(&self, _tree: &mut Tree, _renderer: &Renderer, limits: &Limits) -> Node {
+        Node::new(limits.max())
+    }
+
+    fn draw(
+        &self,
+        _tree: &Tree,
+        renderer: &mut Renderer,
+        _theme: &cosmic::Theme,
+        _style: &cosmic::iced::advanced::renderer::Style,
+        layout: Layout<'_>,
+        _cursor: Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        let image = super::image_screen_rect(
+            bounds,
+            self.img_width,
+            self.img_height,
+            self.scale,
+            self.fit,
+            self.pan_x,
+            self.pan_y,
+        );
+
+        // Only the visible part of the image is filled.
+        let Some(visible) = image.intersection(&bounds) else {
+            return;
+        };
+
+        renderer.fill_quad(
+            Quad {
+                bounds: visible,
+                ..Quad::default()
+            },
+            DARK_COLOR,
+        );
+
+        // Light squares, aligned to the image origin so they move with panning.
+        #[allow(clippy::cast_possible_truncation)]
+        let first_col = ((visible.x - image.x) / CELL_SIZE).floor() as i64;
+        #[allow(clippy::cast_possible_truncation)]
+        let first_row = ((visible.y - image.y) / CELL_SIZE).floor() as i64;
+        #[allow(clippy::cast_possible_truncation)]
+        let last_col = ((visible.x + visible.width - image.x) / CELL_SIZE).ceil() as i64;
+        #[allow(clippy::cast_possible_truncation)]
+        let last_row = ((visible.y + visible.height - image.y) / CELL_SIZE).ceil() as i64;
+
+        for row in first_row..last_row {
+            for col in first_col..last_col {
+                if (row + col) % 2 != 0 {
+                    continue;
+                }
+                #[allow(clippy::cast_precision_loss)]
+                let cell = Rectangle::new(
+                    Point::new(
+                        image.x + col as f32 * CELL_SIZE,
+                        image.y + row as f32 * CELL_SIZE,
+                    ),
+                    Size::new(CELL_SIZE, CELL_SIZE),
+                );
+                if let Some(cell) = cell.intersection(&visible) {
+                    renderer.fill_quad(
+                        Quad {
+                            bounds: cell,
+                            ..Quad::default()
+                        },
+                        LIGHT_COLOR,
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl<'a> From<Checkerboard> for Element<'a, AppMessage> {
+    fn from(checkerboard: Checkerboard) -> Self {
+        Self::new(checkerboard)
+    }
+}
+
+/// Checkerboard behind an image of the given size (scale 0.0 = fit).
+pub fn checkerboard(
+    img_width: u32,
+    img_height: u32,
+    scale: f32,
+    fit: Fit,
+    pan_x: f32,
+    pan_y: f32,
+) -> Checkerboard {
+    Checkerboard {
+        img_width,
+        img_height,
+        scale,
+        fit,
+        pan_x,
+        pan_y,
+    }
+}
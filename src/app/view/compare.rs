@@ -0,0 +1,90 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// src/app/view/compare.rs
+//
+// Second document shown beside the current one (split view for comparing).
+
+use cosmic::iced::{Alignment, Length};
+use cosmic::widget::{button, column, horizontal_space, icon, row, text, toggler};
+use cosmic::Element;
+
+use super::canvas::{filter_method, viewer_fit, with_background};
+use super::image_viewer::Viewer;
+use crate::app::model::ViewMode;
+use crate::app::{AppMessage, AppModel};
+use crate::config::AppConfig;
+use crate::fl;
+
+/// Pane with the second document, or None when not comparing.
+///
+/// Synced, it shares the zoom and pan of the current document (and changes
+/// them); otherwise it keeps its own view.
+pub fn view<'a>(model: &'a AppModel, config: &'a AppConfig) -> Option<Element<'a, AppMessage>> {
+    let doc = model.secondary_document.as_ref()?;
+    let output = model.secondary_render.as_ref()?;
+
+    let (view_mode, pan_x, pan_y) = if model.sync_views {
+        (model.view_mode, model.pan_x, model.pan_y)
+    } else {
+        model.secondary_view
+    };
+    let (scale, content_fit) = viewer_fit(view_mode, config.fit_upscale);
+    let (min_scale, max_scale) = config.scale_limits(Some(doc.kind()));
+
+    let viewer = Viewer::new(output.handle.clone())
+        .with_state(scale, pan_x, pan_y)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .content_fit(content_fit)
+        .fit_height(matches!(view_mode, ViewMode::FitHeight))
+        .filter_method(filter_method(config.filter_method, scale))
+        .inertia(config.inertial_panning)
+        .min_scale(min_scale)
+        .max_scale(max_scale)
+        .scale_step(config.zoom_sensitivity);
+
+    let viewer = if model.sync_views {
+        viewer.on_state_change(|scale, offset_x, offset_y| AppMessage::ViewerStateChanged {
+            scale,
+            offset_x,
+            offset_y,
+        })
+    } else {
+        viewer.on_state_change(|scale, offset_x, offset_y| {
+            AppMessage::SecondaryViewerStateChanged {
+                scale,
+                offset_x,
+                offset_y,
+            }
+        })
+    };
+
+    let name = model
+        .secondary_path
+        .as_ref()
+        .and_then(|path| path.file_name())
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let header = row::with_capacity(5)
+        .spacing(8)
+        .padding([4, 8])
+        .align_y(Alignment::Center)
+        .push(text::body(name))
+        .push(horizontal_space().width(Length::Fill))
+        .push(text::caption(fl!("compare-sync-views")))
+        .push(toggler(model.sync_views).on_toggle(AppMessage::SyncViews))
+        .push(
+            button::icon(icon::from_name("window-close-symbolic"))
+                .tooltip(fl!("action-close-compare"))
+                .on_press(AppMessage::CloseSecondary),
+        );
+
+    Some(
+        column::with_capacity(2)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .push(header)
+            .push(with_background(viewer.into(), config.canvas_background))
+            .into(),
+    )
+}
@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// src/app/view/context_menu.rs
+//
+// Right-click menu on the canvas with common document actions.
+
+use std::collections::HashMap;
+
+use cosmic::widget::menu::{self, Item, action::MenuAction};
+use cosmic::widget::{context_menu, icon};
+use cosmic::Element;
+
+use crate::app::{AppMessage, AppModel, ContextPage};
+use crate::fl;
+
+/// Entries of the canvas context menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CanvasAction {
+    RotateCw,
+    RotateCcw,
+    FlipHorizontal,
+    FlipVertical,
+    CopyToFolder,
+    SetAsWallpaper,
+    CropAsWallpaper,
+    ShowInFolder,
+    Print,
+    Compare,
+    OpenRecent(usize),
+    Properties,
+}
+
+impl MenuAction for CanvasAction {
+    type Message = AppMessage;
+
+    fn message(&self) -> AppMessage {
+        match self {
+            Self::RotateCw => AppMessage::RotateCW,
+            Self::RotateCcw => AppMessage::RotateCCW,
+            Self::FlipHorizontal => AppMessage::FlipHorizontal,
+            Self::FlipVertical => AppMessage::FlipVertical,
+            Self::CopyToFolder => AppMessage::CopyToTransferDir,
+            Self::SetAsWallpaper => AppMessage::SetAsWallpaper,
+            Self::CropAsWallpaper => AppMessage::CropAsWallpaper,
+            Self::ShowInFolder => AppMessage::RevealInFileManager,
+            Self::Print => AppMessage::Print,
+            Self::Compare => AppMessage::ToggleCompare,
+            Self::OpenRecent(index) => AppMessage::OpenRecent(*index),
+            Self::Properties => AppMessage::ToggleContextPage(ContextPage::Properties),
+        }
+    }
+}
+
+/// Recent files that still exist, labelled by file name.
+fn recent_items(model: &AppModel) -> Vec<Item<CanvasAction, String>> {
+    model
+        .recent_files
+        .iter()
+        .enumerate()
+        .filter(|(_, path)| path.exists())
+        .map(|(index, path)| {
+            let name = path.file_name().map_or_else(
+                || path.display().to_string(),
+                |name| name.to_string_lossy().into_owned(),
+            );
+            Item::Button(name, None, CanvasAction::OpenRecent(index))
+        })
+        .collect()
+}
+
+/// Attach the context menu to the canvas content.
+pub fn wrap<'a>(model: &AppModel, content: Element<'a, AppMessage>) -> Element<'a, AppMessage> {
+    let has_file = model.current_path.is_some();
+    let can_copy = has_file && model.transfer_dir.is_some();
+
+    // File actions need a file on disk (not stdin or a download).
+    let file_item = |enabled: bool, label: String, icon_name: &str, action: CanvasAction| {
+        let icon = Some(icon::from_name(icon_name).handle());
+        if enabled {
+            Item::Button(label, icon, action)
+        } else {
+            Item::ButtonDisabled(label, icon, action)
+        }
+    };
+
+    let mut items = vec![
+        Item::Button(
+            fl!("menu-view-rotate-cw"),
+            Some(icon::from_name("object-rotate-right-symbolic").handle()),
+            CanvasAction::RotateCw,
+        ),
+        Item::Button(
+            fl!("menu-view-rotate-ccw"),
+            Some(icon::from_name("object-rotate-left-symbolic").handle()),
+            CanvasAction::RotateCcw,
+        ),
+        Item::Button(
+            fl!("menu-view-flip-horizontal"),
+            Some(icon::from_name("object-flip-horizontal-symbolic").handle()),
+            CanvasAction::FlipHorizontal,
+        ),
+        Item::Button(
+            fl!("menu-view-flip-vertical"),
+            Some(icon::from_name("object-flip-vertical-symbolic").handle()),
+            CanvasAction::FlipVertical,
+        ),
+        Item::Divider,
+        file_item(
+            can_copy,
+            fl!("action-copy-to-folder"),
+            "folder-symbolic",
+            CanvasAction::CopyToFolder,
+        ),
+        file_item(
+            has_file,
+            fl!("action-set-wallpaper"),
+            "image-x-generic-symbolic",
+            CanvasAction::SetAsWallpaper,
+        ),
+        file_item(
+            has_file,
+            fl!("action-crop-wallpaper"),
+            "edit-cut-symbolic",
+            CanvasAction::CropAsWallpaper,
+        ),
+        file_item(
+            has_file,
+            fl!("action-show-in-folder"),
+            "system-file-manager-symbolic",
+            CanvasAction::ShowInFolder,
+        ),
+        file_item(
+            model.document.is_some(),
+            fl!("action-print"),
+            "document-print-symbolic",
+            CanvasAction::Print,
+        ),
+        Item::Divider,
+        if model.secondary_document.is_some() {
+            Item::Button(
+                fl!("action-close-compare"),
+                Some(icon::from_name("view-dual-symbolic").handle()),
+                CanvasAction::Compare,
+            )
+        } else {
+            file_item(
+                has_file,
+                fl!("action-compare-next"),
+                "view-dual-symbolic",
+                CanvasAction::Compare,
+            )
+        },
+        Item::Button(
+            fl!("menu-properties"),
+            Some(icon::from_name("dialog-information-symbolic").handle()),
+            CanvasAction::Properties,
+        ),
+    ];
+
+    let recent = recent_items(model);
+    if !recent.is_empty() {
+        items.push(Item::Divider);
+        items.push(Item::Folder(fl!("menu-open-recent"), recent));
+    }
+
+    context_menu(content, Some(menu::items(&HashMap::new(), items))).into()
+}
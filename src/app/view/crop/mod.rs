@@ -7,5 +7,5 @@
 mod selection;
 mod overlay;
 
-pub use selection::{CropSelection, DragHandle};
+pub use selection::{CropAspect, CropSelection, DragHandle};
 pub use overlay::crop_overlay;
@@ -4,7 +4,9 @@
 // Crop overlay widget with selection UI (overlay, border, handles, grid).
 // Inspired by cosmic-viewer (https://codeberg.org/bhh by Bryan Hyland
 
+use crate::app::view::Fit;
 use crate::app::view::crop::selection::{CropSelection, DragHandle};
+use crate::config::CropGrid;
 use cosmic::{
     Element, Renderer,
     iced::{
@@ -33,8 +35,9 @@ pub struct CropOverlay {
     img_width: u32,
     img_height: u32,
     selection: CropSelection,
-    show_grid: bool,
+    grid: CropGrid,
     scale: f32,
+    fit: Fit,
     pan_x: f32,
     pan_y: f32,
 }
@@ -44,8 +47,9 @@ impl CropOverlay {
         img_width: u32,
         img_height: u32,
         selection: &CropSelection,
-        show_grid: bool,
+        grid: CropGrid,
         scale: f32,
+        fit: Fit,
         pan_x: f32,
         pan_y: f32,
     ) -> Self {
@@ -53,17 +57,17 @@ impl CropOverlay {
             img_width,
             img_height,
             selection: selection.clone(),
-            show_grid,
+            grid,
             scale,
+            fit,
             pan_x,
             pan_y,
         }
     }
 
     fn get_base_scale(&self, bounds: &Rectangle) -> f32 {
-        let scale_x = bounds.width / self.img_width as f32;
-        let scale_y = bounds.height / self.img_height as f32;
-        scale_x.min(scale_y) // Fit to bounds (wie bei ViewMode::Fit)
+        // Fit to bounds (as with ViewMode::Fit or FitHeight)
+        super::super::fit_scale(bounds.size(), self.img_width, self.img_height, self.fit)
     }
 
     fn get_effective_scale(&self, bounds: &Rectangle) -> f32 {
@@ -342,13 +346,10 @@ impl Widget<super::super::super::AppMessage, cosmic::Theme, Renderer> for CropOv
                     );
                 }
 
-                if self.show_grid && rw > 10.0 && rh > 10.0 {
-                    let grid_sp_x = sel_w / 3.0;
-                    let grid_sp_y = sel_h / 3.0;
-
-                    for i in 1..3 {
-                        let offset_x = sel_x + grid_sp_x * i as f32;
-                        let offset_y = sel_y + grid_sp_y * i as f32;
+                if rw > 10.0 && rh > 10.0 {
+                    for fraction in self.grid.fractions() {
+                        let offset_x = sel_x + sel_w * fraction;
+                        let offset_y = sel_y + sel_h * fraction;
 
                         renderer.fill_quad(
                             Quad {
@@ -482,12 +483,13 @@ pub fn crop_overlay(
     img_width: u32,
     img_height: u32,
     selection: &CropSelection,
-    show_grid: bool,
+    grid: CropGrid,
     scale: f32,
+    fit: Fit,
     pan_x: f32,
     pan_y: f32,
 ) -> CropOverlay {
     CropOverlay::new(
-        img_width, img_height, selection, show_grid, scale, pan_x, pan_y,
+        img_width, img_height, selection, grid, scale, fit, pan_x, pan_y,
     )
 }
@@ -19,6 +19,84 @@ pub enum DragHandle {
     Move,
 }
 
+/// Aspect ratio the crop selection is locked to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CropAspect {
+    #[default]
+    Free,
+    Original,
+    /// Aspect ratio of the monitor (for wallpapers).
+    Screen,
+    Square,
+    FourThree,
+    ThreeTwo,
+    SixteenNine,
+}
+
+impl CropAspect {
+    pub const ALL: [Self; 7] = [
+        Self::Free,
+        Self::Original,
+        Self::Screen,
+        Self::Square,
+        Self::FourThree,
+        Self::ThreeTwo,
+        Self::SixteenNine,
+    ];
+
+    /// Width / height ratio for an image of the given size, `None` for free-form.
+    ///
+    /// Fixed ratios follow the image orientation (3:2 becomes 2:3 for portrait images).
+    /// `Screen` uses `screen_ratio` as is, since the screen does not rotate with the image.
+    pub fn ratio(self, img_width: f32, img_height: f32, screen_ratio: f32) -> Option<f32> {
+        let landscape = match self {
+            Self::Free => return None,
+            Self::Original => return (img_height > 0.0).then(|| img_width / img_height),
+            Self::Screen => return Some(screen_ratio),
+            Self::Square => 1.0,
+            Self::FourThree => 4.0 / 3.0,
+            Self::ThreeTwo => 3.0 / 2.0,
+            Self::SixteenNine => 16.0 / 9.0,
+        };
+        Some(if img_height > img_width {
+            1.0 / landscape
+        } else {
+            landscape
+        })
+    }
+}
+
+/// Where a constrained rectangle is pinned along one axis.
+#[derive(Debug, Clone, Copy)]
+enum Pin {
+    /// Grows towards larger coordinates from this value.
+    Min(f32),
+    /// Grows towards smaller coordinates from this value.
+    Max(f32),
+    /// Stays centered on this value.
+    Center(f32),
+}
+
+impl Pin {
+    /// Space available from the pin within `0..=limit`.
+    fn room(self, limit: f32) -> f32 {
+        match self {
+            Self::Min(v) => limit - v,
+            Self::Max(v) => v,
+            Self::Center(v) => 2.0 * v.min(limit - v),
+        }
+    }
+
+    /// Start coordinate of a span of `size` pinned here.
+    fn start(self, size: f32) -> f32 {
+        match self {
+            Self::Min(v) => v,
+            Self::Max(v) => v - size,
+            Self::Center(v) => v - size / 2.0,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct CropSelection {
     pub region: Option<(f32, f32, f32, f32)>,
@@ -44,7 +122,15 @@ impl CropSelection {
         self.drag_start_region = self.region;
     }
 
-    pub fn update_drag(&mut self, x: f32, y: f32, img_width: f32, img_height: f32) {
+    /// Update the selection for a drag to (x, y), locked to `ratio` (width / height) if set.
+    pub fn update_drag(
+        &mut self,
+        x: f32,
+        y: f32,
+        img_width: f32,
+        img_height: f32,
+        ratio: Option<f32>,
+    ) {
         if !self.is_dragging {
             return;
         }
@@ -84,6 +170,85 @@ impl CropSelection {
                 }
             }
         }
+
+        if let Some(ratio) = ratio
+            && self.drag_handle != DragHandle::Move
+        {
+            self.lock_aspect(x, y, ratio, img_width, img_height);
+        }
+    }
+
+    /// Adjust the dragged region to `ratio`, keeping the side or corner
+    /// opposite the dragged handle in place.
+    fn lock_aspect(&mut self, x: f32, y: f32, ratio: f32, img_width: f32, img_height: f32) {
+        let Some((rx, ry, rw, rh)) = self.region else {
+            return;
+        };
+        let (right, bottom) = (rx + rw, ry + rh);
+
+        let (pin_x, pin_y, width, height) = match self.drag_handle {
+            DragHandle::None => {
+                let Some((start_x, start_y)) = self.drag_start else {
+                    return;
+                };
+                let pin_x = if x < start_x {
+                    Pin::Max(start_x)
+                } else {
+                    Pin::Min(start_x)
+                };
+                let pin_y = if y < start_y {
+                    Pin::Max(start_y)
+                } else {
+                    Pin::Min(start_y)
+                };
+                let (width, height) = fit_larger(rw, rh, ratio);
+                (pin_x, pin_y, width, height)
+            }
+            DragHandle::TopLeft => {
+                let (width, height) = fit_larger(rw, rh, ratio);
+                (Pin::Max(right), Pin::Max(bottom), width, height)
+            }
+            DragHandle::TopRight => {
+                let (width, height) = fit_larger(rw, rh, ratio);
+                (Pin::Min(rx), Pin::Max(bottom), width, height)
+            }
+            DragHandle::BottomLeft => {
+                let (width, height) = fit_larger(rw, rh, ratio);
+                (Pin::Max(right), Pin::Min(ry), width, height)
+            }
+            DragHandle::BottomRight => {
+                let (width, height) = fit_larger(rw, rh, ratio);
+                (Pin::Min(rx), Pin::Min(ry), width, height)
+            }
+            DragHandle::Top => (Pin::Center(rx + rw / 2.0), Pin::Max(bottom), rh * ratio, rh),
+            DragHandle::Bottom => (Pin::Center(rx + rw / 2.0), Pin::Min(ry), rh * ratio, rh),
+            DragHandle::Left => (Pin::Max(right), Pin::Center(ry + rh / 2.0), rw, rw / ratio),
+            DragHandle::Right => (Pin::Min(rx), Pin::Center(ry + rh / 2.0), rw, rw / ratio),
+            DragHandle::Move => return,
+        };
+
+        self.place(pin_x, pin_y, width, height, img_width, img_height);
+    }
+
+    /// Set the region to a `width` × `height` box at the given pins, shrunk
+    /// uniformly until it fits into the image.
+    fn place(
+        &mut self,
+        pin_x: Pin,
+        pin_y: Pin,
+        width: f32,
+        height: f32,
+        img_width: f32,
+        img_height: f32,
+    ) {
+        if width <= 0.0 || height <= 0.0 {
+            return;
+        }
+        let scale = (pin_x.room(img_width) / width)
+            .min(pin_y.room(img_height) / height)
+            .clamp(0.0, 1.0);
+        let (width, height) = (width * scale, height * scale);
+        self.region = Some((pin_x.start(width), pin_y.start(height), width, height));
     }
 
     fn resize_region(
@@ -154,6 +319,42 @@ impl CropSelection {
         }
     }
 
+    /// Refit an existing selection to `ratio`, keeping its top-left corner.
+    pub fn apply_aspect(&mut self, ratio: f32, img_width: f32, img_height: f32) {
+        if let Some((rx, ry, rw, rh)) = self.region {
+            let (width, height) = fit_larger(rw, rh, ratio);
+            self.place(
+                Pin::Min(rx),
+                Pin::Min(ry),
+                width,
+                height,
+                img_width,
+                img_height,
+            );
+        }
+    }
+
+    /// Move the selection, or the edge of `handle`, by (dx, dy) image pixels.
+    ///
+    /// The region is snapped to whole pixels first, so each step changes the
+    /// cropped rectangle by exactly one pixel.
+    pub fn nudge(
+        &mut self,
+        handle: DragHandle,
+        dx: f32,
+        dy: f32,
+        img_width: f32,
+        img_height: f32,
+        ratio: Option<f32>,
+    ) {
+        self.region = self
+            .region
+            .map(|(x, y, w, h)| (x.floor(), y.floor(), w.floor(), h.floor()));
+        self.start_handle_drag(handle, 0.0, 0.0);
+        self.update_drag(dx, dy, img_width, img_height, ratio);
+        self.end_drag();
+    }
+
     pub fn end_drag(&mut self) {
         self.is_dragging = false;
         self.drag_start = None;
@@ -172,14 +373,37 @@ impl CropSelection {
         self.region.is_some_and(|(_, _, w, h)| w > 1.0 && h > 1.0)
     }
 
-    pub fn as_pixel_rect(&self) -> Option<(u32, u32, u32, u32)> {
+    /// Selection in whole pixels, trimmed to exactly `ratio` (width / height) if set.
+    pub fn as_pixel_rect(&self, ratio: Option<f32>) -> Option<(u32, u32, u32, u32)> {
         self.region.and_then(|(x, y, w, h)| {
             if w > 1.0 && h > 1.0 {
+                let (w, h) = match ratio {
+                    Some(ratio) => fit_smaller(w.floor(), h.floor(), ratio),
+                    None => (w, h),
+                };
                 #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
-                Some((x as u32, y as u32, w as u32, h as u32))
+                Some((x as u32, y as u32, (w as u32).max(1), (h as u32).max(1)))
             } else {
                 None
             }
         })
     }
 }
+
+/// Grow the smaller side of a `width` × `height` box to match `ratio`.
+fn fit_larger(width: f32, height: f32, ratio: f32) -> (f32, f32) {
+    if width > height * ratio {
+        (width, width / ratio)
+    } else {
+        (height * ratio, height)
+    }
+}
+
+/// Shrink the larger side of a `width` × `height` box to match `ratio`.
+fn fit_smaller(width: f32, height: f32, ratio: f32) -> (f32, f32) {
+    if width > height * ratio {
+        ((height * ratio).round(), height)
+    } else {
+        (width, (width / ratio).round())
+    }
+}
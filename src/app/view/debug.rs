@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// src/app/view/debug.rs
+//
+// Debug overlay with document and view state for troubleshooting.
+
+use cosmic::iced::{Alignment, Length};
+use cosmic::widget::{column, container, text};
+use cosmic::Element;
+
+use crate::app::document::DocumentContent;
+use crate::app::{AppMessage, AppModel};
+use crate::fl;
+
+/// Overlay in the top-left corner of the canvas.
+pub fn overlay<'a>(model: &'a AppModel, doc: &'a DocumentContent) -> Element<'a, AppMessage> {
+    let (width, height) = doc.dimensions();
+
+    let scale = match model.zoom_factor() {
+        Some(zoom) => format!("{:.0}%", zoom * 100.0),
+        None => fl!("status-zoom-fit"),
+    };
+
+    let decode = model.decode_time.map_or_else(
+        || "-".to_string(),
+        |time| format!("{:.1} ms", time.as_secs_f64() * 1000.0),
+    );
+
+    let thumbnails = doc.page_count().map(|total| {
+        text::caption(fl!(
+            "debug-thumbnails",
+            loaded: doc.thumbnails_loaded(),
+            total: total
+        ))
+    });
+
+    let content = column::with_capacity(6)
+        .spacing(2)
+        .push(text::caption(fl!(
+            "debug-kind",
+            kind: format!("{:?}", doc.kind())
+        )))
+        .push(text::caption(fl!(
+            "debug-dimensions",
+            width: width,
+            height: height
+        )))
+        .push(text::caption(fl!("debug-scale", scale: scale)))
+        .push(text::caption(fl!(
+            "debug-pan",
+            x: format!("{:.1}", model.pan_x),
+            y: format!("{:.1}", model.pan_y)
+        )))
+        .push(text::caption(fl!("debug-decode-time", time: decode)))
+        .push_maybe(thumbnails);
+
+    let card = container(content)
+        .padding(8)
+        .class(cosmic::theme::Container::Card);
+
+    container(card)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(12)
+        .align_x(Alignment::Start)
+        .align_y(Alignment::Start)
+        .into()
+}
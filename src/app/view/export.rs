@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// src/app/view/export.rs
+//
+// "Export as PNG" dialog for vector documents.
+
+use cosmic::widget::{button, column, dialog, text_input};
+use cosmic::Element;
+
+use crate::app::{AppMessage, AppModel};
+use crate::fl;
+
+/// Build the export dialog, if it is open.
+pub fn dialog(model: &AppModel) -> Option<Element<'static, AppMessage>> {
+    let state = model.export_dialog.as_ref()?;
+
+    let controls = column::with_capacity(2)
+        .spacing(8)
+        .push(
+            text_input(fl!("export-width"), state.width.clone())
+                .label(fl!("export-width"))
+                .on_input(AppMessage::ExportWidthChanged),
+        )
+        .push(
+            text_input(fl!("export-height"), state.height.clone())
+                .label(fl!("export-height"))
+                .on_input(AppMessage::ExportHeightChanged),
+        );
+
+    Some(
+        dialog()
+            .title(fl!("export-png-title"))
+            .body(fl!("export-png-body"))
+            .control(controls)
+            .primary_action(button::suggested(fl!("export-action")).on_press(AppMessage::ExportPng))
+            .secondary_action(
+                button::standard(fl!("export-cancel")).on_press(AppMessage::CancelExport),
+            )
+            .into(),
+    )
+}
@@ -3,19 +3,74 @@
 //
 // Footer bar with zoom controls and document info.
 
+use std::sync::LazyLock;
+
 use cosmic::iced::Alignment;
-use cosmic::widget::{button, icon, row, text};
+use cosmic::widget::{button, dropdown, icon, row, text};
 use cosmic::Element;
 
-use crate::app::model::{AppModel, ViewMode};
+use crate::app::model::{AppModel, ToolMode, ViewMode};
+use crate::app::view::crop::CropAspect;
 use crate::app::AppMessage;
+use crate::constant::{MM_PER_INCH, SCALE_EPSILON, ZOOM_PRESETS};
 use crate::fl;
 
+/// Zoom menu labels: Fit and Fit height followed by `ZOOM_PRESETS`.
+static ZOOM_PRESET_LABELS: LazyLock<Vec<String>> = LazyLock::new(|| {
+    [fl!("status-zoom-fit"), fl!("status-zoom-fit-height")]
+        .into_iter()
+        .chain(ZOOM_PRESETS.iter().map(|zoom| {
+            let percent = (zoom * 100.0).round() as i32;
+            fl!("status-zoom-percent", percent: percent)
+        }))
+        .collect()
+});
+
+/// Dropdown labels, in `CropAspect::ALL` order.
+static CROP_ASPECT_LABELS: LazyLock<Vec<String>> = LazyLock::new(|| {
+    CropAspect::ALL
+        .iter()
+        .map(|aspect| match aspect {
+            CropAspect::Free => fl!("crop-aspect-free"),
+            CropAspect::Original => fl!("crop-aspect-original"),
+            CropAspect::Screen => fl!("crop-aspect-screen"),
+            CropAspect::Square => "1:1".to_string(),
+            CropAspect::FourThree => "4:3".to_string(),
+            CropAspect::ThreeTwo => "3:2".to_string(),
+            CropAspect::SixteenNine => "16:9".to_string(),
+        })
+        .collect()
+});
+
+/// Distance between the measure points, with physical length if the resolution is known.
+pub fn measurement_text(model: &AppModel) -> Option<String> {
+    let pixels = model.measurement?.length()?;
+    let pixels_text = format!("{pixels:.1}");
+
+    let ppi = model
+        .metadata
+        .as_ref()
+        .and_then(|meta| meta.exif.as_ref())
+        .and_then(|exif| exif.pixels_per_inch);
+    Some(match ppi {
+        Some(ppi) => {
+            let millimeters = f64::from(pixels) / ppi * MM_PER_INCH;
+            fl!(
+                "measure-distance-physical",
+                pixels: pixels_text,
+                millimeters: format!("{millimeters:.1}")
+            )
+        }
+        None => fl!("measure-distance", pixels: pixels_text),
+    })
+}
+
 /// Build the footer element with zoom controls and document info.
 pub fn view(model: &AppModel) -> Element<'_, AppMessage> {
     // Zoom level display.
     let zoom_text = match model.view_mode {
         ViewMode::Fit => fl!("status-zoom-fit"),
+        ViewMode::FitHeight => fl!("status-zoom-fit-height"),
         _ => {
             if let Some(zoom) = model.zoom_factor() {
                 let percent = (zoom * 100.0).round() as i32;
@@ -26,6 +81,18 @@ pub fn view(model: &AppModel) -> Element<'_, AppMessage> {
         }
     };
 
+    // Zoom menu entry matching the current view, if any.
+    let zoom_preset = match model.view_mode {
+        ViewMode::Fit => Some(0),
+        ViewMode::FitHeight => Some(1),
+        _ => model.zoom_factor().and_then(|zoom| {
+            ZOOM_PRESETS
+                .iter()
+                .position(|preset| (preset - zoom).abs() < SCALE_EPSILON)
+                .map(|index| index + 2)
+        }),
+    };
+
     // Document dimensions (if available).
     let doc_info = if let Some(ref doc) = model.document {
         let (w, h) = doc.dimensions();
@@ -34,6 +101,40 @@ pub fn view(model: &AppModel) -> Element<'_, AppMessage> {
         String::new()
     };
 
+    // Page position for multi-page documents (e.g., "Page iv (4 / 120)").
+    let page_info = model.document.as_ref().and_then(|doc| {
+        if !doc.is_multi_page() {
+            return None;
+        }
+        let (current, total) = (doc.current_page()?, doc.page_count()?);
+        Some(fl!(
+            "status-page-position",
+            label: doc.page_label(current),
+            current: current + 1,
+            total: total
+        ))
+    });
+
+    // Measured distance, or how to start one (measure mode only).
+    let measure_info = (model.tool_mode == ToolMode::Measure)
+        .then(|| measurement_text(model).unwrap_or_else(|| fl!("measure-hint")));
+
+    // Which point the levels tool expects next (levels mode only).
+    let levels_info = (model.tool_mode == ToolMode::Levels).then(|| {
+        if model.levels_white.is_some() {
+            fl!("levels-hint-black")
+        } else {
+            fl!("levels-hint-white")
+        }
+    });
+
+    // Capture date from EXIF (if available).
+    let capture_date = model
+        .metadata
+        .as_ref()
+        .and_then(|meta| meta.exif.as_ref())
+        .and_then(|exif| exif.date_time.clone());
+
     // Navigation position (e.g., "3 / 42").
     let nav_info = if !model.folder_entries.is_empty() {
         let current = model.current_index.map(|i| i + 1).unwrap_or(0);
@@ -67,8 +168,43 @@ pub fn view(model: &AppModel) -> Element<'_, AppMessage> {
                 .on_press(AppMessage::ZoomFit)
                 .padding(4),
         )
+        // Zoom presets.
+        .push(dropdown(
+            ZOOM_PRESET_LABELS.as_slice(),
+            zoom_preset,
+            |index| match index {
+                0 => AppMessage::ZoomFit,
+                1 => AppMessage::ZoomFitHeight,
+                _ => AppMessage::SetZoom(ZOOM_PRESETS[index - 2]),
+            },
+        ))
+        // Crop aspect ratio (crop mode only).
+        .push_maybe((model.tool_mode == ToolMode::Crop).then(|| {
+            let selected = CropAspect::ALL.iter().position(|a| *a == model.crop_aspect);
+            dropdown(CROP_ASPECT_LABELS.as_slice(), selected, |index| {
+                AppMessage::SetCropAspect(CropAspect::ALL[index])
+            })
+        }))
+        // Measured distance (measure mode only).
+        .push_maybe(measure_info.map(text::body))
+        // Next point to pick (levels mode only).
+        .push_maybe(levels_info.map(text::body))
         // Spacer.
         .push(cosmic::widget::horizontal_space())
+        // Capture date with separator.
+        .push_maybe(capture_date.map(|date| {
+            row()
+                .spacing(8)
+                .push(text::body(date))
+                .push(text::body(fl!("status-separator")))
+        }))
+        // Page position with separator.
+        .push_maybe(page_info.map(|page| {
+            row()
+                .spacing(8)
+                .push(text::body(page))
+                .push(text::body(fl!("status-separator")))
+        }))
         // Document dimensions.
         .push(text::body(doc_info))
         // Separator.
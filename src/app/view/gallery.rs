@@ -0,0 +1,71 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// src/app/view/gallery.rs
+//
+// Gallery grid (contact sheet) of all documents in the current folder.
+
+use cosmic::iced::{Alignment, Length};
+use cosmic::widget::image as cosmic_image;
+use cosmic::widget::{button, column, container, flex_row, icon, scrollable, text};
+use cosmic::Element;
+
+use crate::app::{AppMessage, AppModel};
+use crate::constant::GALLERY_THUMBNAIL_SIZE;
+
+/// Build the gallery grid view.
+pub fn view(model: &AppModel) -> Element<'_, AppMessage> {
+    #[allow(clippy::cast_precision_loss)]
+    let cell_size = GALLERY_THUMBNAIL_SIZE as f32;
+
+    let cells: Vec<Element<'_, AppMessage>> = model
+        .folder_entries
+        .iter()
+        .enumerate()
+        .map(|(index, path)| {
+            let preview: Element<'_, AppMessage> = match model.gallery_thumbnails.get(path) {
+                Some(handle) => cosmic_image::Image::new(handle.clone())
+                    .width(Length::Fixed(cell_size))
+                    .height(Length::Fixed(cell_size))
+                    .into(),
+                None => container(icon::from_name("image-x-generic-symbolic").size(64))
+                    .width(Length::Fixed(cell_size))
+                    .height(Length::Fixed(cell_size))
+                    .center_x(Length::Fixed(cell_size))
+                    .center_y(Length::Fixed(cell_size))
+                    .into(),
+            };
+
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+
+            let cell = column::with_capacity(2)
+                .spacing(4)
+                .align_x(Alignment::Center)
+                .width(Length::Fixed(cell_size))
+                .push(preview)
+                .push(text::caption(name));
+
+            let class = if index == model.gallery_selected {
+                cosmic::theme::Button::Suggested
+            } else {
+                cosmic::theme::Button::Standard
+            };
+
+            button::custom(cell)
+                .class(class)
+                .padding(4)
+                .on_press(AppMessage::OpenGalleryEntry(index))
+                .into()
+        })
+        .collect();
+
+    scrollable(
+        container(flex_row(cells).row_spacing(12).column_spacing(12))
+            .padding(12)
+            .width(Length::Fill),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
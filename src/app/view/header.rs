@@ -7,6 +7,7 @@ use cosmic::iced::Length;
 use cosmic::widget::{button, horizontal_space, icon, row};
 use cosmic::Element;
 
+use crate::app::document::DocumentContent;
 use crate::app::message::AppMessage;
 use crate::app::model::AppModel;
 use crate::app::ContextPage;
@@ -14,9 +15,21 @@ use crate::app::ContextPage;
 /// Build the start (left) side of the header bar.
 pub fn start(model: &AppModel) -> Vec<Element<'_, AppMessage>> {
     let has_doc = model.document.is_some();
+    // Flip buttons show the on-screen state and set it absolutely when pressed.
+    let (flip_h, flip_v) = model
+        .document
+        .as_ref()
+        .map(DocumentContent::display_flips)
+        .unwrap_or_default();
+
+    let has_folder = !model.folder_entries.is_empty();
 
     // Left: Nav toggle + Navigation
     let left_controls = row()
+        .push(
+            button::icon(icon::from_name("view-grid-symbolic"))
+                .on_press_maybe(has_folder.then_some(AppMessage::ToggleGalleryView)),
+        )
         .push(
             button::icon(icon::from_name("go-previous-symbolic"))
                 .on_press_maybe(has_doc.then_some(AppMessage::PrevDocument)),
@@ -40,11 +53,19 @@ pub fn start(model: &AppModel) -> Vec<Element<'_, AppMessage>> {
         .push(horizontal_space().width(Length::Fixed(12.0)))
         .push(
             button::icon(icon::from_name("object-flip-horizontal-symbolic"))
-                .on_press_maybe(has_doc.then_some(AppMessage::FlipHorizontal)),
+                .selected(flip_h)
+                .on_press_maybe(has_doc.then_some(AppMessage::SetFlip {
+                    horizontal: !flip_h,
+                    vertical: flip_v,
+                })),
         )
         .push(
             button::icon(icon::from_name("object-flip-vertical-symbolic"))
-                .on_press_maybe(has_doc.then_some(AppMessage::FlipVertical)),
+                .selected(flip_v)
+                .on_press_maybe(has_doc.then_some(AppMessage::SetFlip {
+                    horizontal: flip_h,
+                    vertical: !flip_v,
+                })),
         );
 
     vec![
@@ -58,6 +79,10 @@ pub fn start(model: &AppModel) -> Vec<Element<'_, AppMessage>> {
 /// Build the end (right) side of the header bar.
 pub fn end(_model: &AppModel) -> Vec<Element<'_, AppMessage>> {
     vec![
+        // Settings panel toggle
+        button::icon(icon::from_name("preferences-system-symbolic"))
+            .on_press(AppMessage::ToggleContextPage(ContextPage::Settings))
+            .into(),
         // Info panel toggle
         button::icon(icon::from_name("dialog-information-symbolic"))
             .on_press(AppMessage::ToggleContextPage(ContextPage::Properties))
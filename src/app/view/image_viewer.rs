@@ -11,15 +11,27 @@ use cosmic::iced::advanced::widget::tree::{self, Tree};
 use cosmic::iced::advanced::widget::Widget;
 use cosmic::iced::advanced::{Clipboard, Layout, Shell};
 use cosmic::iced::event::{self, Event};
+use cosmic::iced::keyboard;
 use cosmic::iced::mouse;
+use cosmic::iced::time::{Duration, Instant};
 use cosmic::iced::widget::image::FilterMethod;
+use cosmic::iced::window;
 use cosmic::iced::{ContentFit, Element, Length, Pixels, Point, Radians, Rectangle, Size, Vector};
 
-use crate::constant::{OFFSET_EPSILON, SCALE_EPSILON};
+use crate::constant::{
+    INERTIA_FRICTION, INERTIA_MIN_SPEED, INERTIA_RELEASE_WINDOW_MS, INERTIA_SMOOTHING,
+    OFFSET_EPSILON, PAGE_SCROLL_THRESHOLD, SCALE_EPSILON, SCROLL_PIXELS_PER_LINE, TILE_MARGIN,
+};
 
 /// Callback type for notifying viewer state changes (scale, offset_x, offset_y).
 type StateChangeCallback<Message> = Box<dyn Fn(f32, f32, f32) -> Message>;
 
+/// Callback type for requesting a high-resolution tile (region as fractions of the image).
+type TileRequestCallback<Message> = Box<dyn Fn(Rectangle) -> Message>;
+
+/// Callback type for wheel page navigation (+1 next page, -1 previous page).
+type PageScrollCallback<Message> = Box<dyn Fn(isize) -> Message>;
+
 /// A frame that displays an image with the ability to zoom in/out and pan.
 #[allow(missing_debug_implementations)]
 pub struct Viewer<Handle, Message> {
@@ -32,10 +44,22 @@ pub struct Viewer<Handle, Message> {
     handle: Handle,
     filter_method: FilterMethod,
     content_fit: ContentFit,
+    /// Scale the image to the height of the bounds (overrides `content_fit`).
+    fit_height: bool,
+    /// Keep panning with decaying velocity after a quick drag.
+    inertia: bool,
     /// Optional external state to override internal state (scale, offset)
     external_state: Option<(f32, Vector)>,
     /// Optional callback to notify state changes
     on_state_change: Option<StateChangeCallback<Message>>,
+    /// High-resolution tile drawn over the image (region as fractions of the image)
+    tile: Option<(Handle, Rectangle)>,
+    /// Optional callback to request a tile when the image is magnified
+    on_tile_request: Option<TileRequestCallback<Message>>,
+    /// Optional callback to turn pages with the wheel while the image fits
+    on_page_scroll: Option<PageScrollCallback<Message>>,
+    /// Region to zoom and pan onto (image pixels)
+    frame: Option<Rectangle>,
 }
 
 impl<Handle, Message> Viewer<Handle, Message> {
@@ -46,13 +70,19 @@ impl<Handle, Message> Viewer<Handle, Message> {
             padding: 0.0,
             width: Length::Shrink,
             height: Length::Shrink,
-            min_scale: 0.25,
-            max_scale: 10.0,
+            min_scale: 0.1,
+            max_scale: 8.0,
             scale_step: 0.10,
             filter_method: FilterMethod::default(),
             content_fit: ContentFit::default(),
+            fit_height: false,
+            inertia: false,
             external_state: None,
             on_state_change: None,
+            tile: None,
+            on_tile_request: None,
+            on_page_scroll: None,
+            frame: None,
         }
     }
 
@@ -72,6 +102,42 @@ impl<Handle, Message> Viewer<Handle, Message> {
         self
     }
 
+    /// Enables tiled rendering: when the image is magnified beyond its texture
+    /// size, request a high-resolution tile of the visible region (plus margin).
+    ///
+    /// Only useful when the handle is a downscaled version of the source image.
+    pub fn on_tile_request<F>(mut self, f: F) -> Self
+    where
+        F: 'static + Fn(Rectangle) -> Message,
+    {
+        self.on_tile_request = Some(Box::new(f));
+        self
+    }
+
+    /// Enables wheel page navigation: while the image fits the bounds, the
+    /// wheel turns pages instead of zooming (hold Ctrl to zoom anyway).
+    pub fn on_page_scroll<F>(mut self, f: F) -> Self
+    where
+        F: 'static + Fn(isize) -> Message,
+    {
+        self.on_page_scroll = Some(Box::new(f));
+        self
+    }
+
+    /// Sets the high-resolution tile covering `region` (fractions of the image size).
+    pub fn tile(mut self, handle: Handle, region: Rectangle) -> Self {
+        self.tile = Some((handle, region));
+        self
+    }
+
+    /// Zooms and pans so that `region` (image pixels) fills the bounds.
+    ///
+    /// Applied once per region; the result is reported via `on_state_change`.
+    pub fn frame(mut self, region: Rectangle) -> Self {
+        self.frame = Some(region);
+        self
+    }
+
     /// Sets the [`FilterMethod`] of the [`Viewer`].
     pub fn filter_method(mut self, filter_method: FilterMethod) -> Self {
         self.filter_method = filter_method;
@@ -84,6 +150,23 @@ impl<Handle, Message> Viewer<Handle, Message> {
         self
     }
 
+    /// Scales the image so its height matches the bounds, overriding the
+    /// [`ContentFit`]; wide images can then be panned horizontally.
+    ///
+    /// Default is `false`
+    pub fn fit_height(mut self, fit_height: bool) -> Self {
+        self.fit_height = fit_height;
+        self
+    }
+
+    /// Enables momentum panning after a drag is released.
+    ///
+    /// Default is `false`
+    pub fn inertia(mut self, inertia: bool) -> Self {
+        self.inertia = inertia;
+        self
+    }
+
     /// Sets the padding of the [`Viewer`].
     pub fn padding(mut self, padding: impl Into<Pixels>) -> Self {
         self.padding = padding.into().0;
@@ -104,7 +187,7 @@ impl<Handle, Message> Viewer<Handle, Message> {
 
     /// Sets the max scale applied to the image of the [`Viewer`].
     ///
-    /// Default is `10.0`
+    /// Default is `8.0` (same as `AppConfig::max_scale`)
     pub fn max_scale(mut self, max_scale: f32) -> Self {
         self.max_scale = max_scale;
         self
@@ -112,14 +195,14 @@ impl<Handle, Message> Viewer<Handle, Message> {
 
     /// Sets the min scale applied to the image of the [`Viewer`].
     ///
-    /// Default is `0.25`
+    /// Default is `0.1` (same as `AppConfig::min_scale`)
     pub fn min_scale(mut self, min_scale: f32) -> Self {
         self.min_scale = min_scale;
         self
     }
 
     /// Sets the percentage the image of the [`Viewer`] will be scaled by
-    /// when zoomed in / out.
+    /// per mouse wheel notch; pixel deltas (trackpads) scale proportionally.
     ///
     /// Default is `0.10`
     pub fn scale_step(mut self, scale_step: f32) -> Self {
@@ -128,6 +211,104 @@ impl<Handle, Message> Viewer<Handle, Message> {
     }
 }
 
+impl<Handle, Message> Viewer<Handle, Message> {
+    /// Scaled size of the image in `bounds` for the given state.
+    fn scaled_size<Renderer>(&self, renderer: &Renderer, state: &State, bounds: Size) -> Size
+    where
+        Renderer: img_renderer::Renderer<Handle = Handle>,
+    {
+        if !self.fit_height {
+            return scaled_image_size(renderer, &self.handle, state, bounds, self.content_fit);
+        }
+
+        let Size { width, height } = renderer.measure_image(&self.handle);
+        let fit = bounds.height / height.max(1) as f32;
+        Size::new(
+            width as f32 * fit * state.scale,
+            height as f32 * fit * state.scale,
+        )
+    }
+
+    /// Fit the frame region into the bounds once it differs from the last one applied.
+    fn apply_frame<Renderer>(
+        &self,
+        state: &mut State,
+        bounds: Rectangle,
+        renderer: &Renderer,
+        shell: &mut Shell<'_, Message>,
+    ) where
+        Renderer: img_renderer::Renderer<Handle = Handle>,
+    {
+        let Some(region) = self.frame else {
+            return;
+        };
+        if state.framed == Some(region) || region.width <= 0.0 || region.height <= 0.0 {
+            return;
+        }
+
+        let image_size = renderer.measure_image(&self.handle);
+        let scale = (bounds.width / region.width)
+            .min(bounds.height / region.height)
+            .clamp(self.min_scale, self.max_scale);
+
+        // Offsets are relative to the centered image.
+        let center = region.center();
+        let offset = Vector::new(
+            (center.x - image_size.width as f32 / 2.0) * scale,
+            (center.y - image_size.height as f32 / 2.0) * scale,
+        );
+
+        state.framed = Some(region);
+        state.stop_inertia();
+        state.scale = scale;
+        state.current_offset = offset;
+        state.starting_offset = offset;
+
+        if let Some(ref on_change) = self.on_state_change {
+            shell.publish(on_change(scale, offset.x, offset.y));
+        }
+    }
+
+    /// Request a tile once the magnified visible region leaves the last requested one.
+    fn request_tile<Renderer>(
+        &self,
+        state: &mut State,
+        bounds: Rectangle,
+        renderer: &Renderer,
+        shell: &mut Shell<'_, Message>,
+    ) where
+        Renderer: img_renderer::Renderer<Handle = Handle>,
+    {
+        let Some(ref on_tile_request) = self.on_tile_request else {
+            return;
+        };
+
+        // The texture has enough detail as long as it is not magnified.
+        let texture_size = renderer.measure_image(&self.handle);
+        let scaled_size = self.scaled_size(renderer, state, bounds.size());
+        if scaled_size.width <= texture_size.width as f32 {
+            return;
+        }
+
+        let visible = visible_region(bounds.size(), scaled_size, state.current_offset, 0.0);
+        if state
+            .requested_region
+            .is_some_and(|region| covers(region, visible))
+        {
+            return;
+        }
+
+        let region = visible_region(
+            bounds.size(),
+            scaled_size,
+            state.current_offset,
+            TILE_MARGIN,
+        );
+        state.requested_region = Some(region);
+        shell.publish(on_tile_request(region));
+    }
+}
+
 impl<Message, Theme, Renderer, Handle> Widget<Message, Theme, Renderer> for Viewer<Handle, Message>
 where
     Renderer: img_renderer::Renderer<Handle = Handle>,
@@ -150,12 +331,22 @@ where
     }
 
     fn diff(&mut self, tree: &mut Tree) {
+        // A missing tile (new document or transform) must be requested again.
+        if self.tile.is_none() {
+            tree.state.downcast_mut::<State>().requested_region = None;
+        }
+
+        // Framing the same region again (after leaving it) must re-apply it.
+        if self.frame.is_none() {
+            tree.state.downcast_mut::<State>().framed = None;
+        }
+
         // Sync external state into internal state when user is not dragging
         if let Some((ext_scale, ext_offset)) = self.external_state {
             let state = tree.state.downcast_mut::<State>();
 
-            // Only apply external state if user is not currently dragging
-            if !state.is_cursor_grabbed() {
+            // Only apply external state if user is not currently dragging or gliding
+            if !state.is_cursor_grabbed() && !state.is_gliding() {
                 // Check if external state differs significantly from current state
                 let scale_changed = (state.scale - ext_scale).abs() > SCALE_EPSILON;
                 let offset_changed = (state.current_offset.x - ext_offset.x).abs() > OFFSET_EPSILON
@@ -216,85 +407,117 @@ where
     ) -> event::Status {
         let bounds = layout.bounds();
 
-        match event {
+        self.apply_frame(tree.state.downcast_mut::<State>(), bounds, renderer, shell);
+
+        let status = match event {
             Event::Mouse(mouse::Event::WheelScrolled { delta }) => {
                 let Some(cursor_position) = cursor.position_over(bounds) else {
                     return event::Status::Ignored;
                 };
 
-                match delta {
-                    mouse::ScrollDelta::Lines { y, .. } | mouse::ScrollDelta::Pixels { y, .. } => {
-                        let state = tree.state.downcast_mut::<State>();
-                        let previous_scale = state.scale;
-
-                        if y < 0.0 && previous_scale > self.min_scale
-                            || y > 0.0 && previous_scale < self.max_scale
-                        {
-                            state.scale = (if y > 0.0 {
-                                state.scale * (1.0 + self.scale_step)
-                            } else {
-                                state.scale / (1.0 + self.scale_step)
-                            })
-                            .clamp(self.min_scale, self.max_scale);
-
-                            let scale_factor = state.scale / previous_scale;
-
-                            // Cursor position relative to the image center (not bounds center)
-                            // The image is centered in bounds, so bounds.center() is correct
-                            let cursor_to_center = cursor_position - bounds.center();
-
-                            // Transform offset so the point under cursor stays stationary
-                            // Formula: new_offset = old_offset * scale_factor + cursor_to_center * (scale_factor - 1)
-                            let new_offset = Vector::new(
-                                state.current_offset.x * scale_factor
-                                    + cursor_to_center.x * (scale_factor - 1.0),
-                                state.current_offset.y * scale_factor
-                                    + cursor_to_center.y * (scale_factor - 1.0),
-                            );
-
-                            // Clamp offset to valid range
-                            let scaled_size = scaled_image_size(
-                                renderer,
-                                &self.handle,
-                                state,
-                                bounds.size(),
-                                self.content_fit,
-                            );
-
-                            state.current_offset =
-                                clamp_offset(new_offset, bounds.size(), scaled_size);
-
-                            // Notify state change
-                            if let Some(ref on_change) = self.on_state_change {
-                                shell.publish(on_change(
-                                    state.scale,
-                                    state.current_offset.x,
-                                    state.current_offset.y,
-                                ));
-                            }
+                if let Some(ref on_page_scroll) = self.on_page_scroll {
+                    let state = tree.state.downcast_mut::<State>();
+                    let scaled_size = self.scaled_size(renderer, state, bounds.size());
+                    let fits = scaled_size.width <= bounds.width + OFFSET_EPSILON
+                        && scaled_size.height <= bounds.height + OFFSET_EPSILON;
+
+                    if fits && !state.modifiers.control() {
+                        let lines = match delta {
+                            mouse::ScrollDelta::Lines { y, .. } => y,
+                            mouse::ScrollDelta::Pixels { y, .. } => y / SCROLL_PIXELS_PER_LINE,
+                        };
+                        if let Some(step) = state.accumulate_page_scroll(lines) {
+                            shell.publish(on_page_scroll(step));
                         }
+                        return event::Status::Captured;
+                    }
+                }
+
+                // Trackpads send many small pixel deltas: zoom by the matching
+                // fraction of a notch instead of a full step per event.
+                let notches = match delta {
+                    mouse::ScrollDelta::Lines { y, .. } => y,
+                    mouse::ScrollDelta::Pixels { y, .. } => y / SCROLL_PIXELS_PER_LINE,
+                };
+                let state = tree.state.downcast_mut::<State>();
+                state.stop_inertia();
+                let previous_scale = state.scale;
+
+                if notches < 0.0 && previous_scale > self.min_scale
+                    || notches > 0.0 && previous_scale < self.max_scale
+                {
+                    state.scale = (state.scale * (1.0 + self.scale_step).powf(notches))
+                        .clamp(self.min_scale, self.max_scale);
+
+                    let scale_factor = state.scale / previous_scale;
+
+                    // Cursor position relative to the image center (not bounds center)
+                    // The image is centered in bounds, so bounds.center() is correct
+                    let cursor_to_center = cursor_position - bounds.center();
+
+                    // Transform offset so the point under cursor stays stationary
+                    // Formula: new_offset = old_offset * scale_factor + cursor_to_center * (scale_factor - 1)
+                    let new_offset = Vector::new(
+                        state.current_offset.x * scale_factor
+                            + cursor_to_center.x * (scale_factor - 1.0),
+                        state.current_offset.y * scale_factor
+                            + cursor_to_center.y * (scale_factor - 1.0),
+                    );
+
+                    // Clamp offset to valid range
+                    let scaled_size = self.scaled_size(renderer, state, bounds.size());
+
+                    state.current_offset = clamp_offset(new_offset, bounds.size(), scaled_size);
+
+                    // Notify state change
+                    if let Some(ref on_change) = self.on_state_change {
+                        shell.publish(on_change(
+                            state.scale,
+                            state.current_offset.x,
+                            state.current_offset.y,
+                        ));
                     }
                 }
 
                 event::Status::Captured
             }
-            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+            // Both left and middle button drag pan the image.
+            Event::Mouse(mouse::Event::ButtonPressed(
+                mouse::Button::Left | mouse::Button::Middle,
+            )) => {
                 let Some(cursor_position) = cursor.position_over(bounds) else {
                     return event::Status::Ignored;
                 };
 
                 let state = tree.state.downcast_mut::<State>();
+                state.stop_inertia();
                 state.cursor_grabbed_at = Some(cursor_position);
                 state.starting_offset = state.current_offset;
+                state.last_sample = Some((cursor_position, Instant::now()));
 
                 event::Status::Captured
             }
-            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+            Event::Mouse(mouse::Event::ButtonReleased(
+                mouse::Button::Left | mouse::Button::Middle,
+            )) => {
                 let state = tree.state.downcast_mut::<State>();
 
                 if state.cursor_grabbed_at.is_some() {
                     state.cursor_grabbed_at = None;
 
+                    // Glide only if the cursor was still moving when released.
+                    let moving = state.last_sample.is_some_and(|(_, time)| {
+                        time.elapsed() < Duration::from_millis(INERTIA_RELEASE_WINDOW_MS)
+                    });
+                    state.last_sample = None;
+
+                    if self.inertia && moving && speed(state.velocity) > INERTIA_MIN_SPEED {
+                        state.last_frame = Some(Instant::now());
+                        shell.request_redraw(window::RedrawRequest::NextFrame);
+                    } else {
+                        state.velocity = Vector::default();
+                    }
+
                     // Notify final state after drag ends
                     if let Some(ref on_change) = self.on_state_change {
                         shell.publish(on_change(
@@ -313,13 +536,19 @@ where
                 let state = tree.state.downcast_mut::<State>();
 
                 if let Some(origin) = state.cursor_grabbed_at {
-                    let scaled_size = scaled_image_size(
-                        renderer,
-                        &self.handle,
-                        state,
-                        bounds.size(),
-                        self.content_fit,
-                    );
+                    // Track a smoothed cursor velocity for inertial panning.
+                    let now = Instant::now();
+                    if let Some((last_position, last_time)) = state.last_sample {
+                        let dt = now.duration_since(last_time).as_secs_f32();
+                        if dt > 0.0 {
+                            let sample = (position - last_position) * (1.0 / dt);
+                            state.velocity = state.velocity * (1.0 - INERTIA_SMOOTHING)
+                                + sample * INERTIA_SMOOTHING;
+                        }
+                    }
+                    state.last_sample = Some((position, now));
+
+                    let scaled_size = self.scaled_size(renderer, state, bounds.size());
 
                     let delta = position - origin;
 
@@ -345,8 +574,59 @@ where
                     event::Status::Ignored
                 }
             }
+            Event::Window(window::Event::RedrawRequested(now)) => {
+                let state = tree.state.downcast_mut::<State>();
+                let Some(last_frame) = state.last_frame else {
+                    return event::Status::Ignored;
+                };
+
+                let dt = now.saturating_duration_since(last_frame).as_secs_f32();
+                state.last_frame = Some(now);
+
+                let scaled_size = self.scaled_size(renderer, state, bounds.size());
+
+                // The offset moves opposite to the cursor, as in a drag.
+                let target = state.current_offset - state.velocity * dt;
+                let clamped = clamp_offset(target, bounds.size(), scaled_size);
+
+                state.current_offset = clamped;
+                state.starting_offset = clamped;
+                state.velocity = state.velocity * INERTIA_FRICTION.powf(dt);
+
+                // Stop gliding along an axis once the image edge is reached.
+                if (clamped.x - target.x).abs() > OFFSET_EPSILON {
+                    state.velocity.x = 0.0;
+                }
+                if (clamped.y - target.y).abs() > OFFSET_EPSILON {
+                    state.velocity.y = 0.0;
+                }
+
+                if speed(state.velocity) < INERTIA_MIN_SPEED {
+                    state.stop_inertia();
+                } else {
+                    shell.request_redraw(window::RedrawRequest::NextFrame);
+                }
+
+                if let Some(ref on_change) = self.on_state_change {
+                    shell.publish(on_change(
+                        state.scale,
+                        state.current_offset.x,
+                        state.current_offset.y,
+                    ));
+                }
+
+                event::Status::Ignored
+            }
+            Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                tree.state.downcast_mut::<State>().modifiers = modifiers;
+                event::Status::Ignored
+            }
             _ => event::Status::Ignored,
-        }
+        };
+
+        self.request_tile(tree.state.downcast_mut::<State>(), bounds, renderer, shell);
+
+        status
     }
 
     fn mouse_interaction(
@@ -383,13 +663,7 @@ where
         let state = tree.state.downcast_ref::<State>();
         let bounds = layout.bounds();
 
-        let scaled_size = scaled_image_size(
-            renderer,
-            &self.handle,
-            state,
-            bounds.size(),
-            self.content_fit,
-        );
+        let scaled_size = self.scaled_size(renderer, state, bounds.size());
 
         // Calculate translation to center the image and apply offset
         let translation = {
@@ -419,6 +693,28 @@ where
                     1.0,
                     [0.0; 4],
                 );
+
+                // Full-resolution detail for the visible region.
+                if let Some((tile, region)) = &self.tile {
+                    let tile_bounds = Rectangle::new(
+                        Point::new(
+                            drawing_bounds.x + region.x * scaled_size.width,
+                            drawing_bounds.y + region.y * scaled_size.height,
+                        ),
+                        Size::new(
+                            region.width * scaled_size.width,
+                            region.height * scaled_size.height,
+                        ),
+                    );
+                    renderer.draw_image(
+                        tile.clone(),
+                        self.filter_method,
+                        tile_bounds,
+                        Radians(0.0),
+                        1.0,
+                        [0.0; 4],
+                    );
+                }
             });
         };
 
@@ -433,6 +729,20 @@ pub struct State {
     starting_offset: Vector,
     current_offset: Vector,
     cursor_grabbed_at: Option<Point>,
+    /// Pan velocity in pixels per second (for inertial panning).
+    velocity: Vector,
+    /// Last cursor sample during a drag (position, time).
+    last_sample: Option<(Point, Instant)>,
+    /// Time of the previous inertia frame; Some while gliding.
+    last_frame: Option<Instant>,
+    /// Region of the last tile request (fractions of the image size).
+    requested_region: Option<Rectangle>,
+    /// Keyboard modifiers currently held (Ctrl forces wheel zoom).
+    modifiers: keyboard::Modifiers,
+    /// Wheel movement in lines not yet turned into a page change.
+    page_scroll: f32,
+    /// Region of the last applied frame (image pixels).
+    framed: Option<Rectangle>,
 }
 
 impl Default for State {
@@ -442,6 +752,13 @@ impl Default for State {
             starting_offset: Vector::default(),
             current_offset: Vector::default(),
             cursor_grabbed_at: None,
+            velocity: Vector::default(),
+            last_sample: None,
+            last_frame: None,
+            requested_region: None,
+            modifiers: keyboard::Modifiers::default(),
+            page_scroll: 0.0,
+            framed: None,
         }
     }
 }
@@ -456,6 +773,66 @@ impl State {
     pub fn is_cursor_grabbed(&self) -> bool {
         self.cursor_grabbed_at.is_some()
     }
+
+    /// Returns if the [`Viewer`] is currently panning by inertia.
+    pub fn is_gliding(&self) -> bool {
+        self.last_frame.is_some()
+    }
+
+    /// Stops any inertial panning in progress.
+    fn stop_inertia(&mut self) {
+        self.velocity = Vector::default();
+        self.last_frame = None;
+    }
+
+    /// Adds wheel movement (in lines) towards the next page change.
+    ///
+    /// Returns +1 (scrolled down) or -1 (scrolled up) once the threshold is
+    /// reached, so small touchpad deltas don't turn pages erratically.
+    fn accumulate_page_scroll(&mut self, lines: f32) -> Option<isize> {
+        // Start over when the direction changes.
+        if lines * self.page_scroll < 0.0 {
+            self.page_scroll = 0.0;
+        }
+        self.page_scroll += lines;
+
+        if self.page_scroll.abs() < PAGE_SCROLL_THRESHOLD {
+            return None;
+        }
+        let step = if self.page_scroll < 0.0 { 1 } else { -1 };
+        self.page_scroll = 0.0;
+        Some(step)
+    }
+}
+
+/// Visible part of the image as fractions of its size, grown by `margin`
+/// (a fraction of the viewport size) on each side.
+fn visible_region(viewport_size: Size, image_size: Size, offset: Vector, margin: f32) -> Rectangle {
+    // Viewport position in image coordinates (inverse of the draw translation).
+    let left = (image_size.width - viewport_size.width) / 2.0 + offset.x;
+    let top = (image_size.height - viewport_size.height) / 2.0 + offset.y;
+    let margin_x = viewport_size.width * margin;
+    let margin_y = viewport_size.height * margin;
+
+    let x0 = ((left - margin_x) / image_size.width).clamp(0.0, 1.0);
+    let y0 = ((top - margin_y) / image_size.height).clamp(0.0, 1.0);
+    let x1 = ((left + viewport_size.width + margin_x) / image_size.width).clamp(0.0, 1.0);
+    let y1 = ((top + viewport_size.height + margin_y) / image_size.height).clamp(0.0, 1.0);
+
+    Rectangle::new(Point::new(x0, y0), Size::new(x1 - x0, y1 - y0))
+}
+
+/// Whether `outer` fully contains `inner`.
+fn covers(outer: Rectangle, inner: Rectangle) -> bool {
+    inner.x >= outer.x
+        && inner.y >= outer.y
+        && inner.x + inner.width <= outer.x + outer.width
+        && inner.y + inner.height <= outer.y + outer.height
+}
+
+/// Length of a velocity vector.
+fn speed(velocity: Vector) -> f32 {
+    velocity.x.hypot(velocity.y)
 }
 
 /// Clamps the offset to keep the image within reasonable bounds.
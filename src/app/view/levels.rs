@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// src/app/view/levels.rs
+//
+// Levels overlay widget: click a white and a black point to remap the image tones.
+
+use cosmic::{
+    Element, Renderer,
+    iced::{
+        Border, Color, Length, Point, Rectangle, Size,
+        advanced::{
+            Clipboard, Layout, Shell, Widget,
+            layout::{Limits, Node},
+            renderer::{Quad, Renderer as QuadRenderer},
+            widget::Tree,
+        },
+        event::{Event, Status},
+        mouse::{self, Button, Cursor},
+    },
+};
+
+use super::Fit;
+use crate::app::AppMessage;
+
+/// Diameter of the white point marker in screen pixels.
+const MARKER_SIZE: f32 = 10.0;
+
+pub struct LevelsOverlay {
+    img_width: u32,
+    img_height: u32,
+    white: Option<(f32, f32)>,
+    scale: f32,
+    fit: Fit,
+    pan_x: f32,
+    pan_y: f32,
+}
+
+impl LevelsOverlay {
+    /// Image rectangle on screen and its scale relative to image pixels.
+    fn image_rect(&self, bounds: &Rectangle) -> (Rectangle, f32) {
+        let rect = super::image_screen_rect(
+            *bounds,
+            self.img_width,
+            self.img_height,
+            self.scale,
+            self.fit,
+            self.pan_x,
+            self.pan_y,
+        );
+        (rect, rect.width / self.img_width as f32)
+    }
+
+    /// Image pixel under the cursor, if it is over the image.
+    fn image_point(&self, bounds: &Rectangle, cursor: Cursor) -> Option<(f32, f32)> {
+        let pos = cursor.position_over(*bounds)?;
+        let (rect, scale) = self.image_rect(bounds);
+        rect.contains(pos)
+            .then(|| ((pos.x - rect.x) / scale, (pos.y - rect.y) / scale))
+    }
+}
+
+impl Widget<AppMessage, cosmic::Theme, Renderer> for LevelsOverlay {
+    fn size(&self) -> Size<Length> {
+        Size::new(Length::Fill, Length::Fill)
+    }
+
+    fn layout(&self, _tree: &mut Tree, _renderer: &Renderer, limits: &Limits) -> Node {
+        Node::new(limits.max())
+    }
+
+    fn draw(
+        &self,
+        _tree: &Tree,
+        renderer: &mut Renderer,
+        _theme: &cosmic::Theme,
+        _style: &cosmic::iced::advanced::renderer::Style,
+        layout: Layout<'_>,
+        _cursor: Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let Some((x, y)) = self.white else {
+            return;
+        };
+
+        // Mark the picked white point until the black point is clicked.
+        let (rect, scale) = self.image_rect(&layout.bounds());
+        let center = Point::new(rect.x + x * scale, rect.y + y * scale);
+        renderer.fill_quad(
+            Quad {
+                bounds: Rectangle::new(
+                    Point::new(center.x - MARKER_SIZE / 2.0, center.y - MARKER_SIZE / 2.0),
+                    Size::new(MARKER_SIZE, MARKER_SIZE),
+                ),
+                border: Border {
+                    radius: (MARKER_SIZE / 2.0).into(),
+                    width: 2.0,
+                    color: Color::BLACK,
+                },
+                ..Quad::default()
+            },
+            Color::WHITE,
+        );
+    }
+
+    fn on_event(
+        &mut self,
+        _tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, AppMessage>,
+        _viewport: &Rectangle,
+    ) -> Status {
+        if let Event::Mouse(mouse::Event::ButtonPressed(Button::Left)) = event
+            && let Some((x, y)) = self.image_point(&layout.bounds(), cursor)
+        {
+            shell.publish(AppMessage::LevelsPoint { x, y });
+            return Status::Captured;
+        }
+
+        // Everything else (wheel zoom, clicks beside the image) reaches the viewer.
+        Status::Ignored
+    }
+
+    fn mouse_interaction(
+        &self,
+        _tree: &Tree,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        if self.image_point(&layout.bounds(), cursor).is_some() {
+            mouse::Interaction::Crosshair
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+}
+
+impl<'a> From<LevelsOverlay> for Element<'a, AppMessage> {
+    fn from(overlay: LevelsOverlay) -> Self {
+        Self::new(overlay)
+    }
+}
+
+pub fn levels_overlay(
+    img_width: u32,
+    img_height: u32,
+    white: Option<(f32, f32)>,
+    scale: f32,
+    fit: Fit,
+    pan_x: f32,
+    pan_y: f32,
+) -> LevelsOverlay {
+    LevelsOverlay {
+        img_width,
+        img_height,
+        white,
+        scale,
+        fit,
+        pan_x,
+        pan_y,
+    }
+}
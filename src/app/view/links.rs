@@ -0,0 +1,183 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// src/app/view/links.rs
+//
+// Link overlay widget: clickable link regions with a hover highlight.
+
+use cosmic::{
+    Element, Renderer,
+    iced::{
+        Color, Length, Point, Rectangle, Size,
+        advanced::{
+            Clipboard, Layout, Shell, Widget,
+            layout::{Limits, Node},
+            renderer::{Quad, Renderer as QuadRenderer},
+            widget::Tree,
+        },
+        event::{Event, Status},
+        mouse::{self, Button, Cursor},
+    },
+};
+
+use super::Fit;
+use crate::app::AppMessage;
+use crate::app::document::{LinkRegion, LinkTarget};
+
+const HOVER_COLOR: Color = Color::from_rgba(0.2, 0.5, 1.0, 0.2);
+
+pub struct LinkOverlay {
+    img_width: u32,
+    img_height: u32,
+    regions: Vec<LinkRegion>,
+    scale: f32,
+    fit: Fit,
+    pan_x: f32,
+    pan_y: f32,
+}
+
+impl LinkOverlay {
+    /// Scale 0.0 means the image is fitted into the bounds as described by `fit`.
+    pub fn new(
+        img_width: u32,
+        img_height: u32,
+        regions: Vec<LinkRegion>,
+        scale: f32,
+        fit: Fit,
+        pan_x: f32,
+        pan_y: f32,
+    ) -> Self {
+        Self {
+            img_width,
+            img_height,
+            regions,
+            scale,
+            fit,
+            pan_x,
+            pan_y,
+        }
+    }
+
+    /// Image rectangle on screen and its scale relative to image pixels.
+    fn image_rect(&self, bounds: &Rectangle) -> (Rectangle, f32) {
+        let rect = super::image_screen_rect(
+            *bounds,
+            self.img_width,
+            self.img_height,
+            self.scale,
+            self.fit,
+            self.pan_x,
+            self.pan_y,
+        );
+        (rect, rect.width / self.img_width as f32)
+    }
+
+    fn region_at(&self, bounds: &Rectangle, point: Point) -> Option<&LinkRegion> {
+        let (rect, scale) = self.image_rect(bounds);
+        let x = (point.x - rect.x) / scale;
+        let y = (point.y - rect.y) / scale;
+        self.regions.iter().find(|region| region.contains(x, y))
+    }
+
+    fn hovered(&self, bounds: &Rectangle, cursor: Cursor) -> Option<&LinkRegion> {
+        cursor
+            .position_over(*bounds)
+            .and_then(|pos| self.region_at(bounds, pos))
+    }
+}
+
+impl Widget<AppMessage, cosmic::Theme, Renderer> for LinkOverlay {
+    fn size(&self) -> Size<Length> {
+        Size::new(Length::Fill, Length::Fill)
+    }
+
+    fn layout(&self, _tree: &mut Tree, _renderer: &Renderer, limits: &Limits) -> Node {
+        Node::new(limits.max())
+    }
+
+    fn draw(
+        &self,
+        _tree: &Tree,
+        renderer: &mut Renderer,
+        _theme: &cosmic::Theme,
+        _style: &cosmic::iced::advanced::renderer::Style,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        let Some(region) = self.hovered(&bounds, cursor) else {
+            return;
+        };
+
+        let (rect, scale) = self.image_rect(&bounds);
+        renderer.fill_quad(
+            Quad {
+                bounds: Rectangle::new(
+                    Point::new(rect.x + region.x * scale, rect.y + region.y * scale),
+                    Size::new(region.width * scale, region.height * scale),
+                ),
+                ..Quad::default()
+            },
+            HOVER_COLOR,
+        );
+    }
+
+    fn on_event(
+        &mut self,
+        _tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, AppMessage>,
+        _viewport: &Rectangle,
+    ) -> Status {
+        let bounds = layout.bounds();
+
+        if let Event::Mouse(mouse::Event::ButtonPressed(Button::Left)) = event
+            && let Some(region) = self.hovered(&bounds, cursor)
+        {
+            shell.publish(match &region.target {
+                LinkTarget::Page(page) => AppMessage::GotoPage(*page),
+                LinkTarget::Uri(uri) => AppMessage::OpenUrl(uri.clone()),
+            });
+            return Status::Captured;
+        }
+
+        // Everything outside link regions falls through to the image viewer.
+        Status::Ignored
+    }
+
+    fn mouse_interaction(
+        &self,
+        _tree: &Tree,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        if self.hovered(&layout.bounds(), cursor).is_some() {
+            mouse::Interaction::Pointer
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+}
+
+impl<'a> From<LinkOverlay> for Element<'a, AppMessage> {
+    fn from(overlay: LinkOverlay) -> Self {
+        Self::new(overlay)
+    }
+}
+
+pub fn link_overlay(
+    img_width: u32,
+    img_height: u32,
+    regions: Vec<LinkRegion>,
+    scale: f32,
+    fit: Fit,
+    pan_x: f32,
+    pan_y: f32,
+) -> LinkOverlay {
+    LinkOverlay::new(img_width, img_height, regions, scale, fit, pan_x, pan_y)
+}
@@ -0,0 +1,256 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// src/app/view/measure.rs
+//
+// Measure overlay widget: click two points to measure the distance between them.
+
+use cosmic::{
+    Element, Renderer,
+    iced::{
+        Alignment, Border, Color, Length, Point, Rectangle, Size,
+        advanced::{
+            Clipboard, Layout, Shell, Widget,
+            layout::{Limits, Node},
+            renderer::{Quad, Renderer as QuadRenderer},
+            widget::Tree,
+        },
+        event::{Event, Status},
+        mouse::{self, Button, Cursor},
+        window,
+    },
+    widget::{container, text},
+};
+
+use super::Fit;
+use crate::app::model::Measurement;
+use crate::app::{AppMessage, AppModel};
+
+const LINE_COLOR: Color = Color::from_rgb(1.0, 0.3, 0.2);
+/// Thickness of the measuring line in screen pixels.
+const LINE_WIDTH: f32 = 2.0;
+/// Diameter of the end point markers in screen pixels.
+const MARKER_SIZE: f32 = 8.0;
+
+pub struct MeasureOverlay {
+    img_width: u32,
+    img_height: u32,
+    measurement: Option<Measurement>,
+    scale: f32,
+    fit: Fit,
+    pan_x: f32,
+    pan_y: f32,
+}
+
+impl MeasureOverlay {
+    /// Scale 0.0 means the image is fitted into the bounds as described by `fit`.
+    pub fn new(
+        img_width: u32,
+        img_height: u32,
+        measurement: Option<Measurement>,
+        scale: f32,
+        fit: Fit,
+        pan_x: f32,
+        pan_y: f32,
+    ) -> Self {
+        Self {
+            img_width,
+            img_height,
+            measurement,
+            scale,
+            fit,
+            pan_x,
+            pan_y,
+        }
+    }
+
+    /// Image rectangle on screen and its scale relative to image pixels.
+    fn image_rect(&self, bounds: &Rectangle) -> (Rectangle, f32) {
+        let rect = super::image_screen_rect(
+            *bounds,
+            self.img_width,
+            self.img_height,
+            self.scale,
+            self.fit,
+            self.pan_x,
+            self.pan_y,
+        );
+        (rect, rect.width / self.img_width as f32)
+    }
+
+    /// Image pixel under the cursor, if it is over the image.
+    fn image_point(&self, bounds: &Rectangle, cursor: Cursor) -> Option<(f32, f32)> {
+        let pos = cursor.position_over(*bounds)?;
+        let (rect, scale) = self.image_rect(bounds);
+        rect.contains(pos)
+            .then(|| ((pos.x - rect.x) / scale, (pos.y - rect.y) / scale))
+    }
+}
+
+/// Draw a straight line as a row of small quads.
+fn draw_line(renderer: &mut Renderer, from: Point, to: Point) {
+    let (dx, dy) = (to.x - from.x, to.y - from.y);
+    let steps = (dx.hypot(dy) / LINE_WIDTH).ceil().max(1.0) as usize;
+    for step in 0..=steps {
+        let t = step as f32 / steps as f32;
+        renderer.fill_quad(
+            Quad {
+                bounds: Rectangle::new(
+                    Point::new(
+                        from.x + dx * t - LINE_WIDTH / 2.0,
+                        from.y + dy * t - LINE_WIDTH / 2.0,
+                    ),
+                    Size::new(LINE_WIDTH, LINE_WIDTH),
+                ),
+                ..Quad::default()
+            },
+            LINE_COLOR,
+        );
+    }
+}
+
+/// Draw a round marker centered on `center`.
+fn draw_marker(renderer: &mut Renderer, center: Point) {
+    renderer.fill_quad(
+        Quad {
+            bounds: Rectangle::new(
+                Point::new(center.x - MARKER_SIZE / 2.0, center.y - MARKER_SIZE / 2.0),
+                Size::new(MARKER_SIZE, MARKER_SIZE),
+            ),
+            border: Border {
+                radius: (MARKER_SIZE / 2.0).into(),
+                width: 1.0,
+                color: Color::WHITE,
+            },
+            ..Quad::default()
+        },
+        LINE_COLOR,
+    );
+}
+
+impl Widget<AppMessage, cosmic::Theme, Renderer> for MeasureOverlay {
+    fn size(&self) -> Size<Length> {
+        Size::new(Length::Fill, Length::Fill)
+    }
+
+    fn layout(&self, _tree: &mut Tree, _renderer: &Renderer, limits: &Limits) -> Node {
+        Node::new(limits.max())
+    }
+
+    fn draw(
+        &self,
+        _tree: &Tree,
+        renderer: &mut Renderer,
+        _theme: &cosmic::Theme,
+        _style: &cosmic::iced::advanced::renderer::Style,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let Some(measurement) = self.measurement else {
+            return;
+        };
+
+        let bounds = layout.bounds();
+        let (rect, scale) = self.image_rect(&bounds);
+        let to_screen = |(x, y): (f32, f32)| Point::new(rect.x + x * scale, rect.y + y * scale);
+
+        let start = to_screen(measurement.start);
+        // Until the second click the line follows the cursor.
+        let end = match measurement.end {
+            Some(end) => Some(to_screen(end)),
+            None => cursor.position_over(bounds),
+        };
+
+        if let Some(end) = end {
+            draw_line(renderer, start, end);
+        }
+        draw_marker(renderer, start);
+        if let Some(end) = measurement.end {
+            draw_marker(renderer, to_screen(end));
+        }
+    }
+
+    fn on_event(
+        &mut self,
+        _tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, AppMessage>,
+        _viewport: &Rectangle,
+    ) -> Status {
+        let bounds = layout.bounds();
+
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(Button::Left)) => {
+                if let Some((x, y)) = self.image_point(&bounds, cursor) {
+                    shell.publish(AppMessage::MeasurePoint { x, y });
+                    return Status::Captured;
+                }
+            }
+            // Redraw the rubber band line while waiting for the second point.
+            Event::Mouse(mouse::Event::CursorMoved { .. })
+                if self.measurement.is_some_and(|m| m.end.is_none()) =>
+            {
+                shell.request_redraw(window::RedrawRequest::NextFrame);
+            }
+            _ => {}
+        }
+
+        // Everything else (wheel zoom, clicks beside the image) reaches the viewer.
+        Status::Ignored
+    }
+
+    fn mouse_interaction(
+        &self,
+        _tree: &Tree,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        if self.image_point(&layout.bounds(), cursor).is_some() {
+            mouse::Interaction::Crosshair
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+}
+
+impl<'a> From<MeasureOverlay> for Element<'a, AppMessage> {
+    fn from(overlay: MeasureOverlay) -> Self {
+        Self::new(overlay)
+    }
+}
+
+pub fn measure_overlay(
+    img_width: u32,
+    img_height: u32,
+    measurement: Option<Measurement>,
+    scale: f32,
+    fit: Fit,
+    pan_x: f32,
+    pan_y: f32,
+) -> MeasureOverlay {
+    MeasureOverlay::new(img_width, img_height, measurement, scale, fit, pan_x, pan_y)
+}
+
+/// Distance label in the top-left corner of the canvas once both points are set.
+pub fn label(model: &AppModel) -> Option<Element<'_, AppMessage>> {
+    let distance = super::footer::measurement_text(model)?;
+
+    let card = container(text::body(distance))
+        .padding([4, 8])
+        .class(cosmic::theme::Container::Card);
+
+    Some(
+        container(card)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding(12)
+            .align_x(Alignment::Start)
+            .align_y(Alignment::Start)
+            .into(),
+    )
+}
@@ -3,40 +3,124 @@
 //
 // View module root, combining all view components.
 
+mod animation;
 mod canvas;
+mod checkerboard;
+mod compare;
+mod context_menu;
 pub mod crop;
+mod debug;
+pub mod export;
 pub mod footer;
+pub mod gallery;
 pub mod header;
 mod image_viewer;
+mod levels;
+mod links;
+mod measure;
 pub mod pages_panel;
 pub mod panels;
+mod rating;
+mod scrollbars;
+pub mod settings;
 
-use cosmic::iced::Length;
-use cosmic::widget::container;
+use cosmic::iced::{Length, Point, Rectangle, Size};
+use cosmic::widget::{column, container, row, warning};
 use cosmic::{Action, Element};
 
 use crate::app::{AppMessage, AppModel};
 use crate::config::AppConfig;
 
-/// Main application view (canvas area).
+/// Main application view (canvas area with optional error banner).
 pub fn view<'a>(model: &'a AppModel, config: &'a AppConfig) -> Element<'a, AppMessage> {
-    canvas::view(model, config)
+    let canvas = if model.gallery_visible {
+        gallery::view(model)
+    } else if let Some(secondary) = compare::view(model, config) {
+        row::with_capacity(2)
+            .spacing(2)
+            .push(canvas::view(model, config))
+            .push(secondary)
+            .into()
+    } else {
+        canvas::view(model, config)
+    };
+
+    match model.error.as_deref() {
+        Some(message) => column::with_capacity(2)
+            .push(warning(message).on_close(AppMessage::ClearError))
+            .push(canvas)
+            .into(),
+        None => canvas,
+    }
 }
 
 /// Navigation bar content (left panel for multi-page documents).
 ///
 /// Returns None if no multi-page document is loaded.
-pub fn nav_bar(model: &AppModel) -> Option<Element<'_, Action<AppMessage>>> {
+pub fn nav_bar<'a>(
+    model: &'a AppModel,
+    config: &AppConfig,
+) -> Option<Element<'a, Action<AppMessage>>> {
     let doc = model.document.as_ref()?;
     if !doc.is_multi_page() {
         return None;
     }
 
-    pages_panel::view(model).map(|panel| {
+    pages_panel::view(model, config.thumbnail_width).map(|panel| {
         container(panel.map(Action::App))
             .width(Length::Shrink)
             .height(Length::Fill)
-            .max_width(200)
+            .max_width(config.nav_panel_width)
             .into()
     })
 }
+
+/// How the overlays resolve a scale of 0.0 against the canvas bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fit {
+    /// Whole image, enlarging small ones (`ContentFit::Contain`).
+    Contain,
+    /// Whole image, but never above 100% (`ContentFit::ScaleDown`).
+    ScaleDown,
+    /// Image height matches the bounds (`ViewMode::FitHeight`).
+    Height,
+}
+
+/// Scale that fits an image into `bounds`.
+fn fit_scale(bounds: Size, img_width: u32, img_height: u32, fit: Fit) -> f32 {
+    let height_scale = bounds.height / img_height as f32;
+    let scale = (bounds.width / img_width as f32).min(height_scale);
+    match fit {
+        Fit::Contain => scale,
+        Fit::ScaleDown => scale.min(1.0),
+        Fit::Height => height_scale,
+    }
+}
+
+/// Screen rectangle of the centered, zoomed and panned document image.
+///
+/// A scale of 0.0 fits the image into `bounds` as described by `fit`.
+fn image_screen_rect(
+    bounds: Rectangle,
+    img_width: u32,
+    img_height: u32,
+    scale: f32,
+    fit: Fit,
+    pan_x: f32,
+    pan_y: f32,
+) -> Rectangle {
+    let scale = if scale > 0.0 {
+        scale
+    } else {
+        fit_scale(bounds.size(), img_width, img_height, fit)
+    };
+    let size = Size::new(img_width as f32 * scale, img_height as f32 * scale);
+
+    Rectangle::new(
+        Point::new(
+            bounds.x + (bounds.width - size.width) / 2.0 - pan_x,
+            bounds.y + (bounds.height - size.height) / 2.0 - pan_y,
+        ),
+        size,
+    )
+}
@@ -3,18 +3,38 @@
 //
 // Page navigation panel for multi-page documents (PDF, multi-page TIFF, etc.).
 
+use std::sync::LazyLock;
+
+use cosmic::iced::widget::scrollable::{self as iced_scrollable, RelativeOffset};
 use cosmic::iced::{Alignment, Length};
-use cosmic::widget::{button, column, scrollable, text};
+use cosmic::widget::{button, column, progress_bar, scrollable, text};
 use cosmic::widget::image as cosmic_image;
-use cosmic::Element;
+use cosmic::{Action, Element, Task};
 
 use crate::app::{AppMessage, AppModel};
-use crate::constant::THUMBNAIL_MAX_WIDTH;
 use crate::fl;
 
-/// Build the page navigation panel view.
+/// Widget id of the page list, used to scroll the current page into view.
+static PAGES_SCROLL_ID: LazyLock<iced_scrollable::Id> =
+    LazyLock::new(|| iced_scrollable::Id::new("pages-panel"));
+
+/// Scroll the page list so that the given page is visible.
+///
+/// Thumbnails have a uniform height, so the page index maps linearly
+/// onto the relative scroll offset.
+pub fn scroll_to_page(page: usize, page_count: usize) -> Task<Action<AppMessage>> {
+    #[allow(clippy::cast_precision_loss)]
+    let y = if page_count > 1 {
+        page as f32 / (page_count - 1) as f32
+    } else {
+        0.0
+    };
+    iced_scrollable::snap_to(PAGES_SCROLL_ID.clone(), RelativeOffset { x: 0.0, y })
+}
+
+/// Build the page navigation panel view with thumbnails `thumbnail_width` pixels wide.
 /// Returns None if the current document doesn't support multiple pages.
-pub fn view(model: &AppModel) -> Option<Element<'static, AppMessage>> {
+pub fn view(model: &AppModel, thumbnail_width: f32) -> Option<Element<'static, AppMessage>> {
     let doc = model.document.as_ref()?;
 
     // Only show for multi-page documents.
@@ -26,7 +46,7 @@ pub fn view(model: &AppModel) -> Option<Element<'static, AppMessage>> {
     let loaded = doc.thumbnails_loaded();
     let current_page = doc.current_page()?;
 
-    let mut content = column::with_capacity(page_count + 1)
+    let mut content = column::with_capacity(page_count + 2)
         .spacing(12)
         .padding([12, 8])
         .align_x(Alignment::Center)
@@ -34,8 +54,12 @@ pub fn view(model: &AppModel) -> Option<Element<'static, AppMessage>> {
 
     // Show loading progress if not all thumbnails are ready.
     if !doc.thumbnails_ready() {
+        #[allow(clippy::cast_precision_loss)]
+        let progress = loaded as f32 / page_count as f32;
         let loading_msg = fl!("loading-thumbnails", current: loaded, total: page_count);
-        content = content.push(text::caption(loading_msg));
+        content = content
+            .push(progress_bar(0.0..=1.0, progress))
+            .push(text::caption(loading_msg));
     }
 
     // Build thumbnail list for pages that are already loaded.
@@ -46,15 +70,15 @@ pub fn view(model: &AppModel) -> Option<Element<'static, AppMessage>> {
         let thumbnail_element: Element<'static, AppMessage> =
             if let Some(handle) = doc.get_thumbnail(page_index) {
                 cosmic_image::Image::new(handle)
-                    .width(Length::Fixed(THUMBNAIL_MAX_WIDTH))
+                    .width(Length::Fixed(thumbnail_width))
                     .into()
             } else {
-                // Fallback: show page number if no thumbnail.
-                text::body(format!("{}", page_index + 1)).into()
+                // Fallback: show page label if no thumbnail.
+                text::body(doc.page_label(page_index)).into()
             };
 
-        // Page number label.
-        let page_label = text::caption(format!("{}", page_index + 1));
+        // Page label (e.g. "iv" for front matter, else the page number).
+        let page_label = text::caption(doc.page_label(page_index));
 
         // Combine thumbnail and label in a column.
         let page_content = column::with_capacity(2)
@@ -83,6 +107,7 @@ pub fn view(model: &AppModel) -> Option<Element<'static, AppMessage>> {
     // Wrap in scrollable container.
     Some(
         scrollable(content)
+            .id(PAGES_SCROLL_ID.clone())
             .width(Length::Shrink)
             .height(Length::Fill)
             .into(),
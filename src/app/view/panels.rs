@@ -7,9 +7,17 @@ use cosmic::iced::Length;
 use cosmic::widget::{button, column, divider, horizontal_space, icon, row, text};
 use cosmic::Element;
 
+use crate::app::document::meta::DocumentMeta;
+use crate::app::document::{DocumentContent, TransformState};
 use crate::app::{AppMessage, AppModel};
 use crate::fl;
 
+/// A titled group of metadata rows (label, value).
+pub struct MetaSection {
+    pub title: String,
+    pub rows: Vec<(String, String)>,
+}
+
 /// Build the properties panel view.
 pub fn view(model: &AppModel) -> Element<'static, AppMessage> {
     let mut content = column::with_capacity(16).spacing(8);
@@ -19,66 +27,14 @@ pub fn view(model: &AppModel) -> Element<'static, AppMessage> {
 
     // Display document metadata if available (cached in model).
     if let Some(ref meta) = model.metadata {
-        // --- Basic Information Section ---
-        content = content
-            .push(section_header(fl!("meta-section-file")))
-            .push(meta_row(fl!("meta-filename"), meta.basic.file_name.clone()))
-            .push(meta_row(fl!("meta-format"), meta.basic.format.clone()))
-            .push(meta_row(
-                fl!("meta-dimensions"),
-                meta.basic.resolution_display(),
-            ))
-            .push(meta_row(
-                fl!("meta-filesize"),
-                meta.basic.file_size_display(),
-            ))
-            .push(meta_row(
-                fl!("meta-colortype"),
-                meta.basic.color_type.clone(),
-            ));
-
-        // --- EXIF Section (if available) ---
-        if let Some(ref exif) = meta.exif {
-            let has_exif_data = exif.camera_display().is_some()
-                || exif.date_time.is_some()
-                || exif.exposure_time.is_some()
-                || exif.f_number.is_some()
-                || exif.iso.is_some()
-                || exif.focal_length.is_some()
-                || exif.gps_display().is_some();
-
-            if has_exif_data {
-                content = content
-                    .push(divider::horizontal::light())
-                    .push(section_header(fl!("meta-section-exif")));
-
-                if let Some(camera) = exif.camera_display() {
-                    content = content.push(meta_row(fl!("meta-camera"), camera));
-                }
-
-                if let Some(ref date) = exif.date_time {
-                    content = content.push(meta_row(fl!("meta-datetime"), date.clone()));
-                }
-
-                if let Some(ref exposure) = exif.exposure_time {
-                    content = content.push(meta_row(fl!("meta-exposure"), exposure.clone()));
-                }
-
-                if let Some(ref fnumber) = exif.f_number {
-                    content = content.push(meta_row(fl!("meta-aperture"), fnumber.clone()));
-                }
-
-                if let Some(iso) = exif.iso {
-                    content = content.push(meta_row(fl!("meta-iso"), fl!("meta-iso", iso: iso)));
-                }
-
-                if let Some(ref focal) = exif.focal_length {
-                    content = content.push(meta_row(fl!("meta-focal"), focal.clone()));
-                }
-
-                if let Some(gps) = exif.gps_display() {
-                    content = content.push(meta_row(fl!("meta-gps"), gps));
-                }
+        let transform = model.document.as_ref().map(|doc| doc.transform_state());
+        for (index, section) in meta_sections(meta, transform).into_iter().enumerate() {
+            if index > 0 {
+                content = content.push(divider::horizontal::light());
+            }
+            content = content.push(section_header(section.title));
+            for (label, value) in section.rows {
+                content = content.push(meta_row(label, value));
             }
         }
 
@@ -96,6 +52,180 @@ pub fn view(model: &AppModel) -> Element<'static, AppMessage> {
     content.into()
 }
 
+/// Collect the metadata shown in the properties panel, grouped by section.
+///
+/// Shared by the panel view and the plain-text export so both stay consistent.
+pub fn meta_sections(meta: &DocumentMeta, transform: Option<TransformState>) -> Vec<MetaSection> {
+    let mut sections = Vec::with_capacity(3);
+
+    // --- Basic Information Section ---
+    let mut rows = vec![
+        (fl!("meta-filename"), meta.basic.file_name.clone()),
+        (fl!("meta-format"), meta.basic.format.clone()),
+        (fl!("meta-dimensions"), meta.basic.resolution_display()),
+    ];
+    if let Some(ratio) = meta.basic.aspect_ratio_display() {
+        rows.push((fl!("meta-aspect-ratio"), ratio));
+    }
+    rows.extend([
+        (fl!("meta-megapixels"), meta.basic.megapixels_display()),
+        (fl!("meta-filesize"), meta.basic.file_size_display()),
+        (fl!("meta-colortype"), meta.basic.color_type.clone()),
+    ]);
+    if let Some(display) = meta.basic.display_size_display() {
+        rows.push((fl!("meta-display-size"), display));
+    }
+    if let Some(frames) = meta.basic.frame_count {
+        rows.push((fl!("meta-frames"), frames.to_string()));
+    }
+    if let Some(transform) = transform {
+        rows.push((fl!("meta-transform"), transform_display(transform)));
+    }
+    sections.push(MetaSection {
+        title: fl!("meta-section-file"),
+        rows,
+    });
+
+    // --- EXIF Section (if available) ---
+    if let Some(ref exif) = meta.exif {
+        let mut rows = Vec::new();
+
+        if let Some(camera) = exif.camera_display() {
+            rows.push((fl!("meta-camera"), camera));
+        }
+        if let Some(ref date) = exif.date_time {
+            rows.push((fl!("meta-datetime"), date.clone()));
+        }
+        if let Some(ref exposure) = exif.exposure_time {
+            rows.push((fl!("meta-exposure"), exposure.clone()));
+        }
+        if let Some(ref fnumber) = exif.f_number {
+            rows.push((fl!("meta-aperture"), fnumber.clone()));
+        }
+        if let Some(iso) = exif.iso {
+            rows.push((fl!("meta-iso"), fl!("meta-iso", iso: iso)));
+        }
+        if let Some(ref focal) = exif.focal_length {
+            rows.push((fl!("meta-focal"), focal.clone()));
+        }
+        if let Some(ref lens) = exif.lens_model {
+            rows.push((fl!("meta-lens"), lens.clone()));
+        }
+        if let Some(ref program) = exif.exposure_program {
+            rows.push((fl!("meta-exposure-program"), program.clone()));
+        }
+        if let Some(ref metering) = exif.metering_mode {
+            rows.push((fl!("meta-metering"), metering.clone()));
+        }
+        if let Some(ref flash) = exif.flash {
+            rows.push((fl!("meta-flash"), flash.clone()));
+        }
+        if let Some(ref wb) = exif.white_balance {
+            rows.push((fl!("meta-white-balance"), wb.clone()));
+        }
+        if let Some(orientation) = exif.orientation_display() {
+            rows.push((fl!("meta-orientation"), orientation));
+        }
+        if let Some(gps) = exif.gps_display() {
+            rows.push((fl!("meta-gps"), gps));
+        }
+
+        if !rows.is_empty() {
+            sections.push(MetaSection {
+                title: fl!("meta-section-exif"),
+                rows,
+            });
+        }
+    }
+
+    // --- Description Section (IPTC/XMP, if available) ---
+    if let Some(ref desc) = meta.descriptive {
+        let mut rows = Vec::new();
+
+        if let Some(ref title) = desc.title {
+            rows.push((fl!("meta-title"), title.clone()));
+        }
+        if let Some(ref description) = desc.description {
+            rows.push((fl!("meta-description"), description.clone()));
+        }
+        if let Some(keywords) = desc.keywords_display() {
+            rows.push((
+                fl!("meta-keywords"),
+                sidecar_value(keywords, desc.sidecar.keywords),
+            ));
+        }
+        if let Some(ref creator) = desc.creator {
+            rows.push((fl!("meta-creator"), creator.clone()));
+        }
+        if let Some(ref copyright) = desc.copyright {
+            rows.push((fl!("meta-copyright"), copyright.clone()));
+        }
+        let rating = if desc.rejected {
+            Some(fl!("meta-rejected"))
+        } else {
+            desc.rating_display()
+        };
+        if let Some(rating) = rating {
+            rows.push((
+                fl!("meta-rating"),
+                sidecar_value(rating, desc.sidecar.rating),
+            ));
+        }
+        if let Some(ref label) = desc.label {
+            rows.push((
+                fl!("meta-label"),
+                sidecar_value(label.clone(), desc.sidecar.label),
+            ));
+        }
+
+        if !rows.is_empty() {
+            sections.push(MetaSection {
+                title: fl!("meta-section-description"),
+                rows,
+            });
+        }
+    }
+
+    sections
+}
+
+/// Serialize the metadata into a human-readable multi-line string.
+pub fn meta_text(meta: &DocumentMeta, transform: Option<TransformState>) -> String {
+    let mut out = String::new();
+
+    for section in meta_sections(meta, transform) {
+        out.push_str(&section.title);
+        out.push('\n');
+        for (label, value) in section.rows {
+            out.push_str(&format!("{label}: {value}\n"));
+        }
+        out.push('\n');
+    }
+
+    out.push_str(&format!("{}: {}\n", fl!("meta-path"), meta.basic.file_path));
+    out
+}
+
+/// Describe the applied rotation and flips (e.g. "Rotated 90°, Flipped horizontally").
+fn transform_display(transform: TransformState) -> String {
+    if transform.is_identity() {
+        return fl!("transform-none");
+    }
+
+    let mut parts = Vec::with_capacity(3);
+    let degrees = transform.rotation.to_degrees();
+    if degrees != 0 {
+        parts.push(fl!("transform-rotated", degrees: degrees));
+    }
+    if transform.flip_h {
+        parts.push(fl!("transform-flipped-h"));
+    }
+    if transform.flip_v {
+        parts.push(fl!("transform-flipped-v"));
+    }
+    parts.join(", ")
+}
+
 /// Section header for grouping metadata.
 fn section_header(label: String) -> Element<'static, AppMessage> {
     text::body(label).into()
@@ -110,6 +240,15 @@ fn meta_row(label: String, value: String) -> Element<'static, AppMessage> {
         .into()
 }
 
+/// Mark a value that was read from a sidecar `.xmp` file.
+fn sidecar_value(value: String, from_sidecar: bool) -> String {
+    if from_sidecar {
+        fl!("meta-from-sidecar", value: value)
+    } else {
+        value
+    }
+}
+
 /// Helper for less prominent metadata (smaller text, e.g., file path).
 fn meta_row_small(label: String, value: String) -> Element<'static, AppMessage> {
     column::with_capacity(2)
@@ -122,26 +261,59 @@ fn meta_row_small(label: String, value: String) -> Element<'static, AppMessage>
 /// Panel header with title and action icon buttons.
 fn panel_header(model: &AppModel) -> Element<'static, AppMessage> {
     let has_doc = model.document.is_some();
+    let has_meta = model.metadata.is_some();
+    let is_vector = matches!(model.document, Some(DocumentContent::Vector(_)));
+    let is_raster = matches!(model.document, Some(DocumentContent::Raster(_)));
 
-    row::with_capacity(5)
+    row::with_capacity(7)
         .spacing(4)
         .align_y(cosmic::iced::Alignment::Center)
         .push(text::title4(fl!("panel-properties")))
         .push(horizontal_space().width(Length::Fill))
+        .push(
+            button::icon(icon::from_name("edit-copy-symbolic"))
+                .tooltip(fl!("action-copy-metadata"))
+                .on_press_maybe(has_meta.then_some(AppMessage::CopyMetadata)),
+        )
+        .push(
+            button::icon(icon::from_name("document-save-symbolic"))
+                .tooltip(fl!("action-export-metadata-json"))
+                .on_press_maybe(has_meta.then_some(AppMessage::ExportMetadataJson)),
+        )
         .push(
             button::icon(icon::from_name("image-x-generic-symbolic"))
                 .tooltip(fl!("action-set-wallpaper"))
                 .on_press_maybe(has_doc.then_some(AppMessage::SetAsWallpaper)),
         )
-        // .push(
-        //     button::icon(icon::from_name("system-run-symbolic"))
-        //         .on_press_maybe(has_doc.then_some(AppMessage::NoOp)) // TODO: Implement
-        //         .tooltip(fl!("action-open-with"))
-        // )
-        // .push(
-        //     button::icon(icon::from_name("system-file-manager-symbolic"))
-        //         .on_press_maybe(has_doc.then_some(AppMessage::NoOp)) // TODO: Implement
-        //         .tooltip(fl!("action-show-in-folder"))
-        // )
+        .push(
+            button::icon(icon::from_name("edit-cut-symbolic"))
+                .tooltip(fl!("action-crop-wallpaper"))
+                .on_press_maybe(is_raster.then_some(AppMessage::CropAsWallpaper)),
+        )
+        .push(
+            button::icon(icon::from_name("document-save-as-symbolic"))
+                .tooltip(fl!("action-export-png"))
+                .on_press_maybe(is_vector.then_some(AppMessage::OpenExportDialog)),
+        )
+        .push(
+            button::icon(icon::from_name("system-run-symbolic"))
+                .tooltip(fl!("action-open-with"))
+                .on_press_maybe(
+                    model
+                        .current_path
+                        .is_some()
+                        .then_some(AppMessage::OpenExternal),
+                ),
+        )
+        .push(
+            button::icon(icon::from_name("system-file-manager-symbolic"))
+                .tooltip(fl!("action-show-in-folder"))
+                .on_press_maybe(
+                    model
+                        .current_path
+                        .is_some()
+                        .then_some(AppMessage::RevealInFileManager),
+                ),
+        )
         .into()
 }
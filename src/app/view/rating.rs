@@ -0,0 +1,35 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// src/app/view/rating.rs
+//
+// Rating badge shown over the canvas while culling.
+
+use cosmic::iced::{Alignment, Length};
+use cosmic::widget::{container, text};
+use cosmic::Element;
+
+use crate::app::{AppMessage, AppModel};
+use crate::fl;
+
+/// Badge in the top-right corner of the canvas, if the image is rated or rejected.
+pub fn overlay(model: &AppModel) -> Option<Element<'_, AppMessage>> {
+    let desc = model.metadata.as_ref()?.descriptive.as_ref()?;
+    let label = match desc.rating {
+        _ if desc.rejected => fl!("meta-rejected"),
+        Some(stars) if stars > 0 => desc.rating_display()?,
+        _ => return None,
+    };
+
+    let card = container(text::body(label))
+        .padding([4, 8])
+        .class(cosmic::theme::Container::Card);
+
+    Some(
+        container(card)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding(12)
+            .align_x(Alignment::End)
+            .align_y(Alignment::Start)
+            .into(),
+    )
+}
@@ -0,0 +1,367 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// src/app/view/scrollbars.rs
+//
+// Scrollbars along the canvas edges showing and panning the visible region.
+
+use cosmic::{
+    Element, Renderer,
+    iced::{
+        Border, Color, Length, Point, Rectangle, Size,
+        advanced::{
+            Clipboard, Layout, Shell, Widget,
+            layout::{Limits, Node},
+            renderer::{Quad, Renderer as QuadRenderer},
+            widget::{Tree, tree},
+        },
+        event::{Event, Status},
+        mouse::{self, Button, Cursor},
+    },
+};
+
+use super::Fit;
+use crate::app::AppMessage;
+
+/// Thickness of a scrollbar in screen pixels.
+const THICKNESS: f32 = 8.0;
+/// Gap between a scrollbar and the canvas edge.
+const MARGIN: f32 = 2.0;
+/// Shortest thumb, so it stays grabbable on huge images.
+const MIN_THUMB: f32 = 24.0;
+const TRACK_COLOR: Color = Color::from_rgba(0.0, 0.0, 0.0, 0.25);
+const THUMB_COLOR: Color = Color::from_rgba(1.0, 1.0, 1.0, 0.6);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+/// Geometry of one scrollbar along its axis.
+#[derive(Debug, Clone, Copy)]
+struct Bar {
+    /// Screen rectangle of the track.
+    track: Rectangle,
+    /// Offset of the thumb from the track start.
+    thumb_start: f32,
+    thumb_len: f32,
+    /// Pan range is `-overflow / 2 ..= overflow / 2`.
+    overflow: f32,
+}
+
+impl Bar {
+    fn new(
+        track: Rectangle,
+        axis: Axis,
+        image_len: f32,
+        viewport_len: f32,
+        pan: f32,
+    ) -> Option<Self> {
+        let overflow = image_len - viewport_len;
+        if overflow <= 0.0 {
+            return None;
+        }
+
+        let track_len = along(axis, track.size());
+        let thumb_len =
+            (track_len * viewport_len / image_len).clamp(MIN_THUMB.min(track_len), track_len);
+        let position = ((pan + overflow / 2.0) / overflow).clamp(0.0, 1.0);
+
+        Some(Self {
+            track,
+            thumb_start: position * (track_len - thumb_len),
+            thumb_len,
+            overflow,
+        })
+    }
+
+    fn thumb(&self, axis: Axis) -> Rectangle {
+        match axis {
+            Axis::Horizontal => Rectangle::new(
+                Point::new(self.track.x + self.thumb_start, self.track.y),
+                Size::new(self.thumb_len, self.track.height),
+            ),
+            Axis::Vertical => Rectangle::new(
+                Point::new(self.track.x, self.track.y + self.thumb_start),
+                Size::new(self.track.width, self.thumb_len),
+            ),
+        }
+    }
+
+    /// Pan offset that puts the thumb start at `thumb_start` (relative to the track).
+    fn pan_at(&self, axis: Axis, thumb_start: f32) -> f32 {
+        let free = along(axis, self.track.size()) - self.thumb_len;
+        let position = if free > 0.0 {
+            (thumb_start / free).clamp(0.0, 1.0)
+        } else {
+            0.5
+        };
+        position * self.overflow - self.overflow / 2.0
+    }
+}
+
+/// Length of `size` along `axis`.
+fn along(axis: Axis, size: Size) -> f32 {
+    match axis {
+        Axis::Horizontal => size.width,
+        Axis::Vertical => size.height,
+    }
+}
+
+/// Thumb drag in progress: axis and cursor distance from the thumb start.
+#[derive(Debug, Default)]
+struct State {
+    drag: Option<(Axis, f32)>,
+}
+
+pub struct Scrollbars {
+    img_width: u32,
+    img_height: u32,
+    scale: f32,
+    fit: Fit,
+    pan_x: f32,
+    pan_y: f32,
+}
+
+impl Scrollbars {
+    /// Horizontal and vertical bar; None where the image fits the bounds.
+    fn bars(&self, bounds: &Rectangle) -> (Option<Bar>, Option<Bar>) {
+        let image = super::image_screen_rect(
+            *bounds,
+            self.img_width,
+            self.img_height,
+            self.scale,
+            self.fit,
+            self.pan_x,
+            self.pan_y,
+        );
+
+        // Leave the corner free when both bars are shown.
+        let inset = THICKNESS + 2.0 * MARGIN;
+        let horizontal = Rectangle::new(
+            Point::new(
+                bounds.x + MARGIN,
+                bounds.y + bounds.height - THICKNESS - MARGIN,
+            ),
+            Size::new(bounds.width - inset, THICKNESS),
+        );
+        let vertical = Rectangle::new(
+            Point::new(
+                bounds.x + bounds.width - THICKNESS - MARGIN,
+                bounds.y + MARGIN,
+            ),
+            Size::new(THICKNESS, bounds.height - inset),
+        );
+
+        (
+            Bar::new(
+                horizontal,
+                Axis::Horizontal,
+                image.width,
+                bounds.width,
+                self.pan_x,
+            ),
+            Bar::new(
+                vertical,
+                Axis::Vertical,
+                image.height,
+                bounds.height,
+                self.pan_y,
+            ),
+        )
+    }
+
+    fn bar(&self, bounds: &Rectangle, axis: Axis) -> Option<Bar> {
+        let (horizontal, vertical) = self.bars(bounds);
+        match axis {
+            Axis::Horizontal => horizontal,
+            Axis::Vertical => vertical,
+        }
+    }
+
+    /// Scrollbar track under the cursor.
+    fn hovered(&self, bounds: &Rectangle, cursor: Cursor) -> Option<(Axis, Bar)> {
+        let position = cursor.position_over(*bounds)?;
+        let (horizontal, vertical) = self.bars(bounds);
+        [(Axis::Horizontal, horizontal), (Axis::Vertical, vertical)]
+            .into_iter()
+            .find_map(|(axis, bar)| {
+                bar.filter(|bar| bar.track.contains(position))
+                    .map(|bar| (axis, bar))
+            })
+    }
+
+    /// Pan message for the thumb of `axis` starting at `thumb_start`.
+    fn pan_to(&self, axis: Axis, bar: &Bar, thumb_start: f32) -> AppMessage {
+        let pan = bar.pan_at(axis, thumb_start);
+        match axis {
+            Axis::Horizontal => AppMessage::PanTo {
+                x: pan,
+                y: self.pan_y,
+            },
+            Axis::Vertical => AppMessage::PanTo {
+                x: self.pan_x,
+                y: pan,
+            },
+        }
+    }
+}
+
+impl Widget<AppMessage, cosmic::Theme, Renderer> for Scrollbars {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(Length::Fill, Length::Fill)
+    }
+
+    fn layout(&self, _tree: &mut Tree, _renderer: &Renderer, limits: &Limits) -> Node {
+        Node::new(limits.max())
+    }
+
+    fn draw(
+        &self,
+        _tree: &Tree,
+        renderer: &mut Renderer,
+        _theme: &cosmic::Theme,
+        _style: &cosmic::iced::advanced::renderer::Style,
+        layout: Layout<'_>,
+        _cursor: Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        let (horizontal, vertical) = self.bars(&bounds);
+        let border = Border {
+            radius: (THICKNESS / 2.0).into(),
+            ..Border::default()
+        };
+
+        for (axis, bar) in [(Axis::Horizontal, horizontal), (Axis::Vertical, vertical)] {
+            let Some(bar) = bar else {
+                continue;
+            };
+            renderer.fill_quad(
+                Quad {
+                    bounds: bar.track,
+                    border,
+                    ..Quad::default()
+                },
+                TRACK_COLOR,
+            );
+            renderer.fill_quad(
+                Quad {
+                    bounds: bar.thumb(axis),
+                    border,
+                    ..Quad::default()
+                },
+                THUMB_COLOR,
+            );
+        }
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, AppMessage>,
+        _viewport: &Rectangle,
+    ) -> Status {
+        let bounds = layout.bounds();
+        let state = tree.state.downcast_mut::<State>();
+
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(Button::Left)) => {
+                let Some((axis, bar)) = self.hovered(&bounds, cursor) else {
+                    return Status::Ignored;
+                };
+                let Some(position) = cursor.position() else {
+                    return Status::Ignored;
+                };
+
+                let thumb = bar.thumb(axis);
+                let grab = if thumb.contains(position) {
+                    along(axis, Size::new(position.x - thumb.x, position.y - thumb.y))
+                } else {
+                    // Clicking the track centers the thumb on the cursor.
+                    let cursor_along = along(
+                        axis,
+                        Size::new(position.x - bar.track.x, position.y - bar.track.y),
+                    );
+                    shell.publish(self.pan_to(axis, &bar, cursor_along - bar.thumb_len / 2.0));
+                    bar.thumb_len / 2.0
+                };
+                state.drag = Some((axis, grab));
+                Status::Captured
+            }
+            Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                let Some((axis, grab)) = state.drag else {
+                    return Status::Ignored;
+                };
+                let Some(bar) = self.bar(&bounds, axis) else {
+                    return Status::Ignored;
+                };
+                let cursor_along = along(
+                    axis,
+                    Size::new(position.x - bar.track.x, position.y - bar.track.y),
+                );
+                shell.publish(self.pan_to(axis, &bar, cursor_along - grab));
+                Status::Captured
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(Button::Left)) if state.drag.is_some() => {
+                state.drag = None;
+                Status::Captured
+            }
+            _ => Status::Ignored,
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        if tree.state.downcast_ref::<State>().drag.is_some() {
+            mouse::Interaction::Grabbing
+        } else if self.hovered(&layout.bounds(), cursor).is_some() {
+            mouse::Interaction::Pointer
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+}
+
+impl<'a> From<Scrollbars> for Element<'a, AppMessage> {
+    fn from(scrollbars: Scrollbars) -> Self {
+        Self::new(scrollbars)
+    }
+}
+
+/// Scrollbars for an image of the given size (scale 0.0 = fit).
+pub fn scrollbars(
+    img_width: u32,
+    img_height: u32,
+    scale: f32,
+    fit: Fit,
+    pan_x: f32,
+    pan_y: f32,
+) -> Scrollbars {
+    Scrollbars {
+        img_width,
+        img_height,
+        scale,
+        fit,
+        pan_x,
+        pan_y,
+    }
+}
@@ -0,0 +1,365 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// src/app/view/settings.rs
+//
+// Settings panel content for COSMIC context drawer.
+
+use std::sync::LazyLock;
+
+use cosmic::iced::Length;
+use cosmic::widget::{button, column, dropdown, horizontal_space, row, text, text_input, toggler};
+use cosmic::Element;
+
+use crate::app::document::meta::format_file_size;
+use crate::app::{AppMessage, AppModel};
+use crate::config::{AppConfig, CanvasBackground, CropGrid, DisplayFilter};
+use crate::constant::{
+    NAV_PANEL_WIDTH_PRESETS, PAN_STEP_PRESETS, THUMBNAIL_WIDTH_PRESETS, ZOOM_SENSITIVITY_PRESETS,
+};
+use crate::fl;
+
+/// Pan distance dropdown labels, in `PAN_STEP_PRESETS` order.
+static PAN_STEP_LABELS: LazyLock<Vec<String>> = LazyLock::new(|| {
+    PAN_STEP_PRESETS
+        .iter()
+        .map(|step| fl!("settings-pan-step-pixels", pixels: step.to_string()))
+        .collect()
+});
+
+/// Wheel zoom step dropdown labels, in `ZOOM_SENSITIVITY_PRESETS` order.
+static ZOOM_SENSITIVITY_LABELS: LazyLock<Vec<String>> = LazyLock::new(|| {
+    ZOOM_SENSITIVITY_PRESETS
+        .iter()
+        .map(|step| {
+            let percent = (step * 100.0).round() as i32;
+            fl!("settings-zoom-sensitivity-percent", percent: percent)
+        })
+        .collect()
+});
+
+/// Thumbnail width dropdown labels, in `THUMBNAIL_WIDTH_PRESETS` order.
+static THUMBNAIL_WIDTH_LABELS: LazyLock<Vec<String>> = LazyLock::new(|| {
+    THUMBNAIL_WIDTH_PRESETS
+        .iter()
+        .map(|width| fl!("settings-pixels", pixels: width.to_string()))
+        .collect()
+});
+
+/// Navigation panel width dropdown labels, in `NAV_PANEL_WIDTH_PRESETS` order.
+static NAV_PANEL_WIDTH_LABELS: LazyLock<Vec<String>> = LazyLock::new(|| {
+    NAV_PANEL_WIDTH_PRESETS
+        .iter()
+        .map(|width| fl!("settings-pixels", pixels: width.to_string()))
+        .collect()
+});
+
+/// Filter dropdown labels, in `DisplayFilter::ALL` order.
+static FILTER_LABELS: LazyLock<Vec<String>> = LazyLock::new(|| {
+    DisplayFilter::ALL
+        .iter()
+        .map(|filter| match filter {
+            DisplayFilter::Linear => fl!("settings-filter-linear"),
+            DisplayFilter::Nearest => fl!("settings-filter-nearest"),
+            DisplayFilter::Auto => fl!("settings-filter-auto"),
+        })
+        .collect()
+});
+
+/// Crop guide dropdown labels, in `CropGrid::ALL` order.
+static CROP_GRID_LABELS: LazyLock<Vec<String>> = LazyLock::new(|| {
+    CropGrid::ALL
+        .iter()
+        .map(|grid| match grid {
+            CropGrid::None => fl!("settings-crop-grid-none"),
+            CropGrid::Thirds => fl!("settings-crop-grid-thirds"),
+            CropGrid::GoldenRatio => fl!("settings-crop-grid-golden"),
+        })
+        .collect()
+});
+
+/// Canvas background dropdown labels, `CanvasBackground::PRESETS` then custom.
+static BACKGROUND_LABELS: LazyLock<Vec<String>> = LazyLock::new(|| {
+    vec![
+        fl!("settings-background-theme"),
+        fl!("settings-background-black"),
+        fl!("settings-background-white"),
+        fl!("settings-background-gray"),
+        fl!("settings-background-custom"),
+    ]
+});
+
+/// Build the settings panel view.
+pub fn view(model: &AppModel, config: &AppConfig) -> Element<'static, AppMessage> {
+    let mut content = column::with_capacity(8).spacing(8);
+
+    content = content
+        .push(text::title4(fl!("panel-settings")))
+        .push(section_header(fl!("settings-section-view")))
+        .push(setting_toggle(
+            fl!("settings-lock-view"),
+            model.lock_view,
+            AppMessage::ToggleLockView,
+        ))
+        .push(setting_toggle(
+            fl!("settings-fit-upscale"),
+            config.fit_upscale,
+            AppMessage::ToggleFitUpscale,
+        ))
+        .push(setting_toggle(
+            fl!("settings-inertial-panning"),
+            config.inertial_panning,
+            AppMessage::ToggleInertialPanning,
+        ))
+        .push(
+            row::with_capacity(3)
+                .spacing(8)
+                .align_y(cosmic::iced::Alignment::Center)
+                .push(text::body(fl!("settings-pan-step")))
+                .push(horizontal_space().width(Length::Fill))
+                .push(dropdown(
+                    PAN_STEP_LABELS.as_slice(),
+                    PAN_STEP_PRESETS
+                        .iter()
+                        .position(|step| (step - config.pan_step).abs() < f32::EPSILON),
+                    |index| AppMessage::SetPanStep(PAN_STEP_PRESETS[index]),
+                )),
+        )
+        .push(
+            row::with_capacity(3)
+                .spacing(8)
+                .align_y(cosmic::iced::Alignment::Center)
+                .push(text::body(fl!("settings-zoom-sensitivity")))
+                .push(horizontal_space().width(Length::Fill))
+                .push(dropdown(
+                    ZOOM_SENSITIVITY_LABELS.as_slice(),
+                    ZOOM_SENSITIVITY_PRESETS
+                        .iter()
+                        .position(|step| (step - config.zoom_sensitivity).abs() < f32::EPSILON),
+                    |index| AppMessage::SetZoomSensitivity(ZOOM_SENSITIVITY_PRESETS[index]),
+                )),
+        )
+        .push(
+            row::with_capacity(3)
+                .spacing(8)
+                .align_y(cosmic::iced::Alignment::Center)
+                .push(text::body(fl!("settings-filter")))
+                .push(horizontal_space().width(Length::Fill))
+                .push(dropdown(
+                    FILTER_LABELS.as_slice(),
+                    DisplayFilter::ALL
+                        .iter()
+                        .position(|f| *f == config.filter_method),
+                    |index| AppMessage::SetFilterMethod(DisplayFilter::ALL[index]),
+                )),
+        )
+        .push(
+            row::with_capacity(3)
+                .spacing(8)
+                .align_y(cosmic::iced::Alignment::Center)
+                .push(text::body(fl!("settings-crop-grid")))
+                .push(horizontal_space().width(Length::Fill))
+                .push(dropdown(
+                    CROP_GRID_LABELS.as_slice(),
+                    CropGrid::ALL.iter().position(|g| *g == config.crop_grid),
+                    |index| AppMessage::SetCropGrid(CropGrid::ALL[index]),
+                )),
+        )
+        .push(
+            row::with_capacity(3)
+                .spacing(8)
+                .align_y(cosmic::iced::Alignment::Center)
+                .push(text::body(fl!("settings-thumbnail-width")))
+                .push(horizontal_space().width(Length::Fill))
+                .push(dropdown(
+                    THUMBNAIL_WIDTH_LABELS.as_slice(),
+                    THUMBNAIL_WIDTH_PRESETS
+                        .iter()
+                        .position(|width| (width - config.thumbnail_width).abs() < f32::EPSILON),
+                    |index| AppMessage::SetThumbnailWidth(THUMBNAIL_WIDTH_PRESETS[index]),
+                )),
+        )
+        .push(
+            row::with_capacity(3)
+                .spacing(8)
+                .align_y(cosmic::iced::Alignment::Center)
+                .push(text::body(fl!("settings-nav-panel-width")))
+                .push(horizontal_space().width(Length::Fill))
+                .push(dropdown(
+                    NAV_PANEL_WIDTH_LABELS.as_slice(),
+                    NAV_PANEL_WIDTH_PRESETS
+                        .iter()
+                        .position(|width| (width - config.nav_panel_width).abs() < f32::EPSILON),
+                    |index| AppMessage::SetNavPanelWidth(NAV_PANEL_WIDTH_PRESETS[index]),
+                )),
+        )
+        .push(background_row(model, config))
+        .push_maybe(
+            matches!(config.canvas_background, CanvasBackground::Custom(_)).then(|| {
+                text_input("#808080", model.canvas_color_input.clone())
+                    .label(fl!("settings-background-color"))
+                    .on_input(AppMessage::SetCanvasColor)
+            }),
+        )
+        .push(setting_toggle(
+            fl!("settings-svg-system-fonts"),
+            config.svg_system_fonts,
+            AppMessage::ToggleSvgSystemFonts,
+        ))
+        .push(setting_toggle(
+            fl!("settings-pdf-transparent"),
+            config.pdf_transparent_background,
+            AppMessage::TogglePdfTransparentBackground,
+        ))
+        .push(section_header(fl!("settings-section-navigation")))
+        .push(setting_toggle(
+            fl!("settings-sort-by-date"),
+            model.sort_by_capture_date,
+            AppMessage::ToggleSortByDate,
+        ))
+        .push(setting_toggle(
+            fl!("settings-show-hidden"),
+            model.show_hidden,
+            AppMessage::ToggleShowHidden,
+        ))
+        .push(setting_toggle(
+            fl!("settings-cross-folder"),
+            model.cross_folder_navigation,
+            AppMessage::ToggleCrossFolderNavigation,
+        ))
+        .push(setting_toggle(
+            fl!("settings-restore-session"),
+            config.restore_last_session,
+            AppMessage::ToggleRestoreSession,
+        ))
+        .push(setting_row(
+            fl!("settings-default-folder"),
+            config.default_image_dir.as_ref().map_or_else(
+                || fl!("settings-default-folder-none"),
+                |dir| dir.display().to_string(),
+            ),
+        ))
+        .push(
+            button::standard(fl!("action-set-default-folder")).on_press_maybe(
+                model
+                    .current_path
+                    .is_some()
+                    .then_some(AppMessage::SetDefaultFolder),
+            ),
+        )
+        .push(section_header(fl!("settings-section-files")))
+        .push(
+            text_input(
+                fl!("settings-transfer-dir-placeholder"),
+                model
+                    .transfer_dir
+                    .as_ref()
+                    .map(|dir| dir.display().to_string())
+                    .unwrap_or_default(),
+            )
+            .label(fl!("settings-transfer-dir"))
+            .on_input(AppMessage::SetTransferDir),
+        )
+        .push(transfer_buttons(model))
+        .push(section_header(fl!("settings-section-editor")))
+        .push(
+            text_input(
+                fl!("settings-external-editor-placeholder"),
+                model.external_editor_input.clone(),
+            )
+            .label(fl!("settings-external-editor"))
+            .on_input(AppMessage::SetExternalEditor)
+            .on_submit(|_| AppMessage::SaveExternalEditor),
+        )
+        .push(section_header(fl!("settings-section-cache")))
+        .push(setting_row(
+            fl!("settings-cache-size"),
+            format_file_size(model.cache_size),
+        ));
+
+    if let Some(freed) = model.cache_freed {
+        content = content.push(text::caption(fl!(
+            "settings-cache-freed",
+            size: format_file_size(freed)
+        )));
+    }
+
+    content = content.push(
+        button::standard(fl!("action-clear-cache"))
+            .on_press_maybe((model.cache_size > 0).then_some(AppMessage::ClearCache)),
+    );
+
+    content.into()
+}
+
+/// Canvas background dropdown; choosing custom keeps the last entered color.
+fn background_row(model: &AppModel, config: &AppConfig) -> Element<'static, AppMessage> {
+    let custom = CanvasBackground::parse_hex(&model.canvas_color_input).unwrap_or([128; 3]);
+    let selected = CanvasBackground::PRESETS
+        .iter()
+        .position(|background| *background == config.canvas_background)
+        .unwrap_or(CanvasBackground::PRESETS.len());
+
+    row::with_capacity(3)
+        .spacing(8)
+        .align_y(cosmic::iced::Alignment::Center)
+        .push(text::body(fl!("settings-background")))
+        .push(horizontal_space().width(Length::Fill))
+        .push(dropdown(
+            BACKGROUND_LABELS.as_slice(),
+            Some(selected),
+            move |index| {
+                AppMessage::SetCanvasBackground(
+                    CanvasBackground::PRESETS
+                        .get(index)
+                        .copied()
+                        .unwrap_or(CanvasBackground::Custom(custom)),
+                )
+            },
+        ))
+        .into()
+}
+
+/// Copy/move buttons for the destination folder.
+fn transfer_buttons(model: &AppModel) -> Element<'static, AppMessage> {
+    let target = model
+        .transfer_dir
+        .clone()
+        .filter(|_| model.current_path.is_some());
+
+    row::with_capacity(2)
+        .spacing(8)
+        .push(
+            button::standard(fl!("action-copy-to-folder"))
+                .on_press_maybe(target.clone().map(AppMessage::CopyTo)),
+        )
+        .push(
+            button::standard(fl!("action-move-to-folder"))
+                .on_press_maybe(target.map(AppMessage::MoveTo)),
+        )
+        .into()
+}
+
+/// Section header for grouping settings.
+fn section_header(label: String) -> Element<'static, AppMessage> {
+    text::body(label).into()
+}
+
+/// Helper to create a settings row with a toggle switch.
+fn setting_toggle(label: String, value: bool, message: AppMessage) -> Element<'static, AppMessage> {
+    row::with_capacity(3)
+        .spacing(8)
+        .align_y(cosmic::iced::Alignment::Center)
+        .push(text::body(label))
+        .push(horizontal_space().width(Length::Fill))
+        .push(toggler(value).on_toggle(move |_| message.clone()))
+        .into()
+}
+
+/// Helper to create a label-value settings row.
+fn setting_row(label: String, value: String) -> Element<'static, AppMessage> {
+    row::with_capacity(3)
+        .spacing(8)
+        .push(text::body(label))
+        .push(horizontal_space().width(Length::Fill))
+        .push(text::body(value))
+        .into()
+}
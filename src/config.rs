@@ -4,7 +4,121 @@
 // Global configuration for the application with cosmic-config support.
 
 use cosmic::cosmic_config::{self, CosmicConfigEntry, cosmic_config_derive::CosmicConfigEntry};
-use std::path::PathBuf;
+use cosmic::iced::Color;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::app::document::DocumentKind;
+use crate::app::model::ViewMode;
+use crate::constant::FOLDER_PREFS_LIMIT;
+
+/// Interpolation used when drawing the document.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisplayFilter {
+    /// Smooth interpolation, best for photos.
+    #[default]
+    Linear,
+    /// Nearest neighbor, keeps pixel art crisp.
+    Nearest,
+    /// Linear, switching to nearest neighbor when zoomed in far.
+    Auto,
+}
+
+impl DisplayFilter {
+    pub const ALL: [Self; 3] = [Self::Linear, Self::Nearest, Self::Auto];
+
+    /// The next filter in `ALL` order (wraps around).
+    #[must_use]
+    pub fn next(self) -> Self {
+        match self {
+            Self::Linear => Self::Nearest,
+            Self::Nearest => Self::Auto,
+            Self::Auto => Self::Linear,
+        }
+    }
+}
+
+/// Color of the canvas around the document (letterbox margins).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CanvasBackground {
+    /// Window background of the current theme.
+    #[default]
+    Theme,
+    Black,
+    White,
+    /// Neutral 50% gray.
+    Gray,
+    /// RGB color chosen by the user.
+    Custom([u8; 3]),
+}
+
+impl CanvasBackground {
+    /// Fixed choices; `Custom` follows them in the settings dropdown.
+    pub const PRESETS: [Self; 4] = [Self::Theme, Self::Black, Self::White, Self::Gray];
+
+    /// Color to paint, or None to keep the theme background.
+    #[must_use]
+    pub fn color(self) -> Option<Color> {
+        match self {
+            Self::Theme => None,
+            Self::Black => Some(Color::BLACK),
+            Self::White => Some(Color::WHITE),
+            Self::Gray => Some(Color::from_rgb8(128, 128, 128)),
+            Self::Custom([r, g, b]) => Some(Color::from_rgb8(r, g, b)),
+        }
+    }
+
+    /// Parse a `#rrggbb` (or `rrggbb`) hex color.
+    #[must_use]
+    pub fn parse_hex(text: &str) -> Option<[u8; 3]> {
+        let hex = text.trim().trim_start_matches('#');
+        if hex.len() != 6 || !hex.is_ascii() {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        Some([channel(0)?, channel(2)?, channel(4)?])
+    }
+
+    /// Format an RGB color as `#rrggbb`.
+    #[must_use]
+    pub fn hex([r, g, b]: [u8; 3]) -> String {
+        format!("#{r:02x}{g:02x}{b:02x}")
+    }
+}
+
+/// Composition guide drawn inside the crop selection.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CropGrid {
+    None,
+    /// Rule of thirds (3x3 grid).
+    #[default]
+    Thirds,
+    /// Lines at the golden section (0.382 / 0.618).
+    GoldenRatio,
+}
+
+impl CropGrid {
+    pub const ALL: [Self; 3] = [Self::None, Self::Thirds, Self::GoldenRatio];
+
+    /// Positions of the guide lines as fractions of the selection width and height.
+    #[must_use]
+    pub fn fractions(self) -> &'static [f32] {
+        match self {
+            Self::None => &[],
+            Self::Thirds => &[1.0 / 3.0, 2.0 / 3.0],
+            Self::GoldenRatio => &[0.381_966, 0.618_034],
+        }
+    }
+}
+
+/// View preferences remembered for a folder.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FolderPrefs {
+    pub view_mode: ViewMode,
+    pub sort_by_capture_date: bool,
+    pub nav_bar_visible: bool,
+    pub context_drawer_visible: bool,
+}
 
 /// Global configuration for the application.
 #[derive(Debug, Clone, CosmicConfigEntry, PartialEq)]
@@ -18,14 +132,58 @@ pub struct AppConfig {
     pub context_drawer_visible: bool,
     /// Zoom step multiplier for keyboard shortcuts (1.1 = 10% increase per step).
     pub scale_step: f32,
+    /// Zoom change per mouse wheel notch (0.1 = 10%); trackpads zoom proportionally.
+    pub zoom_sensitivity: f32,
     /// Pan distance in pixels per arrow key press.
     pub pan_step: f32,
     /// Minimum zoom level (0.1 = 10% of original size).
     pub min_scale: f32,
     /// Maximum zoom level (8.0 = 800% of original size).
     pub max_scale: f32,
-    /// Show 3x3 grid during crop selection.
-    pub crop_show_grid: bool,
+    /// Maximum zoom level for vector graphics (re-rendered crisply at any size).
+    pub vector_max_scale: f32,
+    /// Maximum side length in pixels of display textures (larger images are downscaled).
+    pub max_texture_size: u32,
+    /// Ask before decoding raster images larger than this (megapixels, 0 = never ask).
+    pub max_decode_megapixels: u32,
+    /// Width in pixels of page thumbnails in the navigation panel.
+    pub thumbnail_width: f32,
+    /// Maximum width in pixels of the page navigation panel.
+    pub nav_panel_width: f32,
+    /// Composition guide drawn inside the crop selection.
+    pub crop_grid: CropGrid,
+    /// Continue panning with decaying velocity after a quick drag.
+    pub inertial_panning: bool,
+    /// Enlarge images smaller than the window in Fit mode (off = show them at 100%).
+    pub fit_upscale: bool,
+    /// Interpolation used when drawing the document.
+    pub filter_method: DisplayFilter,
+    /// Color of the canvas around the document.
+    pub canvas_background: CanvasBackground,
+    /// Sort folder entries by EXIF capture date instead of file name.
+    pub sort_by_capture_date: bool,
+    /// Include hidden (dot) files when navigating a folder.
+    pub show_hidden: bool,
+    /// Continue into the next/previous sibling folder at the end of a folder.
+    pub cross_folder_navigation: bool,
+    /// Reopen the last viewed file when started without arguments.
+    pub restore_last_session: bool,
+    /// File shown when the previous session ended.
+    pub last_opened_path: Option<PathBuf>,
+    /// Recently opened files, most recent first.
+    pub recent_files: Vec<PathBuf>,
+    /// Window size (width, height) when the previous session ended.
+    pub window_size: Option<(f32, f32)>,
+    /// Keep zoom and pan when navigating between documents (compare mode).
+    pub lock_view_on_navigate: bool,
+    /// Load system fonts for text in SVG documents (slow on first use).
+    pub svg_system_fonts: bool,
+    /// Render PDF pages without the white paper background.
+    pub pdf_transparent_background: bool,
+    /// External editor command (program plus optional arguments, e.g. "gimp").
+    pub external_editor: Option<String>,
+    /// View preferences per folder, most recently left first.
+    pub folder_prefs: Vec<(PathBuf, FolderPrefs)>,
 }
 
 impl Default for AppConfig {
@@ -35,10 +193,59 @@ impl Default for AppConfig {
             nav_bar_visible: false,
             context_drawer_visible: false,
             scale_step: 1.1,
+            zoom_sensitivity: 0.1,
             pan_step: 50.0,
             min_scale: 0.1,
             max_scale: 8.0,
-            crop_show_grid: true,
+            vector_max_scale: 32.0,
+            max_texture_size: 8192,
+            max_decode_megapixels: 500,
+            thumbnail_width: 100.0,
+            nav_panel_width: 200.0,
+            crop_grid: CropGrid::default(),
+            inertial_panning: true,
+            fit_upscale: true,
+            filter_method: DisplayFilter::default(),
+            canvas_background: CanvasBackground::default(),
+            sort_by_capture_date: false,
+            show_hidden: false,
+            cross_folder_navigation: false,
+            restore_last_session: true,
+            last_opened_path: None,
+            recent_files: Vec::new(),
+            window_size: None,
+            lock_view_on_navigate: false,
+            svg_system_fonts: true,
+            pdf_transparent_background: false,
+            external_editor: None,
+            folder_prefs: Vec::new(),
+        }
+    }
+}
+
+impl AppConfig {
+    /// Zoom limits (min, max) for the given document kind.
+    pub fn scale_limits(&self, kind: Option<DocumentKind>) -> (f32, f32) {
+        match kind {
+            Some(DocumentKind::Vector) => {
+                (self.min_scale, self.vector_max_scale.max(self.min_scale))
+            }
+            _ => (self.min_scale, self.max_scale),
         }
     }
+
+    /// Preferences remembered for `folder`, if any.
+    pub fn folder_prefs(&self, folder: &Path) -> Option<FolderPrefs> {
+        self.folder_prefs
+            .iter()
+            .find(|(dir, _)| dir == folder)
+            .map(|(_, prefs)| *prefs)
+    }
+
+    /// Remember preferences for `folder`, dropping the oldest beyond the limit.
+    pub fn set_folder_prefs(&mut self, folder: &Path, prefs: FolderPrefs) {
+        self.folder_prefs.retain(|(dir, _)| dir != folder);
+        self.folder_prefs.insert(0, (folder.to_path_buf(), prefs));
+        self.folder_prefs.truncate(FOLDER_PREFS_LIMIT);
+    }
 }
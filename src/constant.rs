@@ -9,6 +9,12 @@ pub const MINUTES_PER_DEGREE: f64 = 60.0;
 /// Seconds per degree (GPS coordinate conversion: DMS to decimal degrees).
 pub const SECONDS_PER_DEGREE: f64 = 3600.0;
 
+/// Millimeters per inch (EXIF resolution to physical measurements).
+pub const MM_PER_INCH: f64 = 25.4;
+
+/// Centimeters per inch (EXIF resolution unit conversion).
+pub const CM_PER_INCH: f64 = 2.54;
+
 /// Minimum pixmap size for SVG rendering (prevents zero-size pixmaps).
 pub const MIN_PIXMAP_SIZE: u32 = 1;
 
@@ -18,8 +24,53 @@ pub const SCALE_EPSILON: f32 = 0.0001;
 /// Tolerance for offset comparisons (float precision in pan synchronization).
 pub const OFFSET_EPSILON: f32 = 0.01;
 
-/// Maximum width in pixels for page navigation thumbnails.
-pub const THUMBNAIL_MAX_WIDTH: f32 = 100.0;
+/// Fraction of the inertial pan velocity that remains after one second.
+pub const INERTIA_FRICTION: f32 = 0.02;
+
+/// Inertial panning stops below this speed (pixels per second).
+pub const INERTIA_MIN_SPEED: f32 = 30.0;
+
+/// A drag must end within this many milliseconds of the last move to glide.
+pub const INERTIA_RELEASE_WINDOW_MS: u64 = 80;
+
+/// Weight of the newest cursor sample when smoothing drag velocity.
+pub const INERTIA_SMOOTHING: f32 = 0.6;
+
+/// Margin around the visible region covered by a high-resolution tile,
+/// as a fraction of the viewport size on each side.
+pub const TILE_MARGIN: f32 = 0.5;
+
+/// Wheel movement in lines that turns one page while a document fits the window.
+pub const PAGE_SCROLL_THRESHOLD: f32 = 1.0;
+
+/// Pixel scroll deltas (touchpads) per wheel line.
+pub const SCROLL_PIXELS_PER_LINE: f32 = 40.0;
+
+/// Seconds before an error banner is dismissed automatically.
+pub const ERROR_DISMISS_SECS: u64 = 5;
+
+/// Page thumbnail widths in pixels offered in the settings panel.
+pub const THUMBNAIL_WIDTH_PRESETS: [f32; 4] = [100.0, 150.0, 200.0, 300.0];
+
+/// Page navigation panel widths in pixels offered in the settings panel.
+pub const NAV_PANEL_WIDTH_PRESETS: [f32; 4] = [200.0, 250.0, 300.0, 400.0];
+
+/// Raster images with at least this many pixels show a preview while the
+/// full resolution is decoded in the background.
+pub const PROGRESSIVE_MIN_PIXELS: u64 = 40_000_000;
+
+/// Largest reduced-resolution copy stored in a TIFF that is decoded as the
+/// preview of a progressive load.
+pub const PREVIEW_MAX_PIXELS: u64 = 4_000_000;
+
+/// Fraction of the darkest and brightest pixels ignored by auto levels.
+pub const AUTO_LEVELS_CLIP: f64 = 0.005;
+
+/// Bounding box in pixels for gallery view thumbnails.
+pub const GALLERY_THUMBNAIL_SIZE: u32 = 160;
+
+/// Command-line argument that reads the document from standard input.
+pub const STDIN_ARG: &str = "-";
 
 /// Cache directory name under ~/.cache/ for thumbnail storage.
 pub const CACHE_DIR: &str = "noctua";
@@ -27,8 +78,41 @@ pub const CACHE_DIR: &str = "noctua";
 /// File extension for cached thumbnails.
 pub const THUMBNAIL_EXT: &str = "png";
 
+/// Subdirectory of the cache holding the current cropped wallpaper.
+pub const WALLPAPER_DIR: &str = "wallpaper";
+
+/// Subdirectory of the cache holding the image last sent to the printer.
+pub const PRINT_DIR: &str = "print";
+
+/// Screen aspect ratio assumed when the monitor size is unknown.
+pub const DEFAULT_SCREEN_ASPECT: f32 = 16.0 / 9.0;
+
+/// Crop selection nudge with Shift held, in image pixels (1 without Shift).
+pub const CROP_NUDGE_LARGE_STEP: f32 = 10.0;
+
 /// PDF page render quality multiplier (2.0 = double resolution for sharp display).
 pub const PDF_RENDER_QUALITY: f64 = 2.0;
 
+/// DjVu thumbnail bounding box in pixels (rendered via ddjvu).
+pub const DJVU_THUMBNAIL_SIZE: u32 = 200;
+
 /// PDF thumbnail size multiplier (0.25 = 25% for fast preview generation).
 pub const PDF_THUMBNAIL_SIZE: f64 = 0.25;
+
+/// Zoom levels offered in the footer zoom menu (besides Fit).
+pub const ZOOM_PRESETS: [f32; 5] = [0.25, 0.5, 1.0, 2.0, 4.0];
+
+/// Pan distances in pixels offered in the settings panel.
+pub const PAN_STEP_PRESETS: [f32; 5] = [10.0, 25.0, 50.0, 100.0, 200.0];
+
+/// Wheel zoom steps per notch offered in the settings panel (0.1 = 10%).
+pub const ZOOM_SENSITIVITY_PRESETS: [f32; 4] = [0.05, 0.1, 0.2, 0.3];
+
+/// Number of folders whose view preferences are remembered.
+pub const FOLDER_PREFS_LIMIT: usize = 100;
+
+/// Number of recently opened files offered for reopening.
+pub const RECENT_FILES_LIMIT: usize = 10;
+
+/// Zoom level from which `DisplayFilter::Auto` switches to nearest neighbor.
+pub const NEAREST_FILTER_MIN_ZOOM: f32 = 4.0;
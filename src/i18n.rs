@@ -18,6 +18,27 @@ pub fn init(requested_languages: &[LanguageIdentifier]) {
     }
 }
 
+/// Parse a language tag from the command line.
+///
+/// Returns None (with a warning) if the tag is invalid or no translation exists for it.
+pub fn language_override(tag: &str) -> Option<LanguageIdentifier> {
+    let language: LanguageIdentifier = match tag.parse() {
+        Ok(language) => language,
+        Err(why) => {
+            eprintln!("invalid language tag {tag:?}: {why}");
+            return None;
+        }
+    };
+
+    let available = localizer().available_languages().unwrap_or_default();
+    if available.iter().any(|l| l.language == language.language) {
+        Some(language)
+    } else {
+        eprintln!("no translation for language {language}, using the desktop language");
+        None
+    }
+}
+
 // Get the `Localizer` to be used for localizing this library.
 #[must_use]
 pub fn localizer() -> Box<dyn Localizer> {
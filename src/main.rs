@@ -11,30 +11,65 @@ mod i18n;
 use anyhow::Result;
 use clap::Parser;
 use cosmic::app::Settings;
+use cosmic::cosmic_config::{self, CosmicConfigEntry};
+use cosmic::iced::Size;
+use cosmic::Application;
 use crate::app::Noctua;
+use crate::config::AppConfig;
 
 #[derive(Parser, Debug, Clone)]
 #[command(version, about)]
 pub struct Args {
-    /// File to open on startup
+    /// File to open on startup ("-" reads from stdin, http(s) URLs are downloaded);
+    /// several files are navigated in the given order instead of their folder
     #[arg(value_name = "FILE")]
-    pub file: Option<std::path::PathBuf>,
+    pub files: Vec<std::path::PathBuf>,
 
-    /// UI language (e.g. "en", "de")
-    #[arg(short, long, default_value = "en")]
-    pub language: String,
+    /// Start in this directory for this session (overrides the default folder)
+    #[arg(long, value_name = "DIR")]
+    pub start_dir: Option<std::path::PathBuf>,
+
+    /// Generate cached thumbnails for all documents below DIR and exit (no GUI)
+    #[arg(long, value_name = "DIR")]
+    pub generate_thumbnails: Option<std::path::PathBuf>,
+
+    /// Show the debug overlay (document kind, scale, pan, decode time)
+    #[arg(long)]
+    pub debug: bool,
+
+    /// UI language (e.g. "en", "de"), overrides the desktop language
+    #[arg(short, long)]
+    pub language: Option<String>,
 }
 
 fn main() -> Result<()> {
-    // Get the system's preferred languages.
-    let requested_languages = i18n_embed::DesktopLanguageRequester::requested_languages();
+    let args = Args::parse();
+
+    // Get the system's preferred languages; --language takes precedence.
+    let mut requested_languages = i18n_embed::DesktopLanguageRequester::requested_languages();
+    if let Some(language) = args.language.as_deref().and_then(i18n::language_override) {
+        requested_languages.insert(0, language);
+    }
 
     // Enable localizations to be applied.
     i18n::init(&requested_languages);
 
     env_logger::init();
-    let args = Args::parse();
 
-    cosmic::app::run::<Noctua>(Settings::default(), app::Flags::Args(args))
-        .map_err(|e| anyhow::anyhow!(e))
+    // Headless mode: pre-warm the thumbnail cache without starting the GUI.
+    if let Some(dir) = args.generate_thumbnails.as_deref() {
+        return app::document::batch::generate_thumbnails(dir);
+    }
+
+    // Reopen the window at the size it had when the last session ended.
+    let mut settings = Settings::default();
+    if let Some((width, height)) = cosmic_config::Config::new(Noctua::APP_ID, AppConfig::VERSION)
+        .ok()
+        .and_then(|handler| AppConfig::get_entry(&handler).ok())
+        .and_then(|config| config.window_size)
+    {
+        settings = settings.size(Size::new(width, height));
+    }
+
+    cosmic::app::run::<Noctua>(settings, app::Flags::Args(args)).map_err(|e| anyhow::anyhow!(e))
 }